@@ -0,0 +1,430 @@
+use crate::{validation, SegmentKind, Sketch, SketchCycle, SketchSegment};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-9;
+
+/// A selection of corners of a [`Sketch`]
+///
+/// A corner is the point where two segments of a cycle meet. Only corners
+/// between two lines are considered. Corners that are adjacent to an arc, or
+/// where both lines go in the same direction, are never selected.
+#[derive(Clone, Debug)]
+pub enum Corners {
+    /// All corners
+    All,
+
+    /// All corners that point away from the interior of the sketch
+    Convex,
+
+    /// All corners that point into the interior of the sketch
+    Concave,
+
+    /// The corners at the given points
+    ///
+    /// Each point is identified by the index of its cycle, followed by its
+    /// index within that cycle.
+    Indices(Vec<[usize; 2]>),
+}
+
+impl Sketch {
+    /// Round the selected corners
+    ///
+    /// Each corner is replaced by an arc of the given radius, which is tangent
+    /// to both of the lines that meet at the corner.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the sketch is not valid (see [`Sketch::validate`]), or if a
+    /// line is too short for the arcs at its ends.
+    pub fn fillet(&self, radius: f64, corners: &Corners) -> Self {
+        self.modify_corners(corners, |corner| {
+            let distance = radius / (corner.angle() / 2.).tan();
+            let [a, b] = corner.tangent_points(distance);
+
+            let left = [-corner.incoming[1], corner.incoming[0]];
+            let side = if corner.turns_left() { 1. } else { -1. };
+            let center = [
+                a[0] + left[0] * radius * side,
+                a[1] + left[1] * radius * side,
+            ];
+
+            (
+                distance,
+                vec![
+                    SketchSegment::arc(a, center, corner.turns_left()),
+                    SketchSegment::line(b),
+                ],
+            )
+        })
+    }
+
+    /// Bevel the selected corners
+    ///
+    /// Each corner is replaced by a line between two points, which are the
+    /// given distance away from the corner, on each of the lines that meet
+    /// there.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the sketch is not valid (see [`Sketch::validate`]), or if a
+    /// line is too short for the bevels at its ends.
+    pub fn chamfer(&self, distance: f64, corners: &Corners) -> Self {
+        self.modify_corners(corners, |corner| {
+            let [a, b] = corner.tangent_points(distance);
+
+            (
+                distance,
+                vec![SketchSegment::line(a), SketchSegment::line(b)],
+            )
+        })
+    }
+
+    /// Replace the selected corners with the segments returned by `replace`
+    ///
+    /// `replace` returns the distance by which the adjacent lines are
+    /// shortened, and the replacement segments. The last of those must be a
+    /// line that starts where the shortened outgoing line starts.
+    fn modify_corners(
+        &self,
+        selection: &Corners,
+        replace: impl Fn(&Corner) -> (f64, Vec<SketchSegment>),
+    ) -> Self {
+        if let Err(err) = self.validate() {
+            panic!("Can't modify corners of invalid sketch: {}", err);
+        }
+
+        let cycles = self.to_cycles();
+
+        // All cycles of a valid sketch have the interior on the same side.
+        let interior_is_left = cycles
+            .first()
+            .map(|outer| validation::signed_area(&outer.to_segments()) > 0.)
+            .unwrap_or(true);
+
+        let cycles = cycles
+            .iter()
+            .enumerate()
+            .map(|(i, cycle)| {
+                let segments = cycle.to_segments();
+                let n = segments.len();
+
+                let replacements: Vec<_> = (0..n)
+                    .map(|j| {
+                        let corner = Corner::new(&segments, j)?;
+
+                        let is_convex = corner.turns_left() == interior_is_left;
+                        let is_selected = match selection {
+                            Corners::All => true,
+                            Corners::Convex => is_convex,
+                            Corners::Concave => !is_convex,
+                            Corners::Indices(indices) => {
+                                indices.contains(&[i, j])
+                            }
+                        };
+
+                        is_selected.then(|| replace(&corner))
+                    })
+                    .collect();
+
+                let mut modified = Vec::new();
+                for (j, segment) in segments.iter().enumerate() {
+                    // Lines are shortened by the replacements of the corners
+                    // at both of their ends. Check whether anything is left.
+                    let next = segments[(j + 1) % n].start;
+                    let shortened_by = replacements[j]
+                        .iter()
+                        .chain(&replacements[(j + 1) % n])
+                        .map(|(distance, _)| distance)
+                        .sum::<f64>();
+                    let remaining =
+                        distance(segment.start, next) - shortened_by;
+
+                    assert!(
+                        remaining > -EPSILON,
+                        "Line starting at point {} of cycle {} is too short to \
+                        modify its corners",
+                        j,
+                        i
+                    );
+
+                    match &replacements[j] {
+                        Some((_, replacement)) => {
+                            // The last replacement segment is the shortened
+                            // line itself. If nothing is left of it, it's
+                            // omitted.
+                            let (line, rest) = replacement
+                                .split_last()
+                                .expect("Expected replacement segments");
+
+                            modified.extend(rest);
+                            if remaining > EPSILON {
+                                modified.push(*line);
+                            }
+                        }
+                        None => {
+                            // The line might still be used up by the
+                            // replacement at its other end.
+                            if remaining > EPSILON {
+                                modified.push(*segment);
+                            }
+                        }
+                    }
+                }
+
+                SketchCycle::from_segments(modified)
+            })
+            .collect();
+
+        Self::from_cycles(cycles)
+    }
+}
+
+/// A corner between two lines
+struct Corner {
+    point: [f64; 2],
+
+    /// The direction of the line that ends at the corner
+    incoming: [f64; 2],
+
+    /// The direction of the line that starts at the corner
+    outgoing: [f64; 2],
+}
+
+impl Corner {
+    /// Create the corner at the start of segment `i`
+    ///
+    /// Returns `None`, if the segments that meet at the point aren't both
+    /// lines, or if they go in the same direction.
+    fn new(segments: &[SketchSegment], i: usize) -> Option<Self> {
+        let n = segments.len();
+
+        let previous = &segments[(i + n - 1) % n];
+        let current = &segments[i];
+        let next = &segments[(i + 1) % n];
+
+        if n < 3 || !is_line(previous) || !is_line(current) {
+            return None;
+        }
+
+        let corner = Self {
+            point: current.start,
+            incoming: direction(previous.start, current.start),
+            outgoing: direction(current.start, next.start),
+        };
+
+        if corner.cross().abs() < EPSILON {
+            return None;
+        }
+
+        Some(corner)
+    }
+
+    /// Indicates whether the outgoing line turns left
+    fn turns_left(&self) -> bool {
+        self.cross() > 0.
+    }
+
+    /// The angle between the lines that meet at the corner
+    fn angle(&self) -> f64 {
+        let cos = -(self.incoming[0] * self.outgoing[0]
+            + self.incoming[1] * self.outgoing[1]);
+        cos.clamp(-1., 1.).acos()
+    }
+
+    /// The points on both lines that are `distance` away from the corner
+    fn tangent_points(&self, distance: f64) -> [[f64; 2]; 2] {
+        [
+            [
+                self.point[0] - self.incoming[0] * distance,
+                self.point[1] - self.incoming[1] * distance,
+            ],
+            [
+                self.point[0] + self.outgoing[0] * distance,
+                self.point[1] + self.outgoing[1] * distance,
+            ],
+        ]
+    }
+
+    fn cross(&self) -> f64 {
+        self.incoming[0] * self.outgoing[1]
+            - self.incoming[1] * self.outgoing[0]
+    }
+}
+
+fn is_line(segment: &SketchSegment) -> bool {
+    matches!(segment.kind, SegmentKind::Line | SegmentKind::Bulge(0.))
+}
+
+fn direction(a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    let length = distance(a, b);
+    [(b[0] - a[0]) / length, (b[1] - a[1]) / length]
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{validation, SegmentKind, Sketch, SketchCycle};
+
+    use super::Corners;
+
+    #[test]
+    fn fillet_centers_arcs_inside_corners() {
+        let square = square([0., 0.], 2.);
+
+        let filleted = square.fillet(0.5, &Corners::All);
+        assert_eq!(filleted.validate(), Ok(()));
+
+        assert_arcs(
+            arcs(&filleted),
+            &[
+                ([0.5, 0.5], true),
+                ([1.5, 0.5], true),
+                ([1.5, 1.5], true),
+                ([0.5, 1.5], true),
+            ],
+        );
+
+        // Each corner loses the area between the corner and its arc.
+        let area =
+            validation::signed_area(&filleted.to_cycles()[0].to_segments());
+        assert!((area - (4. - (1. - PI / 4.))).abs() < 0.01);
+    }
+
+    #[test]
+    fn fillet_of_clockwise_sketch() {
+        let mut points = square_points([0., 0.], 2.);
+        points.reverse();
+        let square = Sketch::from_points(points);
+
+        // The arcs are still inside the corners, but go clockwise.
+        assert_arcs(
+            arcs(&square.fillet(0.5, &Corners::All)),
+            &[
+                ([0.5, 1.5], false),
+                ([1.5, 1.5], false),
+                ([1.5, 0.5], false),
+                ([0.5, 0.5], false),
+            ],
+        );
+    }
+
+    #[test]
+    fn select_convex_and_concave_corners() {
+        // An L-shape. Only the corner at `[1., 1.]` is concave.
+        let l_shape = Sketch::from_points(vec![
+            [0., 0.],
+            [2., 0.],
+            [2., 1.],
+            [1., 1.],
+            [1., 2.],
+            [0., 2.],
+        ]);
+
+        let convex = l_shape.chamfer(0.25, &Corners::Convex);
+        assert_eq!(convex.to_cycles()[0].to_points().len(), 6 + 5);
+
+        let concave = l_shape.fillet(0.25, &Corners::Concave);
+        assert_arcs(arcs(&concave), &[([1.25, 1.25], false)]);
+    }
+
+    #[test]
+    fn corners_of_holes() {
+        // The corners of a hole point into the interior of the sketch.
+        let sketch = square([0., 0.], 4.).with_hole(hole([1., 1.], 2.));
+
+        let convex = sketch.chamfer(0.25, &Corners::Convex);
+        let concave = sketch.chamfer(0.25, &Corners::Concave);
+
+        let points = |sketch: &Sketch| {
+            sketch
+                .to_cycles()
+                .iter()
+                .map(|cycle| cycle.to_points().len())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(points(&convex), vec![8, 4]);
+        assert_eq!(points(&concave), vec![4, 8]);
+    }
+
+    #[test]
+    fn select_corners_by_index() {
+        let sketch = square([0., 0.], 4.).with_hole(hole([1., 1.], 2.));
+
+        let filleted = sketch.fillet(0.5, &Corners::Indices(vec![[1, 2]]));
+        assert_eq!(filleted.validate(), Ok(()));
+
+        // The hole goes through its corners clockwise, starting at the top
+        // left. Its third corner is the bottom right one.
+        let cycles = filleted.to_cycles();
+        assert_arcs(arcs_of_cycle(&cycles[0]), &[]);
+        assert_arcs(arcs_of_cycle(&cycles[1]), &[([2.5, 1.5], false)]);
+    }
+
+    #[test]
+    fn corner_that_uses_up_whole_line() {
+        // The bevel at the top right corner uses up the whole right side. The
+        // unmodified bottom right corner must not leave an edge of zero length
+        // behind.
+        let rectangle =
+            Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 1.], [0., 1.]]);
+
+        let chamfered = rectangle.chamfer(1., &Corners::Indices(vec![[0, 2]]));
+
+        assert_eq!(chamfered.validate(), Ok(()));
+        assert_eq!(
+            chamfered.to_cycles()[0].to_points(),
+            vec![[0., 0.], [2., 0.], [1., 1.], [0., 1.]],
+        );
+    }
+
+    fn arcs(sketch: &Sketch) -> Vec<([f64; 2], bool)> {
+        sketch.to_cycles().iter().flat_map(arcs_of_cycle).collect()
+    }
+
+    fn arcs_of_cycle(cycle: &SketchCycle) -> Vec<([f64; 2], bool)> {
+        cycle
+            .to_segments()
+            .into_iter()
+            .filter_map(|segment| match segment.kind {
+                SegmentKind::Arc {
+                    center,
+                    counter_clockwise,
+                } => Some((center, counter_clockwise)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn assert_arcs(arcs: Vec<([f64; 2], bool)>, expected: &[([f64; 2], bool)]) {
+        assert_eq!(arcs.len(), expected.len(), "{:?}", arcs);
+        for ((center, ccw), (expected_center, expected_ccw)) in
+            arcs.into_iter().zip(expected)
+        {
+            assert_eq!(ccw, *expected_ccw);
+            for (a, b) in center.into_iter().zip(expected_center) {
+                assert!((a - b).abs() < 1e-9, "{:?}", center);
+            }
+        }
+    }
+
+    fn square(min: [f64; 2], size: f64) -> Sketch {
+        Sketch::from_points(square_points(min, size))
+    }
+
+    fn hole(min: [f64; 2], size: f64) -> SketchCycle {
+        let mut points = square_points(min, size);
+        points.reverse();
+
+        SketchCycle::from_points(points)
+    }
+
+    /// The corners of a square, counter-clockwise
+    fn square_points([x, y]: [f64; 2], size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    }
+}
@@ -10,18 +10,29 @@
 //!
 //! [Fornjot repository]: https://github.com/hannobraun/Fornjot
 
+mod corners;
+mod path;
+mod plane;
 mod shape_2d;
 mod shape_3d;
+mod spline;
 mod syntax;
+mod validation;
 
 pub mod prelude {
     pub use crate::syntax::{
-        Difference as _, Rotate as _, Sketch as _, Sweep as _, Translate as _,
-        Union as _,
+        Chamfer as _, CircularPattern as _, CircularPattern2d as _,
+        Difference as _, Fillet as _, Intersection as _, LinearPattern as _,
+        LinearPattern2d as _, Mirror as _, Mirror2d as _, Offset as _,
+        OnPlane as _, PathSweep as _, Revolve as _, Rotate as _, Rotate2d as _,
+        Scale as _, Scale2d as _, Shell as _, Sketch as _, Sweep as _,
+        Translate as _, Translate2d as _, Union as _,
     };
 }
 
-pub use self::{shape_2d::*, shape_3d::*};
+pub use self::{
+    corners::*, path::*, plane::*, shape_2d::*, shape_3d::*, validation::*,
+};
 
 /// A shape
 #[derive(Clone, Debug)]
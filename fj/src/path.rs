@@ -0,0 +1,140 @@
+use std::mem;
+
+/// A path in 3D space
+///
+/// Paths are used to define the movement of a 2-dimensional shape in a
+/// [`PathSweep`](crate::PathSweep).
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub enum Path {
+    /// A circular arc
+    Arc(ArcPath),
+
+    /// A helix around the z-axis
+    Helix(Helix),
+
+    /// A sequence of straight lines
+    Polyline(Polyline),
+}
+
+/// A circular arc
+///
+/// The arc starts at `start`, which is rotated around an axis that goes through
+/// `center`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ArcPath {
+    /// A point on the axis of the rotation
+    pub center: [f64; 3],
+
+    /// The start point of the arc
+    pub start: [f64; 3],
+
+    /// The direction of the axis of the rotation
+    pub axis: [f64; 3],
+
+    /// The angle of the arc, in radians
+    ///
+    /// The rotation follows the right-hand rule. A negative angle results in
+    /// an arc that goes into the opposite direction.
+    pub angle: f64,
+}
+
+impl From<ArcPath> for Path {
+    fn from(path: ArcPath) -> Self {
+        Self::Arc(path)
+    }
+}
+
+/// A helix around the z-axis
+///
+/// The helix starts at the point `[radius, 0., 0.]`, and winds
+/// counter-clockwise around the z-axis (when viewed from above), moving upwards
+/// by `pitch` with each turn.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Helix {
+    /// The distance of the helix from the z-axis
+    pub radius: f64,
+
+    /// The distance between two turns of the helix, along the z-axis
+    ///
+    /// A negative pitch results in a helix that moves downwards.
+    pub pitch: f64,
+
+    /// The number of turns of the helix
+    ///
+    /// Doesn't need to be a whole number.
+    pub turns: f64,
+}
+
+impl From<Helix> for Path {
+    fn from(path: Helix) -> Self {
+        Self::Helix(path)
+    }
+}
+
+/// A sequence of straight lines
+///
+/// Represented by a number of points. For example, if the points a, b, and c
+/// are provided, the lines ab and bc are assumed.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Polyline {
+    // The fields are the raw parts of a `Vec`. `Polyline` needs to be FFI-safe,
+    // meaning it can't store a `Vec` directly. It needs to take this detour.
+    ptr: *mut [f64; 3],
+    length: usize,
+    capacity: usize,
+}
+
+impl Polyline {
+    /// Create a polyline from a bunch of points
+    pub fn from_points(mut points: Vec<[f64; 3]>) -> Self {
+        // This can be cleaned up, once `Vec::into_raw_parts` is stable.
+        let ptr = points.as_mut_ptr();
+        let length = points.len();
+        let capacity = points.capacity();
+
+        // We're taking ownership of the memory here, so we can't allow `points`
+        // to deallocate it.
+        mem::forget(points);
+
+        Self {
+            ptr,
+            length,
+            capacity,
+        }
+    }
+
+    /// Return the points of the polyline
+    pub fn to_points(&self) -> Vec<[f64; 3]> {
+        // This is sound. All invariants are automatically kept, as the raw
+        // parts come from an original `Vec` that is identical to the new one we
+        // create here, and aren't being modified anywhere.
+        let points = unsafe {
+            Vec::from_raw_parts(self.ptr, self.length, self.capacity)
+        };
+
+        // Ownership of the pointer in `self.raw_parts` transferred to `points`.
+        // We work around that, by returning a clone of `points` (hence not
+        // giving ownership to the caller).
+        let ret = points.clone();
+
+        // Now we just need to forget that `points` ever existed, and we keep
+        // ownership of the pointer.
+        mem::forget(points);
+
+        ret
+    }
+}
+
+impl From<Polyline> for Path {
+    fn from(path: Polyline) -> Self {
+        Self::Polyline(path)
+    }
+}
+
+// `Polyline` can be `Send`, because it encapsulates the raw pointer it
+// contains, making sure memory ownership rules are observed.
+unsafe impl Send for Polyline {}
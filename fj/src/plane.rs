@@ -0,0 +1,117 @@
+use crate::Shape3d;
+
+/// A plane in 3D space
+///
+/// 2-dimensional shapes are defined in the x-y plane. Use
+/// [`OnPlane`](crate::OnPlane) to place them on another plane.
+///
+/// A plane defines a coordinate system: Its origin, its x- and y-axes, which
+/// lie within the plane, and its normal, which takes the place of the z-axis.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub enum Plane {
+    /// A plane defined by its origin and orientation
+    Frame(PlaneFrame),
+
+    /// The plane of a flat face of a 3-dimensional shape
+    Face(Box<FacePlane>),
+}
+
+impl Plane {
+    /// Create a plane from its origin, normal, and x-axis
+    ///
+    /// Neither `normal` nor `x_axis` need to be normalized. `x_axis` doesn't
+    /// need to be perpendicular to `normal` either, but must not be parallel to
+    /// it.
+    pub fn new(origin: [f64; 3], normal: [f64; 3], x_axis: [f64; 3]) -> Self {
+        Self::Frame(PlaneFrame {
+            origin,
+            normal,
+            x_axis,
+        })
+    }
+
+    /// Create the x-y plane
+    pub fn xy() -> Self {
+        Self::new([0.; 3], [0., 0., 1.], [1., 0., 0.])
+    }
+
+    /// Create the x-z plane
+    ///
+    /// The x-axis of the plane is the x-axis of the model, its y-axis is the
+    /// z-axis of the model. Its normal points in negative y direction.
+    pub fn xz() -> Self {
+        Self::new([0.; 3], [0., -1., 0.], [1., 0., 0.])
+    }
+
+    /// Create the y-z plane
+    ///
+    /// The x-axis of the plane is the y-axis of the model, its y-axis is the
+    /// z-axis of the model. Its normal points in positive x direction.
+    pub fn yz() -> Self {
+        Self::new([0.; 3], [1., 0., 0.], [0., 1., 0.])
+    }
+
+    /// Create the plane of the face of a shape that is closest to a point
+    ///
+    /// See [`FacePlane`].
+    pub fn face(shape: impl Into<Shape3d>, point: [f64; 3]) -> Self {
+        Self::Face(Box::new(FacePlane {
+            shape: shape.into(),
+            point,
+        }))
+    }
+}
+
+impl From<PlaneFrame> for Plane {
+    fn from(plane: PlaneFrame) -> Self {
+        Self::Frame(plane)
+    }
+}
+
+impl From<FacePlane> for Plane {
+    fn from(plane: FacePlane) -> Self {
+        Self::Face(Box::new(plane))
+    }
+}
+
+/// A plane defined by its origin and orientation
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct PlaneFrame {
+    /// The origin of the plane
+    pub origin: [f64; 3],
+
+    /// The normal of the plane
+    ///
+    /// Doesn't need to be normalized.
+    pub normal: [f64; 3],
+
+    /// The direction of the plane's x-axis
+    ///
+    /// Doesn't need to be normalized. If it's not perpendicular to `normal`,
+    /// it's projected onto the plane. Must not be parallel to `normal`.
+    ///
+    /// The y-axis of the plane is perpendicular to the x-axis, such that the
+    /// x-axis, y-axis, and normal form a right-handed coordinate system.
+    pub x_axis: [f64; 3],
+}
+
+/// The plane of a flat face of a 3-dimensional shape
+///
+/// The face is the one closest to `point`. Its plane is oriented such that its
+/// normal points out of the shape, and its origin is the point on the plane
+/// that is closest to `point`.
+///
+/// The x-axis of the plane is the x-axis of the model, projected onto the
+/// plane. If the plane is perpendicular to the x-axis of the model, its y-axis
+/// is used instead.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct FacePlane {
+    /// The shape whose face defines the plane
+    pub shape: Shape3d,
+
+    /// A point on or near the face
+    pub point: [f64; 3],
+}
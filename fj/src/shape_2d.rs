@@ -1,6 +1,9 @@
 use std::mem;
 
-use crate::Shape;
+use crate::{Plane, Shape};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-9;
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug)]
@@ -9,11 +12,38 @@ pub enum Shape2d {
     /// A circle
     Circle(Circle),
 
+    /// A shape, repeated around the origin
+    CircularPattern(Box<CircularPattern2d>),
+
     /// A difference between two shapes
     Difference(Box<Difference2d>),
 
+    /// An ellipse
+    Ellipse(Ellipse),
+
+    /// An intersection between two shapes
+    Intersection(Box<Intersection2d>),
+
+    /// A shape, repeated along a straight line
+    LinearPattern(Box<LinearPattern2d>),
+
+    /// A shape that is grown or shrunk by a distance
+    Offset(Box<Offset2d>),
+
+    /// A shape, placed on a plane
+    OnPlane(Box<OnPlane>),
+
     /// A sketch
     Sketch(Sketch),
+
+    /// Text
+    Text(Text),
+
+    /// A transformed shape
+    Transform(Box<Transform2d>),
+
+    /// A union of two shapes
+    Union(Box<Union2d>),
 }
 
 /// A circle
@@ -36,6 +66,37 @@ impl From<Circle> for Shape2d {
     }
 }
 
+/// A 2-dimensional shape, repeated around the origin
+///
+/// The shape is repeated `count` times. The first copy is the original shape.
+/// Each other copy is rotated counter-clockwise by `angle` around the origin,
+/// relative to the previous one. To distribute the copies evenly around a full
+/// circle, use an angle of 2π divided by `count`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct CircularPattern2d {
+    /// The shape being repeated
+    pub shape: Shape2d,
+
+    /// The angle between two consecutive copies, in radians
+    pub angle: f64,
+
+    /// The number of copies
+    pub count: u32,
+}
+
+impl From<CircularPattern2d> for Shape {
+    fn from(shape: CircularPattern2d) -> Self {
+        Self::Shape2d(Shape2d::CircularPattern(Box::new(shape)))
+    }
+}
+
+impl From<CircularPattern2d> for Shape2d {
+    fn from(shape: CircularPattern2d) -> Self {
+        Self::CircularPattern(Box::new(shape))
+    }
+}
+
 /// A difference between two shapes
 #[derive(Clone, Debug)]
 #[repr(C)]
@@ -59,36 +120,210 @@ impl From<Difference2d> for Shape2d {
     }
 }
 
+/// An ellipse
+///
+/// The ellipse is centered on the origin, with its axes along the x and y axes.
+/// Use [`Transform2d`] to move or rotate it.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Ellipse {
+    /// The radius of the ellipse along the x axis
+    pub radius_x: f64,
+
+    /// The radius of the ellipse along the y axis
+    pub radius_y: f64,
+}
+
+impl From<Ellipse> for Shape {
+    fn from(shape: Ellipse) -> Self {
+        Self::Shape2d(Shape2d::Ellipse(shape))
+    }
+}
+
+impl From<Ellipse> for Shape2d {
+    fn from(shape: Ellipse) -> Self {
+        Self::Ellipse(shape)
+    }
+}
+
+/// An intersection between two shapes
+///
+/// The resulting shape only consists of the area that is shared by both shapes.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Intersection2d {
+    /// The first of the shapes
+    pub a: Shape2d,
+
+    /// The second of the shapes
+    pub b: Shape2d,
+}
+
+impl From<Intersection2d> for Shape {
+    fn from(shape: Intersection2d) -> Self {
+        Self::Shape2d(Shape2d::Intersection(Box::new(shape)))
+    }
+}
+
+impl From<Intersection2d> for Shape2d {
+    fn from(shape: Intersection2d) -> Self {
+        Self::Intersection(Box::new(shape))
+    }
+}
+
+/// A 2-dimensional shape, repeated along a straight line
+///
+/// The shape is repeated `count` times. The first copy is the original shape.
+/// Each other copy is translated by `offset`, relative to the previous one.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct LinearPattern2d {
+    /// The shape being repeated
+    pub shape: Shape2d,
+
+    /// The offset between two consecutive copies
+    pub offset: [f64; 2],
+
+    /// The number of copies
+    pub count: u32,
+}
+
+impl From<LinearPattern2d> for Shape {
+    fn from(shape: LinearPattern2d) -> Self {
+        Self::Shape2d(Shape2d::LinearPattern(Box::new(shape)))
+    }
+}
+
+impl From<LinearPattern2d> for Shape2d {
+    fn from(shape: LinearPattern2d) -> Self {
+        Self::LinearPattern(Box::new(shape))
+    }
+}
+
+/// A shape that is grown or shrunk by a distance
+///
+/// Every edge of the shape is moved by `distance`, perpendicular to itself.
+/// Positive distances grow the shape, negative distances shrink it. Holes in the
+/// shape shrink or grow accordingly.
+///
+/// Where moving the edges leaves a gap between them, as it does at convex
+/// corners when growing the shape, the gap is closed by an arc around the
+/// original corner. Where the moved edges overlap, they are trimmed.
+///
+/// Holes and faces that vanish entirely, because they are too small for the
+/// distance, are removed. Faces that grow into each other are merged. Distances
+/// at which only some edges of a cycle vanish are not supported.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Offset2d {
+    /// The shape being offset
+    pub shape: Shape2d,
+
+    /// The distance by which the shape is grown
+    ///
+    /// Negative values shrink the shape.
+    pub distance: f64,
+}
+
+impl From<Offset2d> for Shape {
+    fn from(shape: Offset2d) -> Self {
+        Self::Shape2d(Shape2d::Offset(Box::new(shape)))
+    }
+}
+
+impl From<Offset2d> for Shape2d {
+    fn from(shape: Offset2d) -> Self {
+        Self::Offset(Box::new(shape))
+    }
+}
+
+/// A 2-dimensional shape, placed on a plane
+///
+/// The x-y plane that the shape is defined in is moved onto the plane, such
+/// that the shape's x- and y-axes become those of the plane.
+///
+/// 3-dimensional shapes that are created from a shape on a plane, like sweeps
+/// and revolutions, are created as if the shape were in the x-y plane, then
+/// moved onto the plane. Their parameters, like the direction of a sweep, are
+/// relative to the plane. The normal of the plane takes the place of the
+/// z-axis. The same goes for the edges that [`Chamfer`](crate::Chamfer),
+/// [`Fillet`](crate::Fillet), and [`Shell`](crate::Shell) refer to.
+///
+/// Other 2-dimensional shapes expect their shapes to be in the x-y plane. Place
+/// their result on a plane, instead of the shapes they are created from.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct OnPlane {
+    /// The shape being placed
+    pub shape: Shape2d,
+
+    /// The plane that the shape is placed on
+    pub plane: Plane,
+}
+
+impl From<OnPlane> for Shape {
+    fn from(shape: OnPlane) -> Self {
+        Self::Shape2d(Shape2d::OnPlane(Box::new(shape)))
+    }
+}
+
+impl From<OnPlane> for Shape2d {
+    fn from(shape: OnPlane) -> Self {
+        Self::OnPlane(Box::new(shape))
+    }
+}
+
 /// A sketch
 ///
-/// Sketches are currently limited to a single cycle of straight lines,
-/// represented by a number of points. For example, if the points a, b, and c
-/// are provided, the edges ab, bc, and ca are assumed.
+/// A sketch consists of one or more cycles of segments. The first cycle is the
+/// outer boundary of the sketch. All other cycles are holes, which must be
+/// within the outer boundary, and must not be within each other. No two cycles
+/// may intersect.
+///
+/// The outer boundary may wind in either direction. Holes must wind in the
+/// opposite direction of the outer boundary.
 ///
-/// Nothing about these edges is checked right now, but algorithms might assume
-/// that the edges are non-overlapping. If you create a `Sketch` with
-/// overlapping edges, you're on your own.
+/// Algorithms assume that sketches are valid. Use [`Sketch::validate`] to check
+/// that, or rely on the host application, which checks all sketches before
+/// evaluating a model.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Sketch {
     // The fields are the raw parts of a `Vec`. `Sketch` needs to be FFI-safe,
     // meaning it can't store a `Vec` directly. It needs to take this detour.
-    ptr: *mut [f64; 2],
+    ptr: *mut SketchCycle,
     length: usize,
     capacity: usize,
 }
 
 impl Sketch {
     /// Create a sketch from a bunch of points
-    pub fn from_points(mut points: Vec<[f64; 2]>) -> Self {
+    ///
+    /// The sketch consists of a single cycle. The points are connected by
+    /// straight lines.
+    pub fn from_points(points: Vec<[f64; 2]>) -> Self {
+        Self::from_cycles(vec![SketchCycle::from_points(points)])
+    }
+
+    /// Create a sketch from a bunch of segments
+    ///
+    /// The sketch consists of a single cycle.
+    pub fn from_segments(segments: Vec<SketchSegment>) -> Self {
+        Self::from_cycles(vec![SketchCycle::from_segments(segments)])
+    }
+
+    /// Create a sketch from a number of cycles
+    ///
+    /// The first cycle is the outer boundary, all others are holes.
+    pub fn from_cycles(mut cycles: Vec<SketchCycle>) -> Self {
         // This can be cleaned up, once `Vec::into_raw_parts` is stable.
-        let ptr = points.as_mut_ptr();
-        let length = points.len();
-        let capacity = points.capacity();
+        let ptr = cycles.as_mut_ptr();
+        let length = cycles.len();
+        let capacity = cycles.capacity();
 
-        // We're taking ownership of the memory here, so we can't allow `points`
+        // We're taking ownership of the memory here, so we can't allow `cycles`
         // to deallocate it.
-        mem::forget(points);
+        mem::forget(cycles);
 
         Self {
             ptr,
@@ -97,23 +332,32 @@ impl Sketch {
         }
     }
 
-    /// Return the points of the sketch
-    pub fn to_points(&self) -> Vec<[f64; 2]> {
+    /// Add a hole to the sketch
+    #[must_use]
+    pub fn with_hole(self, hole: SketchCycle) -> Self {
+        let mut cycles = self.to_cycles();
+        cycles.push(hole);
+
+        Self::from_cycles(cycles)
+    }
+
+    /// Return the cycles of the sketch
+    pub fn to_cycles(&self) -> Vec<SketchCycle> {
         // This is sound. All invariants are automatically kept, as the raw
         // parts come from an original `Vec` that is identical to the new one we
         // create here, and aren't being modified anywhere.
-        let points = unsafe {
+        let cycles = unsafe {
             Vec::from_raw_parts(self.ptr, self.length, self.capacity)
         };
 
-        // Ownership of the pointer in `self.raw_parts` transferred to `points`.
-        // We work around that, by returning a clone of `points` (hence not
+        // Ownership of the pointer in `self.raw_parts` transferred to `cycles`.
+        // We work around that, by returning a clone of `cycles` (hence not
         // giving ownership to the caller).
-        let ret = points.clone();
+        let ret = cycles.clone();
 
-        // Now we just need to forget that `points` ever existed, and we keep
+        // Now we just need to forget that `cycles` ever existed, and we keep
         // ownership of the pointer.
-        mem::forget(points);
+        mem::forget(cycles);
 
         ret
     }
@@ -131,6 +375,529 @@ impl From<Sketch> for Shape2d {
     }
 }
 
+/// A cycle of segments in a sketch
+///
+/// Each segment starts at its start point, and ends at the start point of the
+/// next segment. The last segment ends at the start point of the first one.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct SketchCycle {
+    // The fields are the raw parts of a `Vec`. `SketchCycle` needs to be
+    // FFI-safe, meaning it can't store a `Vec` directly. It needs to take this
+    // detour.
+    ptr: *mut SketchSegment,
+    length: usize,
+    capacity: usize,
+}
+
+impl SketchCycle {
+    /// Create a cycle from a bunch of points
+    ///
+    /// The points are connected by straight lines.
+    pub fn from_points(points: Vec<[f64; 2]>) -> Self {
+        Self::from_segments(
+            points.into_iter().map(SketchSegment::line).collect(),
+        )
+    }
+
+    /// Create a cycle from a bunch of segments
+    pub fn from_segments(mut segments: Vec<SketchSegment>) -> Self {
+        // This can be cleaned up, once `Vec::into_raw_parts` is stable.
+        let ptr = segments.as_mut_ptr();
+        let length = segments.len();
+        let capacity = segments.capacity();
+
+        // We're taking ownership of the memory here, so we can't allow
+        // `segments` to deallocate it.
+        mem::forget(segments);
+
+        Self {
+            ptr,
+            length,
+            capacity,
+        }
+    }
+
+    /// Return the segments of the cycle
+    pub fn to_segments(&self) -> Vec<SketchSegment> {
+        // This is sound. All invariants are automatically kept, as the raw
+        // parts come from an original `Vec` that is identical to the new one we
+        // create here, and aren't being modified anywhere.
+        let segments = unsafe {
+            Vec::from_raw_parts(self.ptr, self.length, self.capacity)
+        };
+
+        // Ownership of the pointer in `self.raw_parts` transferred to
+        // `segments`. We work around that, by returning a clone of `segments`
+        // (hence not giving ownership to the caller).
+        let ret = segments.clone();
+
+        // Now we just need to forget that `segments` ever existed, and we keep
+        // ownership of the pointer.
+        mem::forget(segments);
+
+        ret
+    }
+
+    /// Return the points of the cycle
+    ///
+    /// These are the start points of all segments.
+    pub fn to_points(&self) -> Vec<[f64; 2]> {
+        self.to_segments()
+            .into_iter()
+            .map(|segment| segment.start)
+            .collect()
+    }
+}
+
+/// A segment of a sketch
+///
+/// The segment starts at `start`, and ends at the start of the next segment of
+/// the cycle.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct SketchSegment {
+    /// The point that the segment starts at
+    pub start: [f64; 2],
+
+    /// The kind of the segment
+    pub kind: SegmentKind,
+}
+
+impl SketchSegment {
+    /// Create a straight line that starts at `start`
+    pub fn line(start: [f64; 2]) -> Self {
+        Self {
+            start,
+            kind: SegmentKind::Line,
+        }
+    }
+
+    /// Create an arc around `center` that starts at `start`
+    ///
+    /// See [`SegmentKind::Arc`].
+    pub fn arc(
+        start: [f64; 2],
+        center: [f64; 2],
+        counter_clockwise: bool,
+    ) -> Self {
+        Self {
+            start,
+            kind: SegmentKind::Arc {
+                center,
+                counter_clockwise,
+            },
+        }
+    }
+
+    /// Create an arc with the given radius that starts at `start`
+    ///
+    /// See [`SegmentKind::ArcWithRadius`].
+    pub fn arc_with_radius(
+        start: [f64; 2],
+        radius: f64,
+        counter_clockwise: bool,
+    ) -> Self {
+        Self {
+            start,
+            kind: SegmentKind::ArcWithRadius {
+                radius,
+                counter_clockwise,
+            },
+        }
+    }
+
+    /// Create an arc with the given bulge that starts at `start`
+    ///
+    /// See [`SegmentKind::Bulge`].
+    pub fn bulge(start: [f64; 2], bulge: f64) -> Self {
+        Self {
+            start,
+            kind: SegmentKind::Bulge(bulge),
+        }
+    }
+
+    /// Create a cubic Bézier curve that starts at `start`
+    ///
+    /// See [`SegmentKind::Bezier`].
+    pub fn bezier(start: [f64; 2], control: [[f64; 2]; 2]) -> Self {
+        Self {
+            start,
+            kind: SegmentKind::Bezier { control },
+        }
+    }
+
+    /// Compute the center of the circle that the segment is an arc of
+    ///
+    /// `end` is the point that the segment ends at, which is the start of the
+    /// next segment of the cycle. Returns the center, and whether the arc goes
+    /// around it counter-clockwise.
+    ///
+    /// Returns `None`, if the segment is a line or a Bézier curve, or if it is
+    /// an arc that can't be constructed from its parameters. The latter is
+    /// reported by [`Sketch::validate`].
+    pub fn arc_center(&self, end: [f64; 2]) -> Option<([f64; 2], bool)> {
+        arc(self.start, end, self.kind).flatten()
+    }
+}
+
+/// Determine the center and direction of an arc from `a` to `b`
+///
+/// Returns `Some(None)`, if the segment is a line or a Bézier curve, and
+/// `None`, if it's an arc that can't be constructed.
+pub(crate) fn arc(
+    a: [f64; 2],
+    b: [f64; 2],
+    kind: SegmentKind,
+) -> Option<Option<([f64; 2], bool)>> {
+    let half_chord = distance(a, b) / 2.;
+
+    let arc = match kind {
+        SegmentKind::Line => None,
+        SegmentKind::Bulge(0.) => None,
+        SegmentKind::Bezier { .. } => None,
+        SegmentKind::Arc {
+            center,
+            counter_clockwise,
+        } => {
+            let radius = distance(a, center);
+            if radius < EPSILON
+                || (radius - distance(b, center)).abs()
+                    > EPSILON * radius.max(1.)
+            {
+                return None;
+            }
+
+            Some((center, counter_clockwise))
+        }
+        SegmentKind::ArcWithRadius {
+            radius,
+            counter_clockwise,
+        } => {
+            if half_chord > radius.abs() {
+                return None;
+            }
+
+            let distance = (radius * radius - half_chord * half_chord).sqrt();
+            let distance = if counter_clockwise == (radius > 0.) {
+                distance
+            } else {
+                -distance
+            };
+
+            Some((bisector_point(a, b, distance), counter_clockwise))
+        }
+        SegmentKind::Bulge(bulge) => {
+            let angle = 4. * bulge.atan();
+
+            Some((
+                bisector_point(a, b, half_chord / (angle / 2.).tan()),
+                bulge > 0.,
+            ))
+        }
+    };
+
+    Some(arc)
+}
+
+/// Compute a point on the bisector of the line from `a` to `b`
+///
+/// `distance` is the distance from the center of the line. Positive distances
+/// are to the left of the line, negative ones to the right.
+fn bisector_point(a: [f64; 2], b: [f64; 2], distance: f64) -> [f64; 2] {
+    let length = self::distance(a, b);
+    let left = [-(b[1] - a[1]) / length, (b[0] - a[0]) / length];
+
+    [
+        (a[0] + b[0]) / 2. + left[0] * distance,
+        (a[1] + b[1]) / 2. + left[1] * distance,
+    ]
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+/// The kind of a sketch segment
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum SegmentKind {
+    /// A straight line
+    Line,
+
+    /// A circular arc around a center point
+    ///
+    /// Both ends of the segment must have the same distance from `center`. If
+    /// both ends are identical, the arc is a full circle.
+    Arc {
+        /// The center of the circle that the arc is a part of
+        center: [f64; 2],
+
+        /// The direction of the arc, from its start to its end
+        counter_clockwise: bool,
+    },
+
+    /// A circular arc with a given radius
+    ///
+    /// There are two arcs with the same radius and direction that connect the
+    /// ends of the segment. If `radius` is positive, the shorter one is chosen.
+    /// If it is negative, the longer one.
+    ///
+    /// The ends of the segment must not be further apart than twice the
+    /// radius.
+    ArcWithRadius {
+        /// The radius of the circle that the arc is a part of
+        radius: f64,
+
+        /// The direction of the arc, from its start to its end
+        counter_clockwise: bool,
+    },
+
+    /// A circular arc, defined by its bulge
+    ///
+    /// The bulge is the tangent of a quarter of the arc's angle. `0` is a
+    /// straight line, `1` a half circle. A positive bulge results in a
+    /// counter-clockwise arc, a negative one in a clockwise arc.
+    Bulge(f64),
+
+    /// A cubic Bézier curve
+    ///
+    /// The curve goes from the start of the segment to its end, pulled towards
+    /// the two control points on the way. It leaves the start in the direction
+    /// of the first control point, and arrives at the end from the direction of
+    /// the second one.
+    ///
+    /// Use [`SketchSegment::b_spline`] or [`SketchCycle::from_b_spline`] to
+    /// create a number of Bézier segments that form a B-spline.
+    Bezier {
+        /// The two control points between the start and the end
+        control: [[f64; 2]; 2],
+    },
+}
+
+/// Text, as the outlines of its glyphs
+///
+/// The text starts at the origin, with the first line on the x-axis (which is
+/// the baseline) and following lines below it. Letters with holes, like "o" or
+/// "a", have holes in the resulting shape.
+///
+/// # Limitations
+///
+/// The curves that make up the glyphs are approximated with lines. Only the
+/// fonts that are bundled with the host application are available.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Text {
+    // The fields are the raw parts of a `String`. `Text` needs to be FFI-safe,
+    // meaning it can't store a `String` directly. It needs to take this detour.
+    ptr: *mut u8,
+    length: usize,
+    capacity: usize,
+
+    /// The font of the text
+    pub font: Font,
+
+    /// The size of the text
+    ///
+    /// This is the size of the font's em square, which is roughly the distance
+    /// from the top of the highest glyph to the bottom of the lowest one.
+    pub size: f64,
+}
+
+impl Text {
+    /// Create text from a string
+    pub fn new(content: impl Into<String>, font: Font, size: f64) -> Self {
+        let mut content = content.into();
+
+        // This can be cleaned up, once `String::into_raw_parts` is stable.
+        let ptr = content.as_mut_ptr();
+        let length = content.len();
+        let capacity = content.capacity();
+
+        // We're taking ownership of the memory here, so we can't allow
+        // `content` to deallocate it.
+        mem::forget(content);
+
+        Self {
+            ptr,
+            length,
+            capacity,
+            font,
+            size,
+        }
+    }
+
+    /// Return the content of the text
+    pub fn content(&self) -> String {
+        // This is sound. All invariants are automatically kept, as the raw
+        // parts come from an original `String` that is identical to the new
+        // one we create here, and aren't being modified anywhere.
+        let content = unsafe {
+            String::from_raw_parts(self.ptr, self.length, self.capacity)
+        };
+
+        // Ownership of the pointer transferred to `content`. We work around
+        // that, by returning a clone of `content` (hence not giving ownership
+        // to the caller).
+        let ret = content.clone();
+
+        // Now we just need to forget that `content` ever existed, and we keep
+        // ownership of the pointer.
+        mem::forget(content);
+
+        ret
+    }
+}
+
+impl From<Text> for Shape {
+    fn from(shape: Text) -> Self {
+        Self::Shape2d(Shape2d::Text(shape))
+    }
+}
+
+impl From<Text> for Shape2d {
+    fn from(shape: Text) -> Self {
+        Self::Text(shape)
+    }
+}
+
+/// A font that can be used for [`Text`]
+///
+/// These are the fonts of the B612 family, which are bundled with the host
+/// application.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum Font {
+    B612Regular,
+    B612Bold,
+    B612Italic,
+    B612BoldItalic,
+    B612MonoRegular,
+    B612MonoBold,
+    B612MonoItalic,
+    B612MonoBoldItalic,
+}
+
+/// A transformed 2-dimensional shape
+///
+/// The transformation is affine, meaning it can rotate, translate, scale,
+/// mirror, and shear the shape within the x-y plane.
+///
+/// Use the constructors of this struct to create common transformations. Nest
+/// multiple `Transform2d`s to combine them.
+///
+/// Circles and arcs stay circles and arcs, if the transformation preserves
+/// their shape. Otherwise, they become ellipses and elliptical arcs.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Transform2d {
+    /// The shape being transformed
+    pub shape: Shape2d,
+
+    /// The transformation, as a 3x3 matrix in column-major order
+    ///
+    /// The last row of the matrix must be `[0., 0., 1.]`.
+    pub matrix: [f64; 9],
+}
+
+impl Transform2d {
+    /// Create a transformation from a 3x3 matrix in column-major order
+    pub fn from_matrix(shape: impl Into<Shape2d>, matrix: [f64; 9]) -> Self {
+        Self {
+            shape: shape.into(),
+            matrix,
+        }
+    }
+
+    /// Create a rotation around the origin
+    ///
+    /// `angle` is in radians. Positive angles rotate counter-clockwise.
+    pub fn rotation(shape: impl Into<Shape2d>, angle: f64) -> Self {
+        let (s, c) = angle.sin_cos();
+        Self::from_matrix(shape, affine([[c, -s], [s, c]], [0.; 2]))
+    }
+
+    /// Create a translation
+    pub fn translation(shape: impl Into<Shape2d>, offset: [f64; 2]) -> Self {
+        Self::from_matrix(shape, affine([[1., 0.], [0., 1.]], offset))
+    }
+
+    /// Create a scaling along the coordinate axes
+    ///
+    /// The shape is scaled by a separate factor along each axis, with the
+    /// origin as the fixed point. Negative factors also mirror the shape.
+    pub fn scaling(shape: impl Into<Shape2d>, factors: [f64; 2]) -> Self {
+        let [x, y] = factors;
+        Self::from_matrix(shape, affine([[x, 0.], [0., y]], [0.; 2]))
+    }
+
+    /// Create a mirroring across a line through the origin
+    ///
+    /// The line is defined by its normal, which doesn't need to be normalized.
+    pub fn mirror(shape: impl Into<Shape2d>, normal: [f64; 2]) -> Self {
+        let [x, y] = normal;
+        let length = x.hypot(y);
+        let [x, y] = [x / length, y / length];
+
+        let rows = [
+            [1. - 2. * x * x, -2. * x * y],
+            [-2. * x * y, 1. - 2. * y * y],
+        ];
+        Self::from_matrix(shape, affine(rows, [0.; 2]))
+    }
+}
+
+impl From<Transform2d> for Shape {
+    fn from(shape: Transform2d) -> Self {
+        Self::Shape2d(Shape2d::Transform(Box::new(shape)))
+    }
+}
+
+impl From<Transform2d> for Shape2d {
+    fn from(shape: Transform2d) -> Self {
+        Self::Transform(Box::new(shape))
+    }
+}
+
+/// Create a column-major 3x3 matrix from a linear part and an offset
+///
+/// The linear part is given as rows.
+fn affine(rows: [[f64; 2]; 2], offset: [f64; 2]) -> [f64; 9] {
+    let [[a, b], [c, d]] = rows;
+    let [x, y] = offset;
+
+    [a, c, 0., b, d, 0., x, y, 1.]
+}
+
+/// A union of two shapes
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Union2d {
+    /// The first of the shapes
+    pub a: Shape2d,
+
+    /// The second of the shapes
+    pub b: Shape2d,
+}
+
+impl From<Union2d> for Shape {
+    fn from(shape: Union2d) -> Self {
+        Self::Shape2d(Shape2d::Union(Box::new(shape)))
+    }
+}
+
+impl From<Union2d> for Shape2d {
+    fn from(shape: Union2d) -> Self {
+        Self::Union(Box::new(shape))
+    }
+}
+
 // `Sketch` can be `Send`, because it encapsulates the raw pointer it contains,
 // making sure memory ownership rules are observed.
 unsafe impl Send for Sketch {}
+
+// `SketchCycle` can be `Send`, for the same reason.
+unsafe impl Send for SketchCycle {}
+
+// `Text` can be `Send`, for the same reason.
+unsafe impl Send for Text {}
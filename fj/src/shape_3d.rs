@@ -1,15 +1,56 @@
-use crate::{Shape, Shape2d};
+use std::mem;
+
+use crate::{Path, Shape, Shape2d};
 
 /// A 3-dimensional shape
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub enum Shape3d {
+    /// A 3-dimensional shape with beveled edges
+    Chamfer(Box<Chamfer>),
+
+    /// A 3-dimensional shape, repeated around an axis
+    CircularPattern(Box<CircularPattern>),
+
+    /// A cone
+    Cone(Cone),
+
+    /// A cylinder
+    Cylinder(Cylinder),
+
     /// The difference of two 3-dimensional shapes
     Difference(Box<Difference>),
 
-    /// A sweep of 2-dimensional shape along the z-axis
+    /// A 3-dimensional shape with rounded edges
+    Fillet(Box<Fillet>),
+
+    /// The intersection of two 3-dimensional shapes
+    Intersection(Box<Intersection>),
+
+    /// A 3-dimensional shape, repeated along a straight line
+    LinearPattern(Box<LinearPattern>),
+
+    /// A loft between a sequence of 2-dimensional shapes
+    Loft(Loft),
+
+    /// A sweep of a 2-dimensional shape along a path
+    PathSweep(PathSweep),
+
+    /// A revolution of a 2-dimensional shape around an axis
+    Revolve(Revolve),
+
+    /// A hollowed 3-dimensional shape
+    Shell(Box<Shell>),
+
+    /// A sphere
+    Sphere(Sphere),
+
+    /// A sweep of 2-dimensional shape along a straight path
     Sweep(Sweep),
 
+    /// A torus
+    Torus(Torus),
+
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
 
@@ -23,15 +64,142 @@ impl From<Shape3d> for Shape {
     }
 }
 
+/// A 3-dimensional shape with beveled edges
+///
+/// Each of the selected edges is replaced by a flat face, which meets the two
+/// faces adjacent to the edge at `distance` from it.
+///
+/// # Limitations
+///
+/// Only sweeps along the z-axis without a draft angle, and cylinders, are
+/// supported, as well as other chamfers and [`Fillet`]s of them. Only corners
+/// between two lines are beveled, if edges that are parallel to the z-axis are
+/// selected (see [`crate::Corners`]).
+///
+/// The edges of the top and bottom faces can only be modified once, and the
+/// modifications of both must fit into the length of the sweep. Models that
+/// don't respect these limitations are rejected when they're loaded.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Chamfer {
+    /// The shape whose edges are beveled
+    pub shape: Shape3d,
+
+    /// The distance from the edge to where the bevel meets the adjacent faces
+    pub distance: f64,
+
+    /// The edges that are beveled
+    pub edges: EdgeSelection,
+}
+
+impl From<Chamfer> for Shape {
+    fn from(shape: Chamfer) -> Self {
+        Self::Shape3d(Shape3d::Chamfer(Box::new(shape)))
+    }
+}
+
+impl From<Chamfer> for Shape3d {
+    fn from(shape: Chamfer) -> Self {
+        Self::Chamfer(Box::new(shape))
+    }
+}
+
+/// A 3-dimensional shape, repeated around an axis
+///
+/// The shape is repeated `count` times. The first copy is the original shape.
+/// Each other copy is rotated by `angle` around an axis through the origin,
+/// relative to the previous one. To distribute the copies evenly around a full
+/// circle, use an angle of 2π divided by `count`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct CircularPattern {
+    /// The shape being repeated
+    pub shape: Shape3d,
+
+    /// The axis of the rotation
+    pub axis: [f64; 3],
+
+    /// The angle between two consecutive copies, in radians
+    pub angle: f64,
+
+    /// The number of copies
+    pub count: u32,
+}
+
+impl From<CircularPattern> for Shape {
+    fn from(shape: CircularPattern) -> Self {
+        Self::Shape3d(Shape3d::CircularPattern(Box::new(shape)))
+    }
+}
+
+impl From<CircularPattern> for Shape3d {
+    fn from(shape: CircularPattern) -> Self {
+        Self::CircularPattern(Box::new(shape))
+    }
+}
+
+/// A cone
+///
+/// The base of the cone is a circle in the x-y plane, centered at the origin.
+/// The apex is on the positive z-axis.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Cone {
+    /// The radius of the base
+    pub radius: f64,
+
+    /// The distance of the apex from the base
+    pub height: f64,
+}
+
+impl From<Cone> for Shape {
+    fn from(shape: Cone) -> Self {
+        Self::Shape3d(Shape3d::Cone(shape))
+    }
+}
+
+impl From<Cone> for Shape3d {
+    fn from(shape: Cone) -> Self {
+        Self::Cone(shape)
+    }
+}
+
+/// A cylinder
+///
+/// The bottom of the cylinder is a circle in the x-y plane, centered at the
+/// origin. The cylinder extends in positive z direction from there.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Cylinder {
+    /// The radius of the cylinder
+    pub radius: f64,
+
+    /// The height of the cylinder
+    pub height: f64,
+}
+
+impl From<Cylinder> for Shape {
+    fn from(shape: Cylinder) -> Self {
+        Self::Shape3d(Shape3d::Cylinder(shape))
+    }
+}
+
+impl From<Cylinder> for Shape3d {
+    fn from(shape: Cylinder) -> Self {
+        Self::Cylinder(shape)
+    }
+}
+
 /// The difference of two 3-dimensional shapes
 ///
 /// # Limitations
 ///
-/// This operation is not supported right now. Using it in a model, will result
-/// in the host application crashing.
+/// The difference is computed from the triangle meshes that approximate the
+/// two shapes. The resulting shape is a triangle mesh too, which means that any
+/// information about curved faces is lost after this operation.
 ///
-/// See issue:
-/// https://github.com/hannobraun/Fornjot/issues/43
+/// Both shapes need to be closed, meaning they must not have any holes in their
+/// surface. Otherwise the result is undefined.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Difference {
@@ -54,21 +222,211 @@ impl From<Difference> for Shape3d {
     }
 }
 
-/// A transformed 3-dimensional shape
+/// A selection of edges of a 3-dimensional shape
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum EdgeSelection {
+    /// All edges that are parallel to the z-axis
+    ParallelToZ,
+
+    /// All edges of the top face, the face with the highest z coordinate
+    TopFace,
+
+    /// All edges of the bottom face, the face with the lowest z coordinate
+    BottomFace,
+}
+
+/// A 3-dimensional shape with rounded edges
+///
+/// Each of the selected edges is replaced by a round face with the given
+/// radius, which is tangent to the two faces adjacent to the edge.
+///
+/// # Limitations
+///
+/// The same limitations as for [`Chamfer`] apply.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Fillet {
+    /// The shape whose edges are rounded
+    pub shape: Shape3d,
+
+    /// The radius of the rounded edges
+    pub radius: f64,
+
+    /// The edges that are rounded
+    pub edges: EdgeSelection,
+}
+
+impl From<Fillet> for Shape {
+    fn from(shape: Fillet) -> Self {
+        Self::Shape3d(Shape3d::Fillet(Box::new(shape)))
+    }
+}
+
+impl From<Fillet> for Shape3d {
+    fn from(shape: Fillet) -> Self {
+        Self::Fillet(Box::new(shape))
+    }
+}
+
+/// The intersection of two 3-dimensional shapes
+///
+/// The resulting shape only consists of the volume that is shared by both
+/// shapes.
 ///
 /// # Limitations
 ///
-/// Transformations are currently limited to a rotation, followed by a
-/// translation.
+/// The intersection is computed from the triangle meshes that approximate the
+/// two shapes. The resulting shape is a triangle mesh too, which means that any
+/// information about curved faces is lost after this operation.
 ///
-/// See issue:
-/// https://github.com/hannobraun/Fornjot/issues/101
+/// Both shapes need to be closed, meaning they must not have any holes in their
+/// surface. Otherwise the result is undefined.
 #[derive(Clone, Debug)]
 #[repr(C)]
-pub struct Transform {
-    /// The shape being rotated
+pub struct Intersection {
+    /// The first of the shapes
+    pub a: Shape3d,
+
+    /// The second of the shapes
+    pub b: Shape3d,
+}
+
+impl From<Intersection> for Shape {
+    fn from(shape: Intersection) -> Self {
+        Self::Shape3d(Shape3d::Intersection(Box::new(shape)))
+    }
+}
+
+impl From<Intersection> for Shape3d {
+    fn from(shape: Intersection) -> Self {
+        Self::Intersection(Box::new(shape))
+    }
+}
+
+/// A 3-dimensional shape, repeated along a straight line
+///
+/// The shape is repeated `count` times. The first copy is the original shape.
+/// Each other copy is translated by `offset`, relative to the previous one.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct LinearPattern {
+    /// The shape being repeated
     pub shape: Shape3d,
 
+    /// The offset between two consecutive copies
+    pub offset: [f64; 3],
+
+    /// The number of copies
+    pub count: u32,
+}
+
+impl From<LinearPattern> for Shape {
+    fn from(shape: LinearPattern) -> Self {
+        Self::Shape3d(Shape3d::LinearPattern(Box::new(shape)))
+    }
+}
+
+impl From<LinearPattern> for Shape3d {
+    fn from(shape: LinearPattern) -> Self {
+        Self::LinearPattern(Box::new(shape))
+    }
+}
+
+/// A loft between a sequence of 2-dimensional shapes
+///
+/// Each of the 2-dimensional shapes, called profiles, is placed in 3D space.
+/// The loft connects each profile to the next one with side faces. The first
+/// and the last profile close off the loft.
+///
+/// If the profiles have the same number of edges, each edge of a profile is
+/// connected to an edge of the next profile. Otherwise, the boundaries of the
+/// profiles are connected as a whole.
+///
+/// # Limitations
+///
+/// All profiles must consist of the same number of outer boundaries and holes.
+/// Each profile should be placed further along the normal of the previous
+/// profile, and the profiles must not intersect each other.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Loft {
+    // The fields are the raw parts of a `Vec`. `Loft` needs to be FFI-safe,
+    // meaning it can't store a `Vec` directly. It needs to take this detour.
+    ptr: *mut LoftProfile,
+    length: usize,
+    capacity: usize,
+}
+
+impl Loft {
+    /// Create a loft from a sequence of profiles
+    ///
+    /// At least two profiles are required.
+    pub fn from_profiles(mut profiles: Vec<LoftProfile>) -> Self {
+        // This can be cleaned up, once `Vec::into_raw_parts` is stable.
+        let ptr = profiles.as_mut_ptr();
+        let length = profiles.len();
+        let capacity = profiles.capacity();
+
+        // We're taking ownership of the memory here, so we can't allow
+        // `profiles` to deallocate it.
+        mem::forget(profiles);
+
+        Self {
+            ptr,
+            length,
+            capacity,
+        }
+    }
+
+    /// Return the profiles of the loft
+    pub fn to_profiles(&self) -> Vec<LoftProfile> {
+        // This is sound. All invariants are automatically kept, as the raw
+        // parts come from an original `Vec` that is identical to the new one we
+        // create here, and aren't being modified anywhere.
+        let profiles = unsafe {
+            Vec::from_raw_parts(self.ptr, self.length, self.capacity)
+        };
+
+        // Ownership of the pointer in `self.raw_parts` transferred to
+        // `profiles`. We work around that, by returning a clone of `profiles`
+        // (hence not giving ownership to the caller).
+        let ret = profiles.clone();
+
+        // Now we just need to forget that `profiles` ever existed, and we keep
+        // ownership of the pointer.
+        mem::forget(profiles);
+
+        ret
+    }
+}
+
+impl From<Loft> for Shape {
+    fn from(shape: Loft) -> Self {
+        Self::Shape3d(Shape3d::Loft(shape))
+    }
+}
+
+impl From<Loft> for Shape3d {
+    fn from(shape: Loft) -> Self {
+        Self::Loft(shape)
+    }
+}
+
+// `Loft` can be `Send`, because it encapsulates the raw pointer it contains,
+// making sure memory ownership rules are observed.
+unsafe impl Send for Loft {}
+
+/// A profile of a [`Loft`]
+///
+/// The 2-dimensional shape is defined in the x-y plane. It is placed in 3D
+/// space by a rotation, followed by a translation.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct LoftProfile {
+    /// The 2-dimensional shape of the profile
+    pub shape: Shape2d,
+
     /// The axis of the rotation
     pub axis: [f64; 3],
 
@@ -79,6 +437,258 @@ pub struct Transform {
     pub offset: [f64; 3],
 }
 
+impl LoftProfile {
+    /// Create a profile that is parallel to the x-y plane
+    ///
+    /// `height` is the z coordinate of the profile.
+    pub fn at_height(shape: impl Into<Shape2d>, height: f64) -> Self {
+        Self {
+            shape: shape.into(),
+            axis: [1., 0., 0.],
+            angle: 0.,
+            offset: [0., 0., height],
+        }
+    }
+}
+
+/// A sweep of a 2-dimensional shape along a path
+///
+/// The 2-dimensional shape is defined in the x-y plane. It is moved to the
+/// start of the path, and rotated, such that the z-axis points along the path.
+/// As the shape moves along the path, it is rotated as little as possible, to
+/// keep it perpendicular to the path.
+///
+/// At the corners of a polyline, the swept shape is mitered.
+///
+/// # Limitations
+///
+/// The swept shape must not intersect itself. This is the case, if the path
+/// bends too sharply for the size of the shape, or if the turns of a helix are
+/// too close to each other.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct PathSweep {
+    /// The 2-dimensional shape being swept
+    pub shape: Shape2d,
+
+    /// The path along which the shape is swept
+    pub path: Path,
+}
+
+impl From<PathSweep> for Shape {
+    fn from(shape: PathSweep) -> Self {
+        Self::Shape3d(Shape3d::PathSweep(shape))
+    }
+}
+
+impl From<PathSweep> for Shape3d {
+    fn from(shape: PathSweep) -> Self {
+        Self::PathSweep(shape)
+    }
+}
+
+/// A revolution of a 2-dimensional shape around an axis
+///
+/// The 2-dimensional shape is defined in the x-y plane. It is revolved around
+/// an axis that goes through the origin and should lie in the x-y plane too.
+/// The shape must not cross the axis, but it may touch it.
+///
+/// # Limitations
+///
+/// Revolutions of shapes whose edges include circles are only supported for
+/// full revolutions.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Revolve {
+    /// The 2-dimensional shape being revolved
+    pub shape: Shape2d,
+
+    /// The direction of the axis that the shape is revolved around
+    pub axis: [f64; 3],
+
+    /// The angle of the revolution, in radians
+    ///
+    /// The revolution follows the right-hand rule. A negative angle revolves
+    /// the shape in the opposite direction. Angles whose magnitude is larger
+    /// than a full revolution are treated as a full revolution.
+    pub angle: f64,
+}
+
+impl From<Revolve> for Shape {
+    fn from(shape: Revolve) -> Self {
+        Self::Shape3d(Shape3d::Revolve(shape))
+    }
+}
+
+impl From<Revolve> for Shape3d {
+    fn from(shape: Revolve) -> Self {
+        Self::Revolve(shape)
+    }
+}
+
+/// A hollowed 3-dimensional shape
+///
+/// The interior of the shape is removed, leaving walls of the given thickness.
+/// Selected faces can be removed too, leaving the shape open there.
+///
+/// # Limitations
+///
+/// Only sweeps along the z-axis without a draft angle, and cylinders, are
+/// supported. Arcs in the boundaries of swept shapes are approximated by lines
+/// on the inside of the walls. Models with shells of other shapes, or with
+/// walls that don't fit into the shape, are rejected when they're loaded.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Shell {
+    /// The shape that is hollowed
+    pub shape: Shape3d,
+
+    /// The thickness of the walls
+    pub thickness: f64,
+
+    /// The faces that are removed
+    pub open_faces: OpenFaces,
+}
+
+impl From<Shell> for Shape {
+    fn from(shape: Shell) -> Self {
+        Self::Shape3d(Shape3d::Shell(Box::new(shape)))
+    }
+}
+
+impl From<Shell> for Shape3d {
+    fn from(shape: Shell) -> Self {
+        Self::Shell(Box::new(shape))
+    }
+}
+
+/// The faces of a [`Shell`] that are removed
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub enum OpenFaces {
+    /// No faces are removed, resulting in a closed, hollow shape
+    None,
+
+    /// The top face, the face with the highest z coordinate, is removed
+    Top,
+
+    /// The bottom face, the face with the lowest z coordinate, is removed
+    Bottom,
+
+    /// The top and bottom faces are removed
+    TopAndBottom,
+}
+
+/// A sphere, centered at the origin
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Sphere {
+    /// The radius of the sphere
+    pub radius: f64,
+}
+
+impl From<Sphere> for Shape {
+    fn from(shape: Sphere) -> Self {
+        Self::Shape3d(Shape3d::Sphere(shape))
+    }
+}
+
+impl From<Sphere> for Shape3d {
+    fn from(shape: Sphere) -> Self {
+        Self::Sphere(shape)
+    }
+}
+
+/// A transformed 3-dimensional shape
+///
+/// The transformation is affine, meaning it can rotate, translate, scale,
+/// mirror, and shear the shape. Transformations that mirror the shape also flip
+/// its faces, so they keep pointing outward.
+///
+/// Use the constructors of this struct to create common transformations. Nest
+/// multiple `Transform`s to combine them.
+///
+/// # Limitations
+///
+/// Shapes that are scaled non-uniformly, or sheared, are approximated with
+/// triangles. Rotations, translations, uniform scaling, and mirroring keep the
+/// exact geometry of the shape.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Transform {
+    /// The shape being transformed
+    pub shape: Shape3d,
+
+    /// The transformation, as a 4x4 matrix in column-major order
+    ///
+    /// The last row of the matrix must be `[0., 0., 0., 1.]`.
+    pub matrix: [f64; 16],
+}
+
+impl Transform {
+    /// Create a transformation from a 4x4 matrix in column-major order
+    pub fn from_matrix(shape: impl Into<Shape3d>, matrix: [f64; 16]) -> Self {
+        Self {
+            shape: shape.into(),
+            matrix,
+        }
+    }
+
+    /// Create a rotation around an axis through the origin
+    ///
+    /// `axis` doesn't need to be normalized. `angle` is in radians.
+    pub fn rotation(
+        shape: impl Into<Shape3d>,
+        axis: [f64; 3],
+        angle: f64,
+    ) -> Self {
+        let [x, y, z] = normalize(axis);
+        let (s, c) = angle.sin_cos();
+        let t = 1. - c;
+
+        let rows = [
+            [c + x * x * t, x * y * t - z * s, x * z * t + y * s],
+            [y * x * t + z * s, c + y * y * t, y * z * t - x * s],
+            [z * x * t - y * s, z * y * t + x * s, c + z * z * t],
+        ];
+
+        Self::from_matrix(shape, affine(rows, [0.; 3]))
+    }
+
+    /// Create a translation
+    pub fn translation(shape: impl Into<Shape3d>, offset: [f64; 3]) -> Self {
+        Self::from_matrix(shape, affine(IDENTITY, offset))
+    }
+
+    /// Create a scaling along the coordinate axes
+    ///
+    /// The shape is scaled by a separate factor along each axis, with the
+    /// origin as the fixed point. Negative factors also mirror the shape.
+    pub fn scaling(shape: impl Into<Shape3d>, factors: [f64; 3]) -> Self {
+        let [x, y, z] = factors;
+        let rows = [[x, 0., 0.], [0., y, 0.], [0., 0., z]];
+
+        Self::from_matrix(shape, affine(rows, [0.; 3]))
+    }
+
+    /// Create a mirroring across a plane through the origin
+    ///
+    /// The plane is defined by its normal, which doesn't need to be
+    /// normalized.
+    pub fn mirror(shape: impl Into<Shape3d>, normal: [f64; 3]) -> Self {
+        let n = normalize(normal);
+
+        let mut rows = IDENTITY;
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value -= 2. * n[i] * n[j];
+            }
+        }
+
+        Self::from_matrix(shape, affine(rows, [0.; 3]))
+    }
+}
+
 impl From<Transform> for Shape {
     fn from(shape: Transform) -> Self {
         Self::Shape3d(Shape3d::Transform(Box::new(shape)))
@@ -91,7 +701,34 @@ impl From<Transform> for Shape3d {
     }
 }
 
-/// A sweep of a 2-dimensional shape along the z-axis
+const IDENTITY: [[f64; 3]; 3] = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+/// Create a column-major 4x4 matrix from a linear part and an offset
+///
+/// The linear part is given as rows.
+fn affine(rows: [[f64; 3]; 3], offset: [f64; 3]) -> [f64; 16] {
+    let mut matrix = [0.; 16];
+    for (i, row) in rows.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            matrix[j * 4 + i] = value;
+        }
+    }
+    matrix[12..15].copy_from_slice(&offset);
+    matrix[15] = 1.;
+
+    matrix
+}
+
+fn normalize([x, y, z]: [f64; 3]) -> [f64; 3] {
+    let length = (x * x + y * y + z * z).sqrt();
+    [x / length, y / length, z / length]
+}
+
+/// A sweep of a 2-dimensional shape along a straight path
+///
+/// The path is defined by `direction` and `length`. Its start point is either
+/// the origin, or, if the sweep is symmetric, half the length of the sweep in
+/// the opposite direction.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Sweep {
@@ -99,7 +736,52 @@ pub struct Sweep {
     pub shape: Shape2d,
 
     /// The length of the sweep
+    ///
+    /// A negative length sweeps the shape in the opposite of `direction`.
     pub length: f64,
+
+    /// The direction of the sweep
+    ///
+    /// Doesn't need to be normalized, but must not be parallel to the x-y
+    /// plane, in which the 2-dimensional shape is defined.
+    pub direction: [f64; 3],
+
+    /// Indicates whether the sweep extends to both sides of the shape
+    ///
+    /// If this is `true`, the shape is swept half the length into each
+    /// direction.
+    pub symmetric: bool,
+
+    /// The draft angle of the sweep, in radians
+    ///
+    /// With a draft angle, the boundary of the shape is moved inward as the
+    /// shape is swept, by the distance travelled times the tangent of the
+    /// angle. A negative angle moves the boundary outward instead. Holes grow
+    /// when the rest of the shape shrinks, and vice versa.
+    ///
+    /// For symmetric sweeps, the distance is measured from the original shape.
+    /// The shape is larger than the original at the start of the sweep, and
+    /// smaller at the end (or the other way around, for a negative angle).
+    ///
+    /// The angle must be small enough that no part of the shape vanishes over
+    /// the length of the sweep.
+    pub draft: f64,
+}
+
+impl Sweep {
+    /// Create a sweep along the z-axis
+    ///
+    /// This is the most common kind of sweep. Use the other fields of [`Sweep`]
+    /// to customize it further.
+    pub fn from_length(shape: impl Into<Shape2d>, length: f64) -> Self {
+        Self {
+            shape: shape.into(),
+            length,
+            direction: [0., 0., 1.],
+            symmetric: false,
+            draft: 0.,
+        }
+    }
 }
 
 impl From<Sweep> for Shape {
@@ -114,20 +796,43 @@ impl From<Sweep> for Shape3d {
     }
 }
 
+/// A torus
+///
+/// The torus is centered at the origin. Its axis is the z-axis.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Torus {
+    /// The distance from the center of the torus to the center of the tube
+    pub major_radius: f64,
+
+    /// The radius of the tube
+    ///
+    /// Must be smaller than `major_radius`.
+    pub minor_radius: f64,
+}
+
+impl From<Torus> for Shape {
+    fn from(shape: Torus) -> Self {
+        Self::Shape3d(Shape3d::Torus(shape))
+    }
+}
+
+impl From<Torus> for Shape3d {
+    fn from(shape: Torus) -> Self {
+        Self::Torus(shape)
+    }
+}
+
 /// The union of two 3-dimensional shapes
 ///
 /// # Limitations
 ///
-/// Support for unions is somewhat limited right now. A union of 2 distinct
-/// shapes doesn't really create a new shape, but just an aggregation of the
-/// two original shapes.
-///
-/// This means, for example, that generating the triangle mesh of the union does
-/// not result in a proper triangle mesh, but rather the two, possibly
-/// intersecting, triangle meshes of the original shapes.
+/// The union is computed from the triangle meshes that approximate the two
+/// shapes. The resulting shape is a triangle mesh too, which means that any
+/// information about curved faces is lost after this operation.
 ///
-/// See issue:
-/// https://github.com/hannobraun/Fornjot/issues/42
+/// Both shapes need to be closed, meaning they must not have any holes in their
+/// surface. Otherwise the result is undefined.
 #[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Union {
@@ -0,0 +1,142 @@
+use crate::{SketchCycle, SketchSegment};
+
+impl SketchSegment {
+    /// Create the segments of a cubic B-spline
+    ///
+    /// The B-spline is defined by its control points, and has uniformly spaced
+    /// knots. It starts at the first control point and ends at the last one,
+    /// which must be the start of the segment that comes after the B-spline.
+    /// The last control point is not part of the returned segments.
+    ///
+    /// The B-spline is converted into Bézier segments (see
+    /// [`SegmentKind::Bezier`](crate::SegmentKind::Bezier)), which represent it
+    /// exactly. With fewer than four control points, it is a line or a
+    /// quadratic Bézier curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if there are fewer than two control points.
+    pub fn b_spline(control: &[[f64; 2]]) -> Vec<Self> {
+        let beziers = match control {
+            [] | [_] => {
+                panic!("A B-spline needs at least two control points")
+            }
+            [a, _] => return vec![Self::line(*a)],
+            [a, b, c] => {
+                // A quadratic Bézier curve is also a cubic one, with its
+                // control points moved two thirds towards the middle one.
+                vec![[*a, lerp(*a, *b, 2. / 3.), lerp(*c, *b, 2. / 3.), *c]]
+            }
+            _ => clamped_b_spline_to_beziers(control),
+        };
+
+        beziers
+            .into_iter()
+            .map(|[start, a, b, _]| Self::bezier(start, [a, b]))
+            .collect()
+    }
+}
+
+impl SketchCycle {
+    /// Create a cycle from a closed cubic B-spline
+    ///
+    /// The B-spline is defined by its control points, and has uniformly spaced
+    /// knots. Its ends connect smoothly. Unlike with an open B-spline, it
+    /// doesn't go through any of its control points.
+    ///
+    /// The B-spline is converted into Bézier segments (see
+    /// [`SegmentKind::Bezier`](crate::SegmentKind::Bezier)), which represent it
+    /// exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if there are fewer than three control points.
+    pub fn from_b_spline(control: &[[f64; 2]]) -> Self {
+        let n = control.len();
+        assert!(
+            n >= 3,
+            "A closed B-spline needs at least three control points"
+        );
+
+        // This is the well-known conversion of a uniform cubic B-spline into
+        // Bézier segments. Each segment is defined by four consecutive control
+        // points.
+        let segments = (0..n)
+            .map(|i| {
+                let [previous, a, b] =
+                    [(i + n - 1) % n, i, (i + 1) % n].map(|j| control[j]);
+
+                let start =
+                    [0, 1].map(|k| (previous[k] + 4. * a[k] + b[k]) / 6.);
+
+                SketchSegment::bezier(
+                    start,
+                    [lerp(a, b, 1. / 3.), lerp(a, b, 2. / 3.)],
+                )
+            })
+            .collect();
+
+        Self::from_segments(segments)
+    }
+}
+
+/// Convert an open, clamped, uniform cubic B-spline into Bézier curves
+///
+/// Every interior knot is inserted, until it has the same multiplicity as the
+/// degree. At that point, the control points are those of the Bézier curves.
+fn clamped_b_spline_to_beziers(control: &[[f64; 2]]) -> Vec<[[f64; 2]; 4]> {
+    const DEGREE: usize = 3;
+
+    let spans = control.len() - DEGREE;
+
+    let mut knots = vec![0.; DEGREE + 1];
+    knots.extend((1..spans).map(|knot| knot as f64));
+    knots.extend(vec![spans as f64; DEGREE + 1]);
+
+    let mut points = control.to_vec();
+
+    for knot in 1..spans {
+        for _ in 1..DEGREE {
+            insert_knot(&mut knots, &mut points, knot as f64, DEGREE);
+        }
+    }
+
+    points
+        .windows(DEGREE + 1)
+        .step_by(DEGREE)
+        .map(|points| [points[0], points[1], points[2], points[3]])
+        .collect()
+}
+
+/// Insert a knot into a B-spline, using Boehm's algorithm
+fn insert_knot(
+    knots: &mut Vec<f64>,
+    points: &mut Vec<[f64; 2]>,
+    knot: f64,
+    degree: usize,
+) {
+    // The span that the knot is inserted into. If the knot already exists,
+    // that's the span after it.
+    let k = knots.iter().rposition(|&other| other <= knot).unwrap();
+
+    let mut new_points = Vec::with_capacity(points.len() + 1);
+    for i in 0..=points.len() {
+        let point = if i + degree <= k {
+            points[i]
+        } else if i > k {
+            points[i - 1]
+        } else {
+            let alpha = (knot - knots[i]) / (knots[i + degree] - knots[i]);
+            lerp(points[i - 1], points[i], alpha)
+        };
+
+        new_points.push(point);
+    }
+
+    knots.insert(k + 1, knot);
+    *points = new_points;
+}
+
+fn lerp(a: [f64; 2], b: [f64; 2], t: f64) -> [f64; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
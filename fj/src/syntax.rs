@@ -1,36 +1,92 @@
-pub trait Rotate {
-    /// Create a rotation
+pub trait Chamfer {
+    /// Create a chamfer
     ///
-    /// Create a rotation that rotates `shape` by `angle` around an axis defined
-    /// by `axis`.
-    fn rotate(&self, axis: [f64; 3], angle: f64) -> crate::Transform;
+    /// Create a shape with the selected edges of `shape` beveled.
+    fn chamfer(
+        &self,
+        distance: f64,
+        edges: crate::EdgeSelection,
+    ) -> crate::Chamfer;
 }
 
-impl<T> Rotate for T
+impl<T> Chamfer for T
 where
     T: Clone + Into<crate::Shape3d>,
 {
-    fn rotate(&self, axis: [f64; 3], angle: f64) -> crate::Transform {
+    fn chamfer(
+        &self,
+        distance: f64,
+        edges: crate::EdgeSelection,
+    ) -> crate::Chamfer {
+        let shape = self.clone().into();
+        crate::Chamfer {
+            shape,
+            distance,
+            edges,
+        }
+    }
+}
+
+pub trait CircularPattern {
+    /// Create a circular pattern
+    ///
+    /// Create a pattern that repeats `shape` `count` times, rotating each copy
+    /// by `angle` around an axis through the origin, defined by `axis`.
+    fn circular_pattern(
+        &self,
+        axis: [f64; 3],
+        angle: f64,
+        count: u32,
+    ) -> crate::CircularPattern;
+}
+
+impl<T> CircularPattern for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn circular_pattern(
+        &self,
+        axis: [f64; 3],
+        angle: f64,
+        count: u32,
+    ) -> crate::CircularPattern {
         let shape = self.clone().into();
-        crate::Transform {
+        crate::CircularPattern {
             shape,
             axis,
             angle,
-            offset: [0.; 3],
+            count,
         }
     }
 }
 
-pub trait Sketch {
-    fn sketch(&self) -> crate::Sketch;
+pub trait CircularPattern2d {
+    /// Create a circular pattern
+    ///
+    /// Create a pattern that repeats `shape` `count` times, rotating each copy
+    /// by `angle` around the origin.
+    fn circular_pattern(
+        &self,
+        angle: f64,
+        count: u32,
+    ) -> crate::CircularPattern2d;
 }
 
-impl<T> Sketch for T
+impl<T> CircularPattern2d for T
 where
-    T: AsRef<[[f64; 2]]>,
+    T: Clone + Into<crate::Shape2d>,
 {
-    fn sketch(&self) -> crate::Sketch {
-        crate::Sketch::from_points(self.as_ref().to_vec())
+    fn circular_pattern(
+        &self,
+        angle: f64,
+        count: u32,
+    ) -> crate::CircularPattern2d {
+        let shape = self.clone().into();
+        crate::CircularPattern2d {
+            shape,
+            angle,
+            count,
+        }
     }
 }
 
@@ -55,8 +111,356 @@ where
     }
 }
 
+pub trait Fillet {
+    /// Create a fillet
+    ///
+    /// Create a shape with the selected edges of `shape` rounded.
+    fn fillet(&self, radius: f64, edges: crate::EdgeSelection)
+        -> crate::Fillet;
+}
+
+impl<T> Fillet for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn fillet(
+        &self,
+        radius: f64,
+        edges: crate::EdgeSelection,
+    ) -> crate::Fillet {
+        let shape = self.clone().into();
+        crate::Fillet {
+            shape,
+            radius,
+            edges,
+        }
+    }
+}
+
+pub trait Intersection {
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection
+    where
+        Other: Clone + Into<crate::Shape3d>;
+}
+
+impl<T> Intersection for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection
+    where
+        Other: Clone + Into<crate::Shape3d>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Intersection { a, b }
+    }
+}
+
+pub trait LinearPattern {
+    /// Create a linear pattern
+    ///
+    /// Create a pattern that repeats `shape` `count` times, translating each
+    /// copy by `offset`.
+    fn linear_pattern(
+        &self,
+        offset: [f64; 3],
+        count: u32,
+    ) -> crate::LinearPattern;
+}
+
+impl<T> LinearPattern for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn linear_pattern(
+        &self,
+        offset: [f64; 3],
+        count: u32,
+    ) -> crate::LinearPattern {
+        let shape = self.clone().into();
+        crate::LinearPattern {
+            shape,
+            offset,
+            count,
+        }
+    }
+}
+
+pub trait LinearPattern2d {
+    /// Create a linear pattern
+    ///
+    /// Create a pattern that repeats `shape` `count` times, translating each
+    /// copy by `offset`.
+    fn linear_pattern(
+        &self,
+        offset: [f64; 2],
+        count: u32,
+    ) -> crate::LinearPattern2d;
+}
+
+impl<T> LinearPattern2d for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn linear_pattern(
+        &self,
+        offset: [f64; 2],
+        count: u32,
+    ) -> crate::LinearPattern2d {
+        let shape = self.clone().into();
+        crate::LinearPattern2d {
+            shape,
+            offset,
+            count,
+        }
+    }
+}
+
+pub trait Mirror {
+    /// Create a mirroring
+    ///
+    /// Create a transformation that mirrors `shape` across a plane through the
+    /// origin, defined by its `normal`.
+    fn mirror(&self, normal: [f64; 3]) -> crate::Transform;
+}
+
+impl<T> Mirror for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn mirror(&self, normal: [f64; 3]) -> crate::Transform {
+        crate::Transform::mirror(self.clone(), normal)
+    }
+}
+
+pub trait Mirror2d {
+    /// Create a mirroring
+    ///
+    /// Create a transformation that mirrors `shape` across a line through the
+    /// origin, defined by its `normal`.
+    fn mirror(&self, normal: [f64; 2]) -> crate::Transform2d;
+}
+
+impl<T> Mirror2d for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn mirror(&self, normal: [f64; 2]) -> crate::Transform2d {
+        crate::Transform2d::mirror(self.clone(), normal)
+    }
+}
+
+pub trait Offset {
+    /// Create an offset
+    ///
+    /// Create a shape that is `shape` grown by `distance`. Negative distances
+    /// shrink the shape.
+    fn offset(&self, distance: f64) -> crate::Offset2d;
+}
+
+impl<T> Offset for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn offset(&self, distance: f64) -> crate::Offset2d {
+        let shape = self.clone().into();
+        crate::Offset2d { shape, distance }
+    }
+}
+
+pub trait OnPlane {
+    /// Place a shape on a plane
+    fn on_plane<P>(&self, plane: P) -> crate::OnPlane
+    where
+        P: Into<crate::Plane>;
+}
+
+impl<T> OnPlane for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn on_plane<P>(&self, plane: P) -> crate::OnPlane
+    where
+        P: Into<crate::Plane>,
+    {
+        let shape = self.clone().into();
+        let plane = plane.into();
+
+        crate::OnPlane { shape, plane }
+    }
+}
+
+pub trait PathSweep {
+    /// Create a sweep along a path
+    fn sweep_path<P>(&self, path: P) -> crate::PathSweep
+    where
+        P: Into<crate::Path>;
+}
+
+impl<T> PathSweep for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn sweep_path<P>(&self, path: P) -> crate::PathSweep
+    where
+        P: Into<crate::Path>,
+    {
+        let shape = self.clone().into();
+        let path = path.into();
+
+        crate::PathSweep { shape, path }
+    }
+}
+
+pub trait Revolve {
+    /// Create a revolution
+    ///
+    /// Create a revolution that revolves `shape` by `angle` around an axis
+    /// through the origin, defined by `axis`.
+    fn revolve(&self, axis: [f64; 3], angle: f64) -> crate::Revolve;
+}
+
+impl<T> Revolve for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn revolve(&self, axis: [f64; 3], angle: f64) -> crate::Revolve {
+        let shape = self.clone().into();
+        crate::Revolve { shape, axis, angle }
+    }
+}
+
+pub trait Rotate {
+    /// Create a rotation
+    ///
+    /// Create a rotation that rotates `shape` by `angle` around an axis defined
+    /// by `axis`.
+    fn rotate(&self, axis: [f64; 3], angle: f64) -> crate::Transform;
+}
+
+impl<T> Rotate for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn rotate(&self, axis: [f64; 3], angle: f64) -> crate::Transform {
+        crate::Transform::rotation(self.clone(), axis, angle)
+    }
+}
+
+pub trait Rotate2d {
+    /// Create a rotation
+    ///
+    /// Create a rotation that rotates `shape` by `angle` around the origin.
+    fn rotate(&self, angle: f64) -> crate::Transform2d;
+}
+
+impl<T> Rotate2d for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn rotate(&self, angle: f64) -> crate::Transform2d {
+        crate::Transform2d::rotation(self.clone(), angle)
+    }
+}
+
+pub trait Scale {
+    /// Create a scaling
+    ///
+    /// Create a transformation that scales `shape` by a separate factor along
+    /// each of the coordinate axes.
+    fn scale(&self, factors: [f64; 3]) -> crate::Transform;
+}
+
+impl<T> Scale for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn scale(&self, factors: [f64; 3]) -> crate::Transform {
+        crate::Transform::scaling(self.clone(), factors)
+    }
+}
+
+pub trait Scale2d {
+    /// Create a scaling
+    ///
+    /// Create a transformation that scales `shape` by a separate factor along
+    /// each of the coordinate axes.
+    fn scale(&self, factors: [f64; 2]) -> crate::Transform2d;
+}
+
+impl<T> Scale2d for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn scale(&self, factors: [f64; 2]) -> crate::Transform2d {
+        crate::Transform2d::scaling(self.clone(), factors)
+    }
+}
+
+pub trait Shell {
+    /// Create a shell
+    ///
+    /// Create a shape that is `shape` hollowed, with walls of the given
+    /// thickness.
+    fn shell(
+        &self,
+        thickness: f64,
+        open_faces: crate::OpenFaces,
+    ) -> crate::Shell;
+}
+
+impl<T> Shell for T
+where
+    T: Clone + Into<crate::Shape3d>,
+{
+    fn shell(
+        &self,
+        thickness: f64,
+        open_faces: crate::OpenFaces,
+    ) -> crate::Shell {
+        let shape = self.clone().into();
+        crate::Shell {
+            shape,
+            thickness,
+            open_faces,
+        }
+    }
+}
+
+pub trait Sketch {
+    fn sketch(&self) -> crate::Sketch;
+}
+
+impl<T> Sketch for T
+where
+    T: AsRef<[[f64; 2]]>,
+{
+    fn sketch(&self) -> crate::Sketch {
+        crate::Sketch::from_points(self.as_ref().to_vec())
+    }
+}
+
 pub trait Sweep {
+    /// Create a sweep along the z-axis
     fn sweep(&self, length: f64) -> crate::Sweep;
+
+    /// Create a sweep along an arbitrary direction
+    ///
+    /// `direction` doesn't need to be normalized. The length of the sweep is
+    /// defined by `length` alone.
+    fn sweep_along(&self, direction: [f64; 3], length: f64) -> crate::Sweep;
+
+    /// Create a sweep along the z-axis, that extends to both sides
+    ///
+    /// The shape is swept half of `length` in each direction.
+    fn sweep_symmetric(&self, length: f64) -> crate::Sweep;
+
+    /// Create a sweep along the z-axis, with a draft angle
+    ///
+    /// See [`crate::Sweep::draft`] for the meaning of `draft`.
+    fn sweep_drafted(&self, length: f64, draft: f64) -> crate::Sweep;
 }
 
 impl<T> Sweep for T
@@ -64,8 +468,28 @@ where
     T: Clone + Into<crate::Shape2d>,
 {
     fn sweep(&self, length: f64) -> crate::Sweep {
-        let shape = self.clone().into();
-        crate::Sweep { shape, length }
+        crate::Sweep::from_length(self.clone(), length)
+    }
+
+    fn sweep_along(&self, direction: [f64; 3], length: f64) -> crate::Sweep {
+        crate::Sweep {
+            direction,
+            ..crate::Sweep::from_length(self.clone(), length)
+        }
+    }
+
+    fn sweep_symmetric(&self, length: f64) -> crate::Sweep {
+        crate::Sweep {
+            symmetric: true,
+            ..crate::Sweep::from_length(self.clone(), length)
+        }
+    }
+
+    fn sweep_drafted(&self, length: f64, draft: f64) -> crate::Sweep {
+        crate::Sweep {
+            draft,
+            ..crate::Sweep::from_length(self.clone(), length)
+        }
     }
 }
 
@@ -81,13 +505,23 @@ where
     T: Clone + Into<crate::Shape3d>,
 {
     fn translate(&self, offset: [f64; 3]) -> crate::Transform {
-        let shape = self.clone().into();
-        crate::Transform {
-            shape,
-            axis: [1., 0., 0.],
-            angle: 0.,
-            offset,
-        }
+        crate::Transform::translation(self.clone(), offset)
+    }
+}
+
+pub trait Translate2d {
+    /// Create a translation
+    ///
+    /// Create a translation that translates `shape` by `offset`.
+    fn translate(&self, offset: [f64; 2]) -> crate::Transform2d;
+}
+
+impl<T> Translate2d for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn translate(&self, offset: [f64; 2]) -> crate::Transform2d {
+        crate::Transform2d::translation(self.clone(), offset)
     }
 }
 
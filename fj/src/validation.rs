@@ -0,0 +1,748 @@
+use std::{error::Error, f64::consts::PI, fmt};
+
+use crate::{shape_2d::arc, SegmentKind, Sketch, SketchSegment};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-9;
+
+/// The number of lines that a full circle is approximated with
+///
+/// Arcs need to be approximated for the intersection tests. This doesn't need
+/// to be very precise, as sketches with arcs that almost, but don't quite,
+/// intersect are unlikely to be a problem in practice.
+const LINES_PER_CIRCLE: f64 = 64.;
+
+/// The number of lines that a Bézier curve is approximated with
+///
+/// See [`LINES_PER_CIRCLE`].
+const LINES_PER_BEZIER: usize = 32;
+
+impl Sketch {
+    /// Check whether the sketch is valid
+    ///
+    /// A sketch is valid, if all of the following is true:
+    ///
+    /// - All arcs can be constructed from their parameters.
+    /// - No segment has zero length.
+    /// - No point appears more than once within a cycle.
+    /// - Every cycle encloses an area.
+    /// - No segments intersect, except for neighboring segments touching at
+    ///   their shared point.
+    /// - All holes are within the outer boundary, and no hole is within
+    ///   another hole.
+    /// - All holes wind in the opposite direction of the outer boundary.
+    ///
+    /// The outer boundary itself may wind clockwise or counter-clockwise.
+    ///
+    /// Returns the first problem that is found. Points are referred to by their
+    /// index within their cycle. Segments are referred to by the index of the
+    /// point they start at.
+    pub fn validate(&self) -> Result<(), SketchError> {
+        let mut cycles = Vec::new();
+
+        for (i, cycle) in self.to_cycles().iter().enumerate() {
+            let segments = cycle.to_segments();
+            if segments.is_empty() {
+                continue;
+            }
+
+            let polyline = validate_cycle(i, &segments)?;
+            cycles.push((i, polyline));
+        }
+
+        for (i, (cycle_a, a)) in cycles.iter().enumerate() {
+            for (cycle_b, b) in &cycles[i + 1..] {
+                if !a.aabb_intersects(b) {
+                    continue;
+                }
+
+                for piece_a in &a.pieces {
+                    for piece_b in &b.pieces {
+                        if touch(piece_a.points, piece_b.points) {
+                            return Err(SketchError::CycleIntersection {
+                                cycles: [*cycle_a, *cycle_b],
+                                points: [piece_a.segment, piece_b.segment],
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(((_, outer), holes)) = cycles.split_first() {
+            // The cycles don't intersect, so they are either within each other
+            // or disjoint. Checking a single point of a cycle is enough to find
+            // out which it is.
+            for (i, (cycle_a, a)) in holes.iter().enumerate() {
+                if !outer.contains(a.start()) {
+                    return Err(SketchError::HoleOutsideBoundary {
+                        cycle: *cycle_a,
+                    });
+                }
+
+                for (cycle_b, b) in &holes[i + 1..] {
+                    if a.contains(b.start()) || b.contains(a.start()) {
+                        return Err(SketchError::NestedHoles {
+                            cycles: [*cycle_a, *cycle_b],
+                        });
+                    }
+                }
+            }
+
+            let outer_is_ccw = outer.signed_area() > 0.;
+
+            for (cycle, hole) in holes {
+                if (hole.signed_area() > 0.) == outer_is_ccw {
+                    return Err(SketchError::WrongWinding { cycle: *cycle });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A problem with a sketch
+///
+/// See [`Sketch::validate`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SketchError {
+    /// An arc can't be constructed from its parameters
+    ///
+    /// Either the ends of an [`SegmentKind::Arc`] have different distances
+    /// from its center, or the radius of an [`SegmentKind::ArcWithRadius`] is
+    /// too small to connect its ends.
+    InvalidArc {
+        /// The index of the cycle
+        cycle: usize,
+
+        /// The index of the point that the arc starts at
+        point: usize,
+    },
+
+    /// A segment starts and ends at the same point
+    ZeroLengthEdge {
+        /// The index of the cycle
+        cycle: usize,
+
+        /// The index of the point that the segment starts at
+        point: usize,
+    },
+
+    /// Two points that aren't neighbors are identical
+    DuplicatePoint {
+        /// The index of the cycle
+        cycle: usize,
+
+        /// The indices of the identical points
+        points: [usize; 2],
+    },
+
+    /// A cycle doesn't enclose any area
+    ///
+    /// This is the case, if all of its points are on the same line, and all of
+    /// its segments are lines.
+    DegenerateCycle {
+        /// The index of the cycle
+        cycle: usize,
+    },
+
+    /// Two segments of a cycle intersect
+    SelfIntersection {
+        /// The index of the cycle
+        cycle: usize,
+
+        /// The indices of the points that the segments start at
+        points: [usize; 2],
+    },
+
+    /// Segments of two different cycles intersect
+    CycleIntersection {
+        /// The indices of the cycles
+        cycles: [usize; 2],
+
+        /// The indices of the points that the segments start at
+        ///
+        /// Each index refers to the cycle at the same position in `cycles`.
+        points: [usize; 2],
+    },
+
+    /// A hole is not within the outer boundary
+    HoleOutsideBoundary {
+        /// The index of the cycle
+        cycle: usize,
+    },
+
+    /// A hole is within another hole
+    NestedHoles {
+        /// The indices of the cycles
+        cycles: [usize; 2],
+    },
+
+    /// A hole winds in the same direction as the outer boundary
+    WrongWinding {
+        /// The index of the cycle
+        cycle: usize,
+    },
+}
+
+impl fmt::Display for SketchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidArc { cycle, point } => write!(
+                f,
+                "Invalid arc starting at point {} of cycle {}",
+                point, cycle
+            ),
+            Self::ZeroLengthEdge { cycle, point } => write!(
+                f,
+                "Zero-length edge starting at point {} of cycle {}",
+                point, cycle
+            ),
+            Self::DuplicatePoint {
+                cycle,
+                points: [a, b],
+            } => write!(
+                f,
+                "Points {} and {} of cycle {} are identical",
+                a, b, cycle
+            ),
+            Self::DegenerateCycle { cycle } => {
+                write!(f, "Cycle {} doesn't enclose any area", cycle)
+            }
+            Self::SelfIntersection {
+                cycle,
+                points: [a, b],
+            } => write!(
+                f,
+                "Segments starting at points {} and {} of cycle {} intersect",
+                a, b, cycle
+            ),
+            Self::CycleIntersection {
+                cycles: [cycle_a, cycle_b],
+                points: [a, b],
+            } => write!(
+                f,
+                "Segment starting at point {} of cycle {} intersects segment \
+                starting at point {} of cycle {}",
+                a, cycle_a, b, cycle_b
+            ),
+            Self::HoleOutsideBoundary { cycle } => {
+                write!(f, "Hole {} is not within the outer boundary", cycle)
+            }
+            Self::NestedHoles {
+                cycles: [cycle_a, cycle_b],
+            } => write!(f, "Holes {} and {} are nested", cycle_a, cycle_b),
+            Self::WrongWinding { cycle } => write!(
+                f,
+                "Hole {} winds in the same direction as the outer boundary",
+                cycle
+            ),
+        }
+    }
+}
+
+impl Error for SketchError {}
+
+/// Validate a single cycle, and compute the polyline that approximates it
+fn validate_cycle(
+    cycle: usize,
+    segments: &[SketchSegment],
+) -> Result<Polyline, SketchError> {
+    let points: Vec<_> = segments.iter().map(|segment| segment.start).collect();
+    let n = points.len();
+
+    for (i, segment) in segments.iter().enumerate() {
+        // A single arc is a full circle. A single Bézier curve loops back to
+        // its start.
+        let is_closed = n == 1
+            && matches!(
+                segment.kind,
+                SegmentKind::Arc { .. } | SegmentKind::Bezier { .. }
+            );
+
+        if !is_closed && distance(points[i], points[(i + 1) % n]) < EPSILON {
+            return Err(SketchError::ZeroLengthEdge { cycle, point: i });
+        }
+    }
+
+    for i in 0..n {
+        for j in i + 2..n {
+            // The first and last points are neighbors too.
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+
+            if distance(points[i], points[j]) < EPSILON {
+                return Err(SketchError::DuplicatePoint {
+                    cycle,
+                    points: [i, j],
+                });
+            }
+        }
+    }
+
+    let polyline = polyline(cycle, segments)?;
+
+    if polyline.is_collinear() {
+        return Err(SketchError::DegenerateCycle { cycle });
+    }
+
+    let pieces = &polyline.pieces;
+    for (i, a) in pieces.iter().enumerate() {
+        for (j, b) in pieces.iter().enumerate().skip(i + 1) {
+            if a.segment == b.segment {
+                // The pieces of a single segment can't intersect each other.
+                continue;
+            }
+
+            let intersect = if j == i + 1 {
+                overlap(a.points, b.points)
+            } else if i == 0 && j == pieces.len() - 1 {
+                overlap(b.points, a.points)
+            } else {
+                touch(a.points, b.points)
+            };
+
+            if intersect {
+                return Err(SketchError::SelfIntersection {
+                    cycle,
+                    points: [a.segment, b.segment],
+                });
+            }
+        }
+    }
+
+    Ok(polyline)
+}
+
+/// Compute the signed area of a cycle
+///
+/// Expects the cycle to be valid. The area is positive, if the cycle winds
+/// counter-clockwise.
+pub(crate) fn signed_area(segments: &[SketchSegment]) -> f64 {
+    polyline(0, segments)
+        .map(|polyline| polyline.signed_area())
+        .unwrap_or(0.)
+}
+
+/// Compute the polyline that approximates a cycle
+fn polyline(
+    cycle: usize,
+    segments: &[SketchSegment],
+) -> Result<Polyline, SketchError> {
+    let points: Vec<_> = segments.iter().map(|segment| segment.start).collect();
+    let n = points.len();
+
+    let mut polyline = Polyline { pieces: Vec::new() };
+    for (i, segment) in segments.iter().enumerate() {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+
+        let arc = arc(a, b, segment.kind)
+            .ok_or(SketchError::InvalidArc { cycle, point: i })?;
+
+        let mut segment_points = vec![a];
+        if let Some((center, counter_clockwise)) = arc {
+            segment_points.extend(arc_points(a, b, center, counter_clockwise));
+        }
+        if let SegmentKind::Bezier { control } = segment.kind {
+            segment_points.extend(bezier_points(a, b, control));
+        }
+        segment_points.push(b);
+
+        for window in segment_points.windows(2) {
+            polyline.pieces.push(Piece {
+                points: [window[0], window[1]],
+                segment: i,
+            });
+        }
+    }
+
+    Ok(polyline)
+}
+
+/// Compute the points between the ends of an arc
+fn arc_points(
+    a: [f64; 2],
+    b: [f64; 2],
+    center: [f64; 2],
+    counter_clockwise: bool,
+) -> Vec<[f64; 2]> {
+    let radius = distance(a, center);
+    let start = (a[1] - center[1]).atan2(a[0] - center[0]);
+    let end = (b[1] - center[1]).atan2(b[0] - center[0]);
+
+    let angle = if counter_clockwise {
+        end - start
+    } else {
+        start - end
+    };
+    let mut angle = angle.rem_euclid(2. * PI);
+    if angle < EPSILON {
+        // The ends are identical. This is a full circle.
+        angle = 2. * PI;
+    }
+    let angle = if counter_clockwise { angle } else { -angle };
+
+    let n = (angle.abs() / (2. * PI) * LINES_PER_CIRCLE).ceil() as usize;
+    (1..n)
+        .map(|i| {
+            let angle = start + angle / n as f64 * i as f64;
+            [
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]
+        })
+        .collect()
+}
+
+/// Compute the points between the ends of a Bézier curve
+fn bezier_points(
+    a: [f64; 2],
+    b: [f64; 2],
+    [c, d]: [[f64; 2]; 2],
+) -> Vec<[f64; 2]> {
+    (1..LINES_PER_BEZIER)
+        .map(|i| {
+            let t = i as f64 / LINES_PER_BEZIER as f64;
+            let s = 1. - t;
+
+            let weights =
+                [s * s * s, 3. * s * s * t, 3. * s * t * t, t * t * t];
+            let points = [a, c, d, b];
+
+            [0, 1].map(|k| {
+                weights.iter().zip(points).map(|(w, p)| w * p[k]).sum()
+            })
+        })
+        .collect()
+}
+
+/// A polyline that approximates a cycle
+struct Polyline {
+    pieces: Vec<Piece>,
+}
+
+impl Polyline {
+    fn signed_area(&self) -> f64 {
+        self.pieces
+            .iter()
+            .map(|piece| {
+                let [a, b] = piece.points;
+                a[0] * b[1] - b[0] * a[1]
+            })
+            .sum::<f64>()
+            / 2.
+    }
+
+    /// The point that the polyline starts at
+    fn start(&self) -> [f64; 2] {
+        self.pieces[0].points[0]
+    }
+
+    /// Determine whether a point is within the area enclosed by the polyline
+    ///
+    /// Points on the polyline may or may not be considered to be within.
+    fn contains(&self, point: [f64; 2]) -> bool {
+        // Count how often a ray from the point in positive x direction crosses
+        // the polyline. Each line includes only one of its ends, so a ray that
+        // passes through a point of the polyline counts as crossing it only if
+        // the polyline actually crosses the ray there.
+        let crossings = self
+            .pieces
+            .iter()
+            .filter(|piece| {
+                let [a, b] = piece.points;
+                if (a[1] > point[1]) == (b[1] > point[1]) {
+                    return false;
+                }
+
+                let x =
+                    a[0] + (point[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1]);
+                x > point[0]
+            })
+            .count();
+
+        crossings % 2 == 1
+    }
+
+    /// Determine whether all points of the polyline are on the same line
+    fn is_collinear(&self) -> bool {
+        let mut points = self.pieces.iter().map(|piece| piece.points[0]);
+
+        let origin = match points.next() {
+            Some(origin) => origin,
+            None => return true,
+        };
+        let furthest = points.clone().fold(origin, |furthest, point| {
+            if distance(origin, point) > distance(origin, furthest) {
+                point
+            } else {
+                furthest
+            }
+        });
+
+        let direction = sub(furthest, origin);
+        let length = distance(origin, furthest);
+
+        points.all(|point| {
+            let offset = sub(point, origin);
+            let cross = direction[0] * offset[1] - direction[1] * offset[0];
+
+            // This is the distance from the line, scaled by its length.
+            cross.abs() <= EPSILON * length
+        })
+    }
+
+    fn aabb(&self) -> [[f64; 2]; 2] {
+        let mut min = [f64::INFINITY; 2];
+        let mut max = [f64::NEG_INFINITY; 2];
+
+        for point in self.pieces.iter().flat_map(|piece| piece.points) {
+            for i in 0..2 {
+                min[i] = min[i].min(point[i]);
+                max[i] = max[i].max(point[i]);
+            }
+        }
+
+        [min, max]
+    }
+
+    fn aabb_intersects(&self, other: &Self) -> bool {
+        let [min_a, max_a] = self.aabb();
+        let [min_b, max_b] = other.aabb();
+
+        (0..2).all(|i| min_a[i] <= max_b[i] && min_b[i] <= max_a[i])
+    }
+}
+
+/// A line that is part of a polyline
+struct Piece {
+    points: [[f64; 2]; 2],
+
+    /// The index of the segment that the line approximates
+    segment: usize,
+}
+
+/// Determine whether two lines touch or cross each other
+fn touch([a, b]: [[f64; 2]; 2], [c, d]: [[f64; 2]; 2]) -> bool {
+    let c_side = side(c, [a, b]);
+    let d_side = side(d, [a, b]);
+    let a_side = side(a, [c, d]);
+    let b_side = side(b, [c, d]);
+
+    if c_side == 0. && d_side == 0. {
+        // The lines are collinear. They touch, if they overlap.
+        let t = |p: [f64; 2]| dot(sub(p, a), sub(b, a));
+        let (min, max) = (t(c).min(t(d)), t(c).max(t(d)));
+
+        return max >= 0. && min <= dot(sub(b, a), sub(b, a));
+    }
+
+    c_side != d_side && a_side != b_side
+}
+
+/// Determine whether line `cd` doubles back along `ab`
+///
+/// Expects that `cd` starts where `ab` ends.
+fn overlap([a, b]: [[f64; 2]; 2], [_, d]: [[f64; 2]; 2]) -> bool {
+    side(d, [a, b]) == 0. && dot(sub(d, b), sub(a, b)) > 0.
+}
+
+/// Determine on which side of line `ab` point `p` is
+///
+/// Returns `1` for the left side, `-1` for the right side, and `0`, if the
+/// point is on the line.
+fn side(p: [f64; 2], [a, b]: [[f64; 2]; 2]) -> f64 {
+    let ab = sub(b, a);
+    let ap = sub(p, a);
+    let cross = ab[0] * ap[1] - ab[1] * ap[0];
+
+    if cross == 0. {
+        0.
+    } else {
+        cross.signum()
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let [x, y] = sub(a, b);
+    x.hypot(y)
+}
+
+fn sub(a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn dot(a: [f64; 2], b: [f64; 2]) -> f64 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Sketch, SketchCycle, SketchSegment};
+
+    use super::SketchError;
+
+    #[test]
+    fn valid_sketch() {
+        let circle = Sketch::from_segments(vec![SketchSegment::arc(
+            [1., 0.],
+            [0., 0.],
+            true,
+        )]);
+        assert_eq!(circle.validate(), Ok(()));
+
+        let sketch = square([0., 0.], 4.).with_hole(hole([1., 1.], 2.));
+        assert_eq!(sketch.validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_arc() {
+        // The end of the arc is further away from its center than its start.
+        let sketch = Sketch::from_segments(vec![
+            SketchSegment::arc([0., 0.], [1., 0.], true),
+            SketchSegment::line([3., 0.]),
+            SketchSegment::line([3., 3.]),
+        ]);
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::InvalidArc { cycle: 0, point: 0 }),
+        );
+
+        // The radius is too small to connect the ends of the arc.
+        let sketch = Sketch::from_segments(vec![
+            SketchSegment::line([0., 0.]),
+            SketchSegment::arc_with_radius([4., 0.], 1., true),
+            SketchSegment::line([2., 3.]),
+        ]);
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::InvalidArc { cycle: 0, point: 1 }),
+        );
+    }
+
+    #[test]
+    fn zero_length_edge() {
+        let sketch =
+            Sketch::from_points(vec![[0., 0.], [1., 0.], [1., 0.], [0., 1.]]);
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::ZeroLengthEdge { cycle: 0, point: 1 }),
+        );
+    }
+
+    #[test]
+    fn duplicate_point() {
+        let sketch = Sketch::from_points(vec![
+            [0., 0.],
+            [2., 0.],
+            [1., 1.],
+            [0., 2.],
+            [1., 1.],
+        ]);
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::DuplicatePoint {
+                cycle: 0,
+                points: [2, 4],
+            }),
+        );
+    }
+
+    #[test]
+    fn degenerate_cycle() {
+        let sketch = Sketch::from_points(vec![[0., 0.], [1., 0.], [2., 0.]]);
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::DegenerateCycle { cycle: 0 }),
+        );
+    }
+
+    #[test]
+    fn self_intersection() {
+        // A bow tie, whose diagonals cross in the middle
+        let sketch =
+            Sketch::from_points(vec![[0., 0.], [2., 2.], [2., 0.], [0., 2.]]);
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::SelfIntersection {
+                cycle: 0,
+                points: [0, 2],
+            }),
+        );
+    }
+
+    #[test]
+    fn cycle_intersection() {
+        // The top side of the hole crosses the right side of the outer
+        // boundary.
+        let sketch = square([0., 0.], 4.).with_hole(hole([3., 1.], 2.));
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::CycleIntersection {
+                cycles: [0, 1],
+                points: [1, 0],
+            }),
+        );
+    }
+
+    #[test]
+    fn hole_outside_boundary() {
+        let sketch = square([0., 0.], 4.)
+            .with_hole(hole([1., 1.], 2.))
+            .with_hole(hole([10., 10.], 2.));
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::HoleOutsideBoundary { cycle: 2 }),
+        );
+    }
+
+    #[test]
+    fn nested_holes() {
+        let sketch = square([0., 0.], 10.)
+            .with_hole(hole([1., 1.], 8.))
+            .with_hole(hole([3., 3.], 2.));
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::NestedHoles { cycles: [1, 2] }),
+        );
+    }
+
+    #[test]
+    fn wrong_winding() {
+        // Holes need to wind clockwise, as the outer boundary winds
+        // counter-clockwise.
+        let sketch = square([0., 0.], 4.)
+            .with_hole(SketchCycle::from_points(square_points([1., 1.], 2.)));
+
+        assert_eq!(
+            sketch.validate(),
+            Err(SketchError::WrongWinding { cycle: 1 }),
+        );
+    }
+
+    fn square(min: [f64; 2], size: f64) -> Sketch {
+        Sketch::from_points(square_points(min, size))
+    }
+
+    fn hole(min: [f64; 2], size: f64) -> SketchCycle {
+        let mut points = square_points(min, size);
+        points.reverse();
+
+        SketchCycle::from_points(points)
+    }
+
+    /// The corners of a square, counter-clockwise
+    fn square_points([x, y]: [f64; 2], size: f64) -> Vec<[f64; 2]> {
+        vec![[x, y], [x + size, y], [x + size, y + size], [x, y + size]]
+    }
+}
@@ -26,10 +26,7 @@ pub extern "C" fn model(args: &HashMap<String, String>) -> fj::Shape {
         b: inner_edge.into(),
     };
 
-    let spacer = fj::Sweep {
-        shape: footprint.into(),
-        length: height,
-    };
+    let spacer = fj::Sweep::from_length(footprint, height);
 
     spacer.into()
 }
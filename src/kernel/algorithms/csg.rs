@@ -0,0 +1,1276 @@
+//! Boolean operations on 3-dimensional faces
+//!
+//! The faces of both operands are approximated with triangles, which are then
+//! combined using binary space partitioning (BSP) trees, an approach that has
+//! been popularized by csg.js: https://github.com/evanw/csg.js
+//!
+//! Each operand is converted into a BSP tree, and the trees are then used to
+//! clip away the parts of each operand that are not part of the result. Faces
+//! are split along the planes of the other operand's faces in the process,
+//! which means the result is trimmed along the intersection curves of the two
+//! operands.
+//!
+//! Faces that come out of this process whole are kept as they are. This means
+//! that exact geometry, like planes and cylinders, is preserved, as long as it
+//! isn't affected by the operation. The pieces of all other faces are welded
+//! into a triangle mesh that connects to the kept faces without any gaps.
+//!
+//! # Limitations
+//!
+//! Faces that are cut by the operation lose their original surface, as they
+//! are represented as triangles.
+//!
+//! Both operands must be closed, and their faces must be oriented such that
+//! their normals point outwards. If they aren't, the results are undefined.
+
+use std::{collections::HashMap, mem};
+
+use parry3d_f64::shape::Triangle;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::topology::faces::{Face, Faces},
+    math::{Point, Vector},
+};
+
+/// The distance below which points are considered to be on a plane
+///
+/// Also the distance below which vertices are considered to be identical, when
+/// the result of an operation is welded together.
+const EPSILON: f64 = 1e-7;
+
+/// Compute the difference of two closed sets of faces
+///
+/// Returns all of `a`, except the volume that is also covered by `b`.
+pub fn difference(
+    a: Faces,
+    b: Faces,
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    boolean(a, b, Operation::Difference, tolerance, debug_info)
+}
+
+/// Compute the intersection of two closed sets of faces
+///
+/// Returns faces that only cover the volume that is covered by both `a` and
+/// `b`.
+pub fn intersection(
+    a: Faces,
+    b: Faces,
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    boolean(a, b, Operation::Intersection, tolerance, debug_info)
+}
+
+/// Compute the union of two closed sets of faces
+///
+/// Returns faces that cover the volume of both `a` and `b`. Faces that end up
+/// inside of the other operand are removed.
+pub fn union(
+    a: Faces,
+    b: Faces,
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    boolean(a, b, Operation::Union, tolerance, debug_info)
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Difference,
+    Intersection,
+    Union,
+}
+
+impl Operation {
+    /// Combine the polygons of both operands
+    ///
+    /// Returns the polygons that make up the result.
+    fn apply(self, a: Vec<Polygon>, b: Vec<Polygon>) -> Vec<Polygon> {
+        let mut a = Node::new(a);
+        let mut b = Node::new(b);
+
+        match self {
+            Self::Difference => {
+                a.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+                a.invert();
+            }
+            Self::Intersection => {
+                a.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                a.build(b.all_polygons());
+                a.invert();
+            }
+            Self::Union => {
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+            }
+        }
+
+        a.all_polygons()
+    }
+
+    /// Indicates whether the faces of `b` are reversed in the result
+    fn reverses_b(self) -> bool {
+        matches!(self, Self::Difference)
+    }
+}
+
+fn boolean(
+    a: Faces,
+    b: Faces,
+    operation: Operation,
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    // The faces of `a` come first, followed by those of `b`. The polygons that
+    // are created from them refer to them by their index.
+    let num_faces_a = a.0.len();
+    let faces: Vec<_> = a.0.into_iter().chain(b.0).collect();
+
+    let triangles: Vec<_> = faces
+        .iter()
+        .map(|face| {
+            let mut triangles = Vec::new();
+            face.triangles(tolerance, &mut triangles, debug_info);
+            triangles
+        })
+        .collect();
+
+    let polygons = |faces: std::ops::Range<usize>| -> Vec<_> {
+        faces
+            .flat_map(|i| {
+                triangles[i].iter().enumerate().filter_map(
+                    move |(j, &Triangle { a, b, c })| {
+                        Polygon::new(vec![a, b, c], i, j)
+                    },
+                )
+            })
+            .collect()
+    };
+    let a = polygons(0..num_faces_a);
+    let b = polygons(num_faces_a..faces.len());
+
+    let mut pieces: Vec<_> = triangles
+        .iter()
+        .map(|triangles| vec![Vec::new(); triangles.len()])
+        .collect();
+    for polygon in operation.apply(a, b) {
+        pieces[polygon.face][polygon.triangle].push(polygon);
+    }
+
+    // Faces that come out of the operation whole haven't been cut by it, and
+    // can be kept as they are.
+    let mut kept: Vec<_> = triangles
+        .iter()
+        .zip(&pieces)
+        .map(|(triangles, pieces)| {
+            let original: f64 = triangles.iter().map(Triangle::area).sum();
+            let remaining: f64 =
+                pieces.iter().flatten().map(Polygon::area).sum();
+
+            original > 0. && (original - remaining).abs() <= original * 1e-9
+        })
+        .collect();
+
+    // The pieces of each triangle are merged into the region they cover. The
+    // splits that didn't end up cutting the triangle leave no trace in that
+    // region, which keeps them from affecting any neighboring faces.
+    let mut vertices = Vertices::default();
+    for triangle in triangles.iter().flatten() {
+        for &vertex in triangle.vertices() {
+            vertices.snap(vertex);
+        }
+    }
+    let regions: Vec<Vec<_>> = pieces
+        .iter()
+        .map(|pieces| {
+            pieces
+                .iter()
+                .filter_map(|pieces| Region::new(pieces, &mut vertices))
+                .collect()
+        })
+        .collect();
+
+    // The regions of the other faces need to connect to the boundaries of the
+    // kept faces. Where they have vertices on such a boundary, that the kept
+    // face doesn't have itself, the kept face can't be connected to them, and
+    // needs to be replaced by its regions too.
+    loop {
+        let vertices = PointIndex::new(
+            regions
+                .iter()
+                .zip(&kept)
+                .filter(|(_, kept)| !**kept)
+                .flat_map(|(regions, _)| regions)
+                .flat_map(|region| region.cycles.iter().flatten().copied())
+                .collect(),
+        );
+
+        let mut changed = false;
+        for (triangles, kept) in triangles.iter().zip(&mut kept) {
+            if !*kept {
+                continue;
+            }
+
+            let connected =
+                boundary(triangles.iter().flat_map(edges).collect())
+                    .iter()
+                    .all(|&segment| vertices.on_segment(segment).is_empty());
+            if !connected {
+                *kept = false;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut anchors = Vec::new();
+    let mut mesh = Vec::new();
+    for ((triangles, regions), kept) in
+        triangles.iter().zip(&regions).zip(&kept)
+    {
+        if *kept {
+            anchors.extend(
+                triangles
+                    .iter()
+                    .flat_map(|triangle| triangle.vertices().iter().copied()),
+            );
+        } else {
+            for region in regions {
+                region.triangles(&mut mesh);
+            }
+        }
+    }
+    let mesh = weld(mesh, &anchors);
+
+    let mut result: Vec<_> = faces
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, _)| kept[i])
+        .map(|(i, face)| {
+            if i >= num_faces_a && operation.reverses_b() {
+                face.reverse()
+            } else {
+                face
+            }
+        })
+        .collect();
+    if !mesh.is_empty() {
+        result.push(Face::Triangles(mesh));
+    }
+
+    Faces(result)
+}
+
+/// Find the segments on the boundary of a set of polygons
+///
+/// Expects the directed edges of the polygons, and returns those that aren't
+/// matched by an edge in the opposite direction.
+fn boundary(edges: Vec<[Point<3>; 2]>) -> Vec<[Point<3>; 2]> {
+    let key = |point: Point<3>| point.coords.map(f64::to_bits);
+
+    let mut count = HashMap::new();
+    for &[p, q] in &edges {
+        *count.entry([key(p), key(q)]).or_insert(0) += 1;
+        *count.entry([key(q), key(p)]).or_insert(0) -= 1;
+    }
+
+    edges
+        .into_iter()
+        .filter(|&[p, q]| {
+            // Can't panic, as all edges have been counted above.
+            let count = count.get_mut(&[key(p), key(q)]).unwrap();
+            if *count > 0 {
+                *count -= 1;
+                true
+            } else {
+                false
+            }
+        })
+        .collect()
+}
+
+/// The directed edges of a triangle
+fn edges(triangle: &Triangle) -> [[Point<3>; 2]; 3] {
+    let [a, b, c] = *triangle.vertices();
+    [[a, b], [b, c], [c, a]]
+}
+
+/// The region of a triangle that is left after an operation
+///
+/// The pieces that a triangle has been split into by the BSP tree are merged
+/// into a set of cycles, which are then triangulated again. Vertices on
+/// straight parts of those cycles are dropped, as they only result from splits
+/// that didn't actually cut the triangle.
+struct Region {
+    /// The normal of the triangle, pointing out of the solid
+    normal: Vector<3>,
+
+    /// The cycles that bound the region
+    ///
+    /// Outer cycles are counter-clockwise, when looking against the normal,
+    /// inner ones clockwise.
+    cycles: Vec<Vec<Point<3>>>,
+}
+
+impl Region {
+    /// Merge the pieces of a triangle into a region
+    ///
+    /// All vertices of the pieces are snapped to `vertices`, which makes sure
+    /// that the edges the pieces share are identical. Returns `None`, if there
+    /// is nothing left of the triangle.
+    fn new(pieces: &[Polygon], vertices: &mut Vertices) -> Option<Self> {
+        let normal = pieces.first()?.plane.normal;
+
+        let pieces: Vec<Vec<_>> = pieces
+            .iter()
+            .map(|piece| {
+                piece.vertices.iter().map(|&v| vertices.snap(v)).collect()
+            })
+            .collect();
+
+        // Pieces can have vertices on the edges of their neighbors, where one
+        // of them has been split further than the other. Those edges need to
+        // be split too, or they wouldn't cancel each other out.
+        let points =
+            PointIndex::new(pieces.iter().flatten().copied().collect());
+        let mut edges = Vec::new();
+        for piece in &pieces {
+            for (i, &a) in piece.iter().enumerate() {
+                let b = piece[(i + 1) % piece.len()];
+                if a == b {
+                    continue;
+                }
+
+                let mut points = points.on_segment([a, b]);
+                points.insert(0, a);
+                points.push(b);
+
+                edges.extend(points.windows(2).map(|w| [w[0], w[1]]));
+            }
+        }
+
+        let key = |point: Point<3>| point.coords.map(f64::to_bits);
+
+        let mut next = HashMap::new();
+        for [a, b] in boundary(edges) {
+            next.entry(key(a)).or_insert_with(Vec::new).push(b);
+        }
+
+        let mut cycles = Vec::new();
+        while let Some(start) = next.values_mut().find_map(Vec::pop) {
+            let mut cycle = vec![start];
+            loop {
+                let current = cycle[cycle.len() - 1];
+                match next.get_mut(&key(current)).and_then(Vec::pop) {
+                    Some(point) if key(point) != key(start) => {
+                        cycle.push(point);
+                    }
+                    _ => break,
+                }
+            }
+
+            simplify(&mut cycle);
+            if cycle.len() >= 3 {
+                cycles.push(cycle);
+            }
+        }
+
+        if cycles.is_empty() {
+            return None;
+        }
+
+        Some(Self { normal, cycles })
+    }
+
+    /// Triangulate the region
+    fn triangles(&self, out: &mut Vec<Triangle>) {
+        use spade::{ConstrainedDelaunayTriangulation, Point2, Triangulation};
+
+        // A coordinate system in the plane of the region, such that its
+        // counter-clockwise direction matches the orientation of the region.
+        let origin = self.cycles[0][0];
+        let u = self.normal.cross(&least_aligned_axis(&self.normal));
+        let u = u.normalize();
+        let v = self.normal.cross(&u);
+        let project = |point: Point<3>| {
+            // The triangulation rejects values that are too close to zero, but
+            // not zero.
+            let flush = |value: f64| {
+                if value.abs() < spade::MIN_ALLOWED_VALUE {
+                    0.
+                } else {
+                    value
+                }
+            };
+
+            let d = point - origin;
+            Point2::new(flush(d.dot(&u)), flush(d.dot(&v)))
+        };
+
+        let mut triangulation =
+            ConstrainedDelaunayTriangulation::<Point2<f64>>::new();
+        let mut points = HashMap::new();
+        let mut cycles = Vec::new();
+
+        for cycle in &self.cycles {
+            let mut handles = Vec::new();
+            for &point in cycle {
+                let handle = triangulation
+                    .insert(project(point))
+                    .expect("Inserted invalid values into triangulation");
+                points.entry(handle).or_insert(point);
+                handles.push(handle);
+            }
+
+            for (i, &a) in handles.iter().enumerate() {
+                let b = handles[(i + 1) % handles.len()];
+                let [p, q] = [a, b].map(|handle| triangulation.vertex(handle));
+
+                // Constraints can't cross each other. The cycles don't cross
+                // either, but snapping might have moved them closer than the
+                // triangulation can tell apart.
+                if a != b
+                    && !triangulation
+                        .intersects_constraint(p.position(), q.position())
+                {
+                    triangulation.add_constraint(a, b);
+                }
+            }
+
+            cycles.push(cycle.iter().map(|&point| project(point)).collect());
+        }
+
+        for face in triangulation.inner_faces() {
+            let [a, b, c] = face.vertices().map(|vertex| vertex.position());
+            let center =
+                Point2::new((a.x + b.x + c.x) / 3., (a.y + b.y + c.y) / 3.);
+            if !contains(&cycles, center) {
+                continue;
+            }
+
+            let [a, b, c] = face.vertices().map(|vertex| points[&vertex.fix()]);
+            out.push(Triangle::new(a, b, c));
+        }
+    }
+}
+
+/// Remove the vertices of a cycle that are on a straight line
+fn simplify(cycle: &mut Vec<Point<3>>) {
+    let mut i = 0;
+    let mut unchanged = 0;
+    while cycle.len() >= 3 && unchanged < cycle.len() {
+        let n = cycle.len();
+        let prev = cycle[(i + n - 1) % n];
+        let next = cycle[(i + 1) % n];
+
+        if distance_to_segment(cycle[i], [prev, next]) <= EPSILON {
+            cycle.remove(i);
+            unchanged = 0;
+        } else {
+            unchanged += 1;
+            i += 1;
+        }
+
+        i %= cycle.len().max(1);
+    }
+}
+
+/// Indicates whether a point is within a set of cycles
+///
+/// Uses the same half-open crossing rule as the ray casting in
+/// [`Face::triangles`].
+fn contains(
+    cycles: &[Vec<spade::Point2<f64>>],
+    point: spade::Point2<f64>,
+) -> bool {
+    let mut inside = false;
+    for cycle in cycles {
+        for (i, a) in cycle.iter().enumerate() {
+            let b = cycle[(i + 1) % cycle.len()];
+            if (a.y > point.y) == (b.y > point.y) {
+                continue;
+            }
+
+            let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if x > point.x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Find the coordinate axis that is least aligned with a vector
+fn least_aligned_axis(vector: &Vector<3>) -> Vector<3> {
+    let abs = vector.abs();
+    if abs.x <= abs.y && abs.x <= abs.z {
+        Vector::x()
+    } else if abs.y <= abs.z {
+        Vector::y()
+    } else {
+        Vector::z()
+    }
+}
+
+/// Connect triangles that have been split independently of each other
+///
+/// Splitting polygons results in vertices that are almost, but not exactly,
+/// identical, and in vertices that lie on the edges of neighboring triangles
+/// (T-junctions). This function merges vertices that are close to each other,
+/// removes triangles that degenerate in the process, and splits edges at the
+/// vertices that lie on them. If the triangles form a closed mesh, each edge of
+/// the result is shared with exactly one other triangle.
+///
+/// `anchors` are vertices of other triangles that the mesh needs to connect
+/// to. They are kept exactly as they are.
+fn weld(triangles: Vec<Triangle>, anchors: &[Point<3>]) -> Vec<Triangle> {
+    let mut vertices = Vertices::default();
+    for &anchor in anchors {
+        vertices.snap(anchor);
+    }
+
+    let triangles: Vec<_> = triangles
+        .into_iter()
+        .map(|Triangle { a, b, c }| [a, b, c].map(|point| vertices.snap(point)))
+        .filter(|triangle| !is_sliver(triangle))
+        .collect();
+
+    let vertices = PointIndex::new(
+        anchors
+            .iter()
+            .copied()
+            .chain(triangles.iter().flatten().copied())
+            .collect(),
+    );
+
+    let mut welded = Vec::new();
+    for triangle in triangles {
+        let [a, b, c] = triangle;
+        let points = [[a, b], [b, c], [c, a]]
+            .map(|segment| vertices.on_segment(segment));
+
+        split(triangle, [&points[0], &points[1], &points[2]], &mut welded);
+    }
+
+    welded
+}
+
+/// Indicates whether a triangle is too thin to be part of a welded mesh
+///
+/// That is the case, if one of its vertices is on the opposite edge. Removing
+/// such a triangle doesn't leave a gap, as the edges of its neighbors are split
+/// at that vertex.
+fn is_sliver(&[a, b, c]: &[Point<3>; 3]) -> bool {
+    [[a, b, c], [b, c, a], [c, a, b]]
+        .into_iter()
+        .any(|[p, q, r]| distance_to_segment(p, [q, r]) <= EPSILON)
+}
+
+/// Split a triangle at points on its edges
+///
+/// `points` contains the points on each of the edges `ab`, `bc`, and `ca`,
+/// sorted from the start of the edge to its end.
+fn split(
+    [a, b, c]: [Point<3>; 3],
+    [ab, bc, ca]: [&[Point<3>]; 3],
+    out: &mut Vec<Triangle>,
+) {
+    // Splitting at the point in the middle of an edge, and connecting it to the
+    // opposite vertex, keeps the resulting triangles from getting too thin.
+    if !ab.is_empty() {
+        let (p, [before, after]) = middle(ab);
+        split([a, p, c], [before, &[], ca], out);
+        split([p, b, c], [after, bc, &[]], out);
+    } else if !bc.is_empty() {
+        split([b, c, a], [bc, ca, ab], out);
+    } else if !ca.is_empty() {
+        split([c, a, b], [ca, ab, bc], out);
+    } else {
+        out.push(Triangle::new(a, b, c));
+    }
+}
+
+fn middle(points: &[Point<3>]) -> (Point<3>, [&[Point<3>]; 2]) {
+    let i = points.len() / 2;
+    (points[i], [&points[..i], &points[i + 1..]])
+}
+
+fn distance_to_segment(point: Point<3>, [a, b]: [Point<3>; 2]) -> f64 {
+    let ab = b - a;
+    let length_squared = ab.magnitude_squared();
+    if length_squared == 0. {
+        return (point - a).magnitude();
+    }
+
+    let t = ((point - a).dot(&ab) / length_squared).clamp(0., 1.);
+    (point - (a + ab * t)).magnitude()
+}
+
+/// A set of vertices, none of which are closer to each other than [`EPSILON`]
+#[derive(Default)]
+struct Vertices {
+    points: Vec<Point<3>>,
+
+    /// The indices of the points, sorted into cubic cells of size [`EPSILON`]
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl Vertices {
+    /// Find the vertex that is close to a point, or add the point as one
+    fn snap(&mut self, point: Point<3>) -> Point<3> {
+        let cell = point.coords.map(|value| (value / EPSILON).floor() as i64);
+
+        // Any point that is close enough is in the same, or a neighboring
+        // cell.
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let neighbor = [cell.x + x, cell.y + y, cell.z + z];
+                    for &i in self.cells.get(&neighbor).into_iter().flatten() {
+                        if (self.points[i] - point).magnitude() <= EPSILON {
+                            return self.points[i];
+                        }
+                    }
+                }
+            }
+        }
+
+        self.cells
+            .entry([cell.x, cell.y, cell.z])
+            .or_default()
+            .push(self.points.len());
+        self.points.push(point);
+
+        point
+    }
+}
+
+/// Points, sorted for finding the ones that are on a segment
+struct PointIndex {
+    /// The points, sorted by their x coordinate
+    points: Vec<Point<3>>,
+}
+
+impl PointIndex {
+    fn new(mut points: Vec<Point<3>>) -> Self {
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self { points }
+    }
+
+    /// Find the points that are on a segment, but not at its ends
+    ///
+    /// Returns the points sorted from the start of the segment to its end.
+    fn on_segment(&self, [a, b]: [Point<3>; 2]) -> Vec<Point<3>> {
+        let ab = b - a;
+        let length_squared = ab.magnitude_squared();
+
+        let min = a.x.min(b.x) - EPSILON;
+        let max = a.x.max(b.x) + EPSILON;
+        let start = self.points.partition_point(|point| point.x < min);
+
+        let mut points: Vec<_> = self.points[start..]
+            .iter()
+            .take_while(|point| point.x <= max)
+            .filter_map(|&point| {
+                if (point - a).magnitude() <= EPSILON
+                    || (point - b).magnitude() <= EPSILON
+                {
+                    return None;
+                }
+
+                let t = (point - a).dot(&ab) / length_squared;
+                let distance = (point - (a + ab * t)).magnitude();
+                if t <= 0. || t >= 1. || distance > EPSILON {
+                    return None;
+                }
+
+                Some((t, point))
+            })
+            .collect();
+
+        points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        points.into_iter().map(|(_, point)| point).collect()
+    }
+}
+
+/// A convex polygon, as used by the BSP tree
+#[derive(Clone)]
+struct Polygon {
+    vertices: Vec<Point<3>>,
+
+    /// The plane that the polygon is in
+    ///
+    /// Polygons that result from splitting another polygon keep the plane of
+    /// the original one, instead of computing it from their (possibly
+    /// imprecise) vertices.
+    plane: Plane,
+
+    /// The index of the face that the polygon is a part of
+    face: usize,
+
+    /// The index of the triangle of the face that the polygon is a part of
+    triangle: usize,
+}
+
+impl Polygon {
+    /// Create a polygon
+    ///
+    /// Returns `None`, if the vertices don't define a plane, for example
+    /// because they are collinear.
+    fn new(
+        vertices: Vec<Point<3>>,
+        face: usize,
+        triangle: usize,
+    ) -> Option<Self> {
+        let plane = Plane::from_points(vertices[0], vertices[1], vertices[2])?;
+        Some(Self {
+            vertices,
+            plane,
+            face,
+            triangle,
+        })
+    }
+
+    fn area(&self) -> f64 {
+        self.triangles().map(|triangle| triangle.area()).sum()
+    }
+
+    /// Triangulate the polygon
+    fn triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
+        // All polygons are convex, so a triangle fan is a valid triangulation.
+        let v0 = self.vertices[0];
+        self.vertices[1..]
+            .windows(2)
+            .map(move |window| Triangle::new(v0, window[0], window[1]))
+    }
+
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        self.plane.flip();
+    }
+}
+
+/// A plane, as used by the BSP tree
+///
+/// The plane consists of all points `p` for which `normal.dot(p) == w`.
+#[derive(Clone, Copy)]
+struct Plane {
+    normal: Vector<3>,
+    w: f64,
+}
+
+impl Plane {
+    fn from_points(a: Point<3>, b: Point<3>, c: Point<3>) -> Option<Self> {
+        let normal = (b - a).cross(&(c - a));
+        if normal.magnitude() <= EPSILON * EPSILON {
+            return None;
+        }
+
+        let normal = normal.normalize();
+        let w = normal.dot(&a.coords);
+
+        Some(Self { normal, w })
+    }
+
+    fn flip(&mut self) {
+        self.normal = -self.normal;
+        self.w = -self.w;
+    }
+
+    fn distance(&self, point: &Point<3>) -> f64 {
+        self.normal.dot(&point.coords) - self.w
+    }
+
+    /// Split a polygon along this plane
+    ///
+    /// Puts the polygon, or the pieces that result from splitting it, into the
+    /// appropriate output `Vec`.
+    fn split(&self, polygon: Polygon, out: &mut Split) {
+        let sides: Vec<_> = polygon
+            .vertices
+            .iter()
+            .map(|vertex| Side::of(self.distance(vertex)))
+            .collect();
+
+        let has_front = sides.contains(&Side::Front);
+        let has_back = sides.contains(&Side::Back);
+
+        match (has_front, has_back) {
+            (false, false) => {
+                if self.normal.dot(&polygon.plane.normal) > 0. {
+                    out.coplanar_front.push(polygon);
+                } else {
+                    out.coplanar_back.push(polygon);
+                }
+            }
+            (true, false) => out.front.push(polygon),
+            (false, true) => out.back.push(polygon),
+            (true, true) => {
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+
+                let n = polygon.vertices.len();
+                for i in 0..n {
+                    let j = (i + 1) % n;
+
+                    let (vi, vj) = (polygon.vertices[i], polygon.vertices[j]);
+                    let (si, sj) = (sides[i], sides[j]);
+
+                    if si != Side::Back {
+                        front.push(vi);
+                    }
+                    if si != Side::Front {
+                        back.push(vi);
+                    }
+
+                    let spanning = matches!(
+                        (si, sj),
+                        (Side::Front, Side::Back) | (Side::Back, Side::Front)
+                    );
+                    if spanning {
+                        let t = (self.w - self.normal.dot(&vi.coords))
+                            / self.normal.dot(&(vj - vi));
+                        let v = vi + (vj - vi) * t;
+
+                        front.push(v);
+                        back.push(v);
+                    }
+                }
+
+                if front.len() >= 3 {
+                    out.front.push(Polygon {
+                        vertices: front,
+                        ..polygon
+                    });
+                }
+                if back.len() >= 3 {
+                    out.back.push(Polygon {
+                        vertices: back,
+                        ..polygon
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The side of a plane that a point is on
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    Front,
+    Back,
+    On,
+}
+
+impl Side {
+    fn of(distance: f64) -> Self {
+        if distance > EPSILON {
+            Self::Front
+        } else if distance < -EPSILON {
+            Self::Back
+        } else {
+            Self::On
+        }
+    }
+}
+
+/// The result of splitting polygons along a plane
+#[derive(Default)]
+struct Split {
+    coplanar_front: Vec<Polygon>,
+    coplanar_back: Vec<Polygon>,
+    front: Vec<Polygon>,
+    back: Vec<Polygon>,
+}
+
+/// A node in a BSP tree
+///
+/// The front of a node's plane is considered to be outside of the solid, the
+/// back is considered to be inside.
+#[derive(Default)]
+struct Node {
+    plane: Option<Plane>,
+    front: Option<Box<Node>>,
+    back: Option<Box<Node>>,
+
+    /// The polygons that are coplanar with this node's plane
+    polygons: Vec<Polygon>,
+}
+
+impl Node {
+    fn new(polygons: Vec<Polygon>) -> Self {
+        let mut node = Self::default();
+        node.build(polygons);
+        node
+    }
+
+    /// Convert solid space to empty space and vice versa
+    fn invert(&mut self) {
+        for polygon in &mut self.polygons {
+            polygon.flip();
+        }
+        if let Some(plane) = &mut self.plane {
+            plane.flip();
+        }
+        if let Some(front) = &mut self.front {
+            front.invert();
+        }
+        if let Some(back) = &mut self.back {
+            back.invert();
+        }
+
+        mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Remove all parts of `polygons` that are inside of this BSP tree
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        let plane = match self.plane {
+            Some(plane) => plane,
+            None => return polygons,
+        };
+
+        let mut split = Split::default();
+        for polygon in polygons {
+            plane.split(polygon, &mut split);
+        }
+
+        let mut front = split.front;
+        front.extend(split.coplanar_front);
+        let mut back = split.back;
+        back.extend(split.coplanar_back);
+
+        let mut polygons = match &self.front {
+            Some(node) => node.clip_polygons(front),
+            None => front,
+        };
+        if let Some(node) = &self.back {
+            polygons.extend(node.clip_polygons(back));
+        }
+
+        polygons
+    }
+
+    /// Remove all parts of the polygons in this tree that are inside of `bsp`
+    fn clip_to(&mut self, bsp: &Node) {
+        self.polygons = bsp.clip_polygons(mem::take(&mut self.polygons));
+
+        if let Some(front) = &mut self.front {
+            front.clip_to(bsp);
+        }
+        if let Some(back) = &mut self.back {
+            back.clip_to(bsp);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon> {
+        let mut polygons = self.polygons.clone();
+
+        if let Some(front) = &self.front {
+            polygons.extend(front.all_polygons());
+        }
+        if let Some(back) = &self.back {
+            polygons.extend(back.all_polygons());
+        }
+
+        polygons
+    }
+
+    /// Add polygons to the tree, creating new nodes as required
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        let plane = match (self.plane, polygons.first()) {
+            (Some(plane), _) => plane,
+            (None, Some(polygon)) => polygon.plane,
+            (None, None) => return,
+        };
+        self.plane = Some(plane);
+
+        let mut split = Split::default();
+        for polygon in polygons {
+            plane.split(polygon, &mut split);
+        }
+
+        self.polygons.extend(split.coplanar_front);
+        self.polygons.extend(split.coplanar_back);
+
+        if !split.front.is_empty() {
+            self.front
+                .get_or_insert_with(Default::default)
+                .build(split.front);
+        }
+        if !split.back.is_empty() {
+            self.back
+                .get_or_insert_with(Default::default)
+                .build(split.back);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+    use nalgebra::point;
+    use parry3d_f64::{math::Isometry, shape::Triangle};
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{self, open_edges, TOLERANCE},
+            topology::faces::{Face, Faces},
+            Shape as _,
+        },
+        math::Point,
+    };
+
+    use super::{difference, intersection, union};
+
+    #[test]
+    fn difference_of_disjoint_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![2., 0., 0.], 1.);
+
+        let result = difference(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        assert_approx(area(&result), 6.);
+    }
+
+    #[test]
+    fn difference_of_overlapping_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![0.5, 0., 0.], 1.);
+
+        let result = difference(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        // The result is a cuboid with dimensions 0.5 x 1 x 1.
+        assert_approx(area(&result), 4.);
+        assert_approx(volume(&result), 0.5);
+    }
+
+    #[test]
+    fn difference_of_hole() {
+        let a = cuboid(point![0., 0., 0.], [2., 2., 2.]);
+        let b = cuboid(point![0.5, 0.5, -1.], [1., 1., 4.]);
+
+        let result = difference(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        assert_approx(volume(&result), 8. - 2.);
+    }
+
+    #[test]
+    fn difference_with_curved_operand() {
+        let (cube, cylinder) = cube_and_cylinder();
+
+        let result =
+            difference(cube, cylinder, TOLERANCE, &mut DebugInfo::new());
+
+        // Half of the cylinder is within the cube.
+        test_util::assert_approx(volume(&result), 8. - PI / 4., 0.01);
+        assert_eq!(open_edges(&result), 0);
+
+        // The faces of the cube that the cylinder doesn't touch are kept.
+        assert_eq!(faces(&result), 3);
+    }
+
+    #[test]
+    fn intersection_of_disjoint_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![2., 0., 0.], 1.);
+
+        let result = intersection(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        assert!(triangles(&result).is_empty());
+    }
+
+    #[test]
+    fn intersection_of_overlapping_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![0.5, 0.5, 0.5], 1.);
+
+        let result = intersection(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        // The result is the cube of 0.5 x 0.5 x 0.5 where both overlap.
+        assert_approx(area(&result), 6. * 0.25);
+        assert_approx(volume(&result), 0.125);
+    }
+
+    #[test]
+    fn intersection_with_curved_operand() {
+        let (cube, cylinder) = cube_and_cylinder();
+
+        let result =
+            intersection(cube, cylinder, TOLERANCE, &mut DebugInfo::new());
+
+        // Half of the cylinder is within the cube.
+        test_util::assert_approx(volume(&result), PI / 4., 0.01);
+        assert_eq!(open_edges(&result), 0);
+    }
+
+    #[test]
+    fn union_of_disjoint_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![2., 0., 0.], 1.);
+
+        let result = union(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        assert_approx(area(&result), 12.);
+        assert_approx(volume(&result), 2.);
+    }
+
+    #[test]
+    fn union_of_overlapping_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![0.5, 0.5, 0.5], 1.);
+
+        let result = union(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        // Each cube loses a corner of 0.5 x 0.5 x 0.5 to the other. The
+        // internal faces of that overlap must not be part of the result.
+        assert_approx(area(&result), 12. - 6. * 0.25);
+        assert_approx(volume(&result), 2. - 0.125);
+    }
+
+    #[test]
+    fn union_with_curved_operand() {
+        let (cube, cylinder) = cube_and_cylinder();
+
+        let result = union(cube, cylinder, TOLERANCE, &mut DebugInfo::new());
+
+        // Half of the cylinder is within the cube.
+        test_util::assert_approx(
+            volume(&result),
+            8. + PI * 3. / 4. - PI / 4.,
+            0.01,
+        );
+        assert_eq!(open_edges(&result), 0);
+
+        // The sides of the cube that the cylinder doesn't touch are kept, as
+        // are both ends of the cylinder.
+        assert_eq!(faces(&result), 3 + 2);
+    }
+
+    #[test]
+    fn union_of_touching_cubes() {
+        let a = cube(point![0., 0., 0.], 1.);
+        let b = cube(point![1., 0., 0.], 1.);
+
+        let result = union(a, b, TOLERANCE, &mut DebugInfo::new());
+
+        // The faces where the cubes touch are internal and must be removed.
+        assert_approx(area(&result), 10.);
+        assert_approx(volume(&result), 2.);
+    }
+
+    /// A cube, and a cylinder that sticks out of one of its sides
+    fn cube_and_cylinder() -> (Faces, Faces) {
+        let cube = fj::Sketch::from_points(vec![
+            [-1., -1.],
+            [1., -1.],
+            [1., 1.],
+            [-1., 1.],
+        ])
+        .sweep(2.)
+        .faces(TOLERANCE, &mut DebugInfo::new());
+        let cylinder = fj::Cylinder {
+            radius: 0.5,
+            height: 3.,
+        }
+        .faces(TOLERANCE, &mut DebugInfo::new())
+        .transform(&Isometry::translation(1., 0., -0.5));
+
+        (cube, cylinder)
+    }
+
+    fn cube(min: Point<3>, size: f64) -> Faces {
+        cuboid(min, [size; 3])
+    }
+
+    fn cuboid(min: Point<3>, [sx, sy, sz]: [f64; 3]) -> Faces {
+        let p = |x: f64, y: f64, z: f64| {
+            point![min.x + x * sx, min.y + y * sy, min.z + z * sz]
+        };
+
+        let quads = [
+            // bottom
+            [p(0., 0., 0.), p(0., 1., 0.), p(1., 1., 0.), p(1., 0., 0.)],
+            // top
+            [p(0., 0., 1.), p(1., 0., 1.), p(1., 1., 1.), p(0., 1., 1.)],
+            // front
+            [p(0., 0., 0.), p(1., 0., 0.), p(1., 0., 1.), p(0., 0., 1.)],
+            // back
+            [p(0., 1., 0.), p(0., 1., 1.), p(1., 1., 1.), p(1., 1., 0.)],
+            // left
+            [p(0., 0., 0.), p(0., 0., 1.), p(0., 1., 1.), p(0., 1., 0.)],
+            // right
+            [p(1., 0., 0.), p(1., 1., 0.), p(1., 1., 1.), p(1., 0., 1.)],
+        ];
+
+        let faces = quads
+            .into_iter()
+            .map(|[a, b, c, d]| {
+                Face::Triangles(vec![
+                    Triangle::new(a, b, c),
+                    Triangle::new(a, c, d),
+                ])
+            })
+            .collect();
+
+        Faces(faces)
+    }
+
+    /// Count the faces that haven't been converted to triangles
+    fn faces(faces: &Faces) -> usize {
+        faces
+            .0
+            .iter()
+            .filter(|face| matches!(face, Face::Face { .. }))
+            .count()
+    }
+
+    fn triangles(faces: &Faces) -> Vec<Triangle> {
+        let mut triangles = Vec::new();
+        faces.triangles(TOLERANCE, &mut triangles, &mut DebugInfo::new());
+        triangles
+    }
+
+    fn area(faces: &Faces) -> f64 {
+        triangles(faces)
+            .iter()
+            .map(|triangle| triangle.area())
+            .sum()
+    }
+
+    fn volume(faces: &Faces) -> f64 {
+        triangles(faces)
+            .iter()
+            .map(|Triangle { a, b, c }| {
+                a.coords.dot(&b.coords.cross(&c.coords)) / 6.
+            })
+            .sum()
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+}
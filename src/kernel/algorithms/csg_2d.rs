@@ -0,0 +1,870 @@
+//! Boolean operations on 2-dimensional faces
+//!
+//! The operations in this module work on faces that lie in the same surface.
+//! The edges of both operands are approximated, and the approximations are
+//! split wherever they intersect the boundary of the other operand. Each of the
+//! resulting pieces is then classified as being inside of, outside of, or on
+//! the boundary of the other operand, and is either kept or discarded, based on
+//! that classification. The pieces that are kept are connected into cycles,
+//! which are finally sorted into faces.
+//!
+//! Edges that don't intersect the other operand are not split, and are kept as
+//! they are. This means that exact geometry, like circles, is preserved, as
+//! long as it isn't affected by the operation.
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{points::SurfacePoint, Curve, Line, Surface},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        util::default_tolerance,
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-9;
+
+/// Compute the difference of two sets of faces
+///
+/// Returns the area of `a`, minus all of the area that is also covered by `b`.
+pub fn difference(a: Faces, b: Faces, tolerance: f64) -> Faces {
+    boolean(a, b, Operation::Difference, tolerance)
+}
+
+/// Compute the intersection of two sets of faces
+///
+/// Returns the area that is covered by both `a` and `b`.
+pub fn intersection(a: Faces, b: Faces, tolerance: f64) -> Faces {
+    boolean(a, b, Operation::Intersection, tolerance)
+}
+
+/// Compute the union of two sets of faces
+///
+/// Returns the area that is covered by `a`, `b`, or both.
+pub fn union(a: Faces, b: Faces, tolerance: f64) -> Faces {
+    boolean(a, b, Operation::Union, tolerance)
+}
+
+/// Normalize the orientation of a set of faces
+///
+/// Returns faces that cover the same area, whose outer boundaries are oriented
+/// counter-clockwise, and whose holes are oriented clockwise, in surface
+/// coordinates. Edges are kept as they are, except for their direction.
+pub fn orient(faces: Faces, tolerance: f64) -> Faces {
+    // The union with nothing doesn't split any edges, but orients all cycles
+    // and sorts them into faces, which is exactly what we need.
+    boolean(faces, Faces(Vec::new()), Operation::Union, tolerance)
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Difference,
+    Intersection,
+    Union,
+}
+
+impl Operation {
+    /// Decide whether to keep a piece of operand `a`
+    fn keep_a(&self, class: Class) -> bool {
+        match self {
+            Self::Difference => {
+                matches!(class, Class::Outside | Class::OppositeBoundary)
+            }
+            Self::Intersection => {
+                matches!(class, Class::Inside | Class::SameBoundary)
+            }
+            Self::Union => {
+                matches!(class, Class::Outside | Class::SameBoundary)
+            }
+        }
+    }
+
+    /// Decide whether to keep a piece of operand `b`
+    ///
+    /// Pieces of the boundary that are shared by both operands are only kept
+    /// once, from operand `a`.
+    fn keep_b(&self, class: Class) -> bool {
+        match self {
+            Self::Difference => class == Class::Inside,
+            Self::Intersection => class == Class::Inside,
+            Self::Union => class == Class::Outside,
+        }
+    }
+
+    /// Indicates whether the pieces of operand `b` need to be reversed
+    fn reverse_b(&self) -> bool {
+        matches!(self, Self::Difference)
+    }
+}
+
+/// The position of a piece of one operand, relative to the other operand
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Class {
+    Inside,
+    Outside,
+
+    /// On the boundary of the other operand, in the same direction
+    SameBoundary,
+
+    /// On the boundary of the other operand, in the opposite direction
+    OppositeBoundary,
+}
+
+fn boolean(a: Faces, b: Faces, operation: Operation, tolerance: f64) -> Faces {
+    let (cycles_a, surface_a) = cycles(a);
+    let (cycles_b, surface_b) = cycles(b);
+
+    let surface = match (surface_a, surface_b) {
+        (Some(a), Some(b)) => {
+            if a != b {
+                // Panicking is not great, but as long as we don't have a real
+                // error handling mechanism, it will do.
+                panic!("Trying to combine faces with different surfaces.")
+            }
+            a
+        }
+        (Some(surface), None) | (None, Some(surface)) => surface,
+        (None, None) => return Faces(Vec::new()),
+    };
+
+    let a = Operand::new(cycles_a, &surface, tolerance);
+    let b = Operand::new(cycles_b, &surface, tolerance);
+
+    let mut pieces = Vec::new();
+    a.pieces(&b, |class| operation.keep_a(class), false, &mut pieces);
+    b.pieces(
+        &a,
+        |class| operation.keep_b(class),
+        operation.reverse_b(),
+        &mut pieces,
+    );
+
+    faces(chain(pieces), surface)
+}
+
+/// Compute the edges of a 2-dimensional shape that is the result of a boolean
+/// operation
+///
+/// Shapes don't get passed a tolerance when their edges are requested, but one
+/// is required to compute the boolean operation. A default tolerance is derived
+/// from the shape's bounding volume.
+pub fn shape_edges(shape: &impl Shape) -> Edges {
+    let tolerance = default_tolerance(&shape.bounding_volume());
+    let (cycles, _) = cycles(shape.faces(tolerance, &mut DebugInfo::new()));
+
+    Edges { cycles }
+}
+
+/// Collect the cycles of all faces
+fn cycles(faces: Faces) -> (Vec<Cycle>, Option<Surface>) {
+    let mut cycles = Vec::new();
+    let mut surface = None;
+
+    for face in faces.0 {
+        match face {
+            Face::Face {
+                edges,
+                surface: face_surface,
+            } => {
+                match &surface {
+                    Some(surface) if surface != &face_surface => {
+                        panic!(
+                            "Trying to combine faces with different surfaces."
+                        );
+                    }
+                    _ => surface = Some(face_surface),
+                }
+
+                cycles.extend(edges.cycles);
+            }
+            Face::Triangles(_) => {
+                // None of the 2D types use the triangles representation.
+                unreachable!()
+            }
+        }
+    }
+
+    (cycles, surface)
+}
+
+/// One of the operands of a boolean operation
+struct Operand {
+    cycles: Vec<ApproxCycle>,
+}
+
+impl Operand {
+    fn new(cycles: Vec<Cycle>, surface: &Surface, tolerance: f64) -> Self {
+        let mut cycles: Vec<_> = cycles
+            .into_iter()
+            .map(|cycle| ApproxCycle::new(cycle, surface, tolerance))
+            .collect();
+
+        // The algorithm relies on the outer boundaries of faces being oriented
+        // counter-clockwise, and holes being oriented clockwise. How deeply a
+        // cycle is nested within the other cycles tells us which one it is.
+        let mut reverse = Vec::new();
+        for (i, cycle) in cycles.iter().enumerate() {
+            let point = match cycle.sample_point() {
+                Some(point) => point,
+                None => {
+                    reverse.push(false);
+                    continue;
+                }
+            };
+
+            let depth = cycles
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| j != i && other.contains(point))
+                .count();
+            let is_hole = depth % 2 == 1;
+
+            reverse.push(is_hole == (cycle.signed_area() > 0.));
+        }
+        for (cycle, reverse) in cycles.iter_mut().zip(reverse) {
+            if reverse {
+                cycle.reverse();
+            }
+        }
+
+        Self { cycles }
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &[SurfacePoint; 2]> + '_ {
+        self.cycles
+            .iter()
+            .flat_map(|cycle| &cycle.edges)
+            .flat_map(|edge| &edge.segments)
+    }
+
+    /// Determine whether a point is within the area bounded by the operand
+    fn contains(&self, point: Point<2>) -> bool {
+        contains(self.segments(), point)
+    }
+
+    /// Classify a segment relative to this operand
+    fn classify(&self, [a, b]: [Point<2>; 2]) -> Class {
+        let center = a + (b - a) / 2.;
+
+        for &[c, d] in self.segments() {
+            if distance_to_segment(center, [c.value, d.value]) < EPSILON {
+                if (b - a).dot(&(d - c)) > 0. {
+                    return Class::SameBoundary;
+                } else {
+                    return Class::OppositeBoundary;
+                }
+            }
+        }
+
+        if self.contains(center) {
+            Class::Inside
+        } else {
+            Class::Outside
+        }
+    }
+
+    /// Compute the pieces of this operand that need to be kept
+    fn pieces(
+        &self,
+        other: &Operand,
+        keep: impl Fn(Class) -> bool,
+        reverse: bool,
+        out: &mut Vec<Piece>,
+    ) {
+        for edge in self.cycles.iter().flat_map(|cycle| &cycle.edges) {
+            let mut split = false;
+            let mut segments = Vec::new();
+
+            for &segment in &edge.segments {
+                let parts = split_segment(segment, other);
+                split |= parts.len() > 1;
+
+                for part in parts {
+                    let class = other.classify(part.map(|point| point.value));
+                    segments.push((part, class));
+                }
+            }
+
+            let first_class = segments.first().map(|&(_, class)| class);
+            let uniform = segments
+                .iter()
+                .all(|&(_, class)| Some(class) == first_class);
+
+            if !split && uniform {
+                // The edge is not affected by the other operand. Keep it as it
+                // is, to preserve its exact geometry.
+                let keep = first_class.map(&keep).unwrap_or(false);
+                if keep {
+                    let mut piece = Piece::from_edge(edge);
+                    if reverse {
+                        piece.reverse();
+                    }
+                    out.push(piece);
+                }
+
+                continue;
+            }
+
+            for (segment, class) in segments {
+                if keep(class) {
+                    let mut piece = Piece::from_segment(segment);
+                    if reverse {
+                        piece.reverse();
+                    }
+                    out.push(piece);
+                }
+            }
+        }
+    }
+}
+
+/// A cycle, together with its approximation
+struct ApproxCycle {
+    edges: Vec<ApproxEdge>,
+}
+
+impl ApproxCycle {
+    fn new(cycle: Cycle, surface: &Surface, tolerance: f64) -> Self {
+        let edges = cycle
+            .edges
+            .into_iter()
+            .map(|edge| ApproxEdge::new(edge, surface, tolerance))
+            .collect();
+
+        Self { edges }
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &[SurfacePoint; 2]> + '_ {
+        self.edges.iter().flat_map(|edge| &edge.segments)
+    }
+
+    /// A point on the cycle that can be used for containment tests
+    fn sample_point(&self) -> Option<Point<2>> {
+        self.segments()
+            .next()
+            .map(|[a, b]| a.value + (b.value - a.value) / 2.)
+    }
+
+    fn contains(&self, point: Point<2>) -> bool {
+        contains(self.segments(), point)
+    }
+
+    fn signed_area(&self) -> f64 {
+        signed_area(self.segments().map(|[a, b]| [a.value, b.value]))
+    }
+
+    fn reverse(&mut self) {
+        self.edges.reverse();
+        for edge in &mut self.edges {
+            edge.reverse();
+        }
+    }
+}
+
+/// An edge, together with its approximation in surface coordinates
+struct ApproxEdge {
+    edge: Edge,
+    segments: Vec<[SurfacePoint; 2]>,
+}
+
+impl ApproxEdge {
+    fn new(edge: Edge, surface: &Surface, tolerance: f64) -> Self {
+        let segments = edge
+            .approx(tolerance)
+            .segments
+            .into_iter()
+            .map(|segment| {
+                // Can't panic, unless the approximation wrongfully generates
+                // points that are not in the surface.
+                [segment.a, segment.b]
+                    .map(|point| surface.point_model_to_surface(point).unwrap())
+            })
+            .collect();
+
+        Self { edge, segments }
+    }
+
+    fn reverse(&mut self) {
+        self.edge.reverse();
+
+        self.segments.reverse();
+        for segment in &mut self.segments {
+            segment.reverse();
+        }
+    }
+}
+
+/// A piece of an operand's boundary that is part of the result
+struct Piece {
+    edge: Edge,
+
+    /// The approximated segments of the piece
+    ///
+    /// Only used to determine the piece's start and end, and for containment
+    /// tests. Never empty.
+    segments: Vec<[SurfacePoint; 2]>,
+
+    /// Indicates whether the piece is connected to itself
+    closed: bool,
+}
+
+impl Piece {
+    fn from_edge(edge: &ApproxEdge) -> Self {
+        Self {
+            edge: edge.edge.clone(),
+            segments: edge.segments.clone(),
+            closed: edge.edge.vertices.is_none(),
+        }
+    }
+
+    fn from_segment([a, b]: [SurfacePoint; 2]) -> Self {
+        Self {
+            edge: Edge::new(Curve::Line(Line {
+                a: a.from,
+                b: b.from,
+            })),
+            segments: vec![[a, b]],
+            closed: false,
+        }
+    }
+
+    fn start(&self) -> SurfacePoint {
+        self.segments[0][0]
+    }
+
+    fn end(&self) -> SurfacePoint {
+        self.segments[self.segments.len() - 1][1]
+    }
+
+    fn reverse(&mut self) {
+        self.edge.reverse();
+
+        self.segments.reverse();
+        for segment in &mut self.segments {
+            segment.reverse();
+        }
+    }
+
+    /// Move the start of the piece to exactly the provided point
+    ///
+    /// The ends of pieces that connect to each other might have been computed
+    /// separately, and might not be exactly identical. This is used to make
+    /// sure they are.
+    fn snap_start(&mut self, point: SurfacePoint) {
+        let i = if self.edge.reverse { 1 } else { 0 };
+        self.edge.snap_vertex(i, point.from);
+
+        self.segments[0][0] = point;
+    }
+
+    /// Move the end of the piece to exactly the provided point
+    ///
+    /// See [`Piece::snap_start`].
+    fn snap_end(&mut self, point: SurfacePoint) {
+        let i = if self.edge.reverse { 0 } else { 1 };
+        self.edge.snap_vertex(i, point.from);
+
+        let last = self.segments.len() - 1;
+        self.segments[last][1] = point;
+    }
+}
+
+/// Split a segment wherever it intersects the boundary of an operand
+fn split_segment(
+    segment: [SurfacePoint; 2],
+    operand: &Operand,
+) -> Vec<[SurfacePoint; 2]> {
+    let [a, b] = segment;
+    let length = (b.value - a.value).magnitude();
+
+    let mut params = Vec::new();
+    for &[c, d] in operand.segments() {
+        for t in intersect([a.value, b.value], [c.value, d.value]) {
+            if t * length > EPSILON && (1. - t) * length > EPSILON {
+                params.push(t);
+            }
+        }
+    }
+    params.sort_by(|a, b| a.total_cmp(b));
+    params.dedup_by(|a, b| (*a - *b).abs() * length < EPSILON);
+
+    let mut points = vec![a];
+    for t in params {
+        points.push(SurfacePoint {
+            value: a.value + (b.value - a.value) * t,
+            from: a.from + (b.from - a.from) * t,
+        });
+    }
+    points.push(b);
+
+    points
+        .windows(2)
+        .map(|window| [window[0], window[1]])
+        .collect()
+}
+
+/// Compute the points where segment `ab` is touched by segment `cd`
+///
+/// Returns the points as parameters of `ab`, where `0` corresponds to `a` and
+/// `1` corresponds to `b`.
+fn intersect([a, b]: [Point<2>; 2], [c, d]: [Point<2>; 2]) -> Vec<f64> {
+    let r = b - a;
+    let s = d - c;
+
+    let denominator = cross(r, s);
+    if denominator.abs() > EPSILON * r.magnitude() * s.magnitude() {
+        let t = cross(c - a, s) / denominator;
+        let u = cross(c - a, r) / denominator;
+
+        let tolerance_t = EPSILON / r.magnitude();
+        let tolerance_u = EPSILON / s.magnitude();
+
+        let on_ab = t >= -tolerance_t && t <= 1. + tolerance_t;
+        let on_cd = u >= -tolerance_u && u <= 1. + tolerance_u;
+
+        if on_ab && on_cd {
+            return vec![t];
+        }
+
+        return Vec::new();
+    }
+
+    // The segments are parallel. If they are collinear, the ends of `cd` split
+    // `ab`.
+    if distance_to_line(c, [a, b]) > EPSILON {
+        return Vec::new();
+    }
+
+    let r2 = r.magnitude_squared();
+    vec![(c - a).dot(&r) / r2, (d - a).dot(&r) / r2]
+}
+
+/// Connect the pieces into cycles
+fn chain(mut pieces: Vec<Piece>) -> Vec<(Cycle, Vec<[SurfacePoint; 2]>)> {
+    let mut cycles = Vec::new();
+
+    // Pieces that are connected to themselves form cycles on their own.
+    let mut i = 0;
+    while i < pieces.len() {
+        if pieces[i].closed {
+            let piece = pieces.swap_remove(i);
+            cycles.push((
+                Cycle {
+                    edges: vec![piece.edge],
+                },
+                piece.segments,
+            ));
+        } else {
+            i += 1;
+        }
+    }
+
+    while let Some(first) = pieces.pop() {
+        let start = first.start();
+        let mut cycle = vec![first];
+
+        loop {
+            // Can't panic. We just pushed to `cycle`, and never remove from it.
+            let end = cycle[cycle.len() - 1].end();
+
+            if (end.value - start.value).magnitude() < EPSILON {
+                break;
+            }
+
+            let next = pieces.iter().position(|piece| {
+                (piece.start().value - end.value).magnitude() < EPSILON
+            });
+
+            match next {
+                Some(i) => {
+                    let mut piece = pieces.swap_remove(i);
+                    piece.snap_start(end);
+                    cycle.push(piece);
+                }
+                None => break,
+            }
+        }
+
+        // Can't panic. `cycle` always contains at least `first`.
+        let last = cycle.len() - 1;
+        if (cycle[last].end().value - start.value).magnitude() >= EPSILON {
+            // The pieces don't form a closed cycle. This shouldn't happen, if
+            // the operands were valid.
+            continue;
+        }
+        cycle[last].snap_end(start);
+
+        let segments = cycle
+            .iter()
+            .flat_map(|piece| piece.segments.iter().copied())
+            .collect();
+        let edges = cycle.into_iter().map(|piece| piece.edge).collect();
+
+        cycles.push((Cycle { edges }, segments));
+    }
+
+    cycles
+}
+
+/// Sort the cycles into faces
+///
+/// Cycles that are oriented counter-clockwise are the outer boundaries of
+/// faces. All others are holes, and are assigned to the smallest face that
+/// contains them.
+fn faces(
+    cycles: Vec<(Cycle, Vec<[SurfacePoint; 2]>)>,
+    surface: Surface,
+) -> Faces {
+    let mut outer = Vec::new();
+    let mut holes = Vec::new();
+
+    for (cycle, segments) in cycles {
+        let segments: Vec<_> =
+            segments.iter().map(|[a, b]| [a.value, b.value]).collect();
+        let area = signed_area(segments.iter().copied());
+
+        if area > 0. {
+            outer.push((vec![cycle], segments, area));
+        } else {
+            holes.push((cycle, segments));
+        }
+    }
+
+    for (hole, segments) in holes {
+        let [a, b] = segments[0];
+        let point = a + (b - a) / 2.;
+
+        let face = outer
+            .iter_mut()
+            .filter(|(_, outer, _)| {
+                contains_points(outer.iter().copied(), point)
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+        // A hole that isn't in any face doesn't make sense, and is ignored.
+        if let Some((cycles, _, _)) = face {
+            cycles.push(hole);
+        }
+    }
+
+    let faces = outer
+        .into_iter()
+        .map(|(cycles, _, _)| Face::Face {
+            edges: Edges { cycles },
+            surface: surface.clone(),
+        })
+        .collect();
+
+    Faces(faces)
+}
+
+fn contains<'r>(
+    segments: impl Iterator<Item = &'r [SurfacePoint; 2]>,
+    point: Point<2>,
+) -> bool {
+    contains_points(segments.map(|[a, b]| [a.value, b.value]), point)
+}
+
+/// Determine whether a point is within a polygon, using the even-odd rule
+pub fn contains_points(
+    segments: impl Iterator<Item = [Point<2>; 2]>,
+    point: Point<2>,
+) -> bool {
+    let mut inside = false;
+
+    for [a, b] in segments {
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Compute the signed area of a polygon
+///
+/// The area is positive, if the polygon is counter-clockwise.
+pub fn signed_area(segments: impl Iterator<Item = [Point<2>; 2]>) -> f64 {
+    segments.map(|[a, b]| a.x * b.y - b.x * a.y).sum::<f64>() / 2.
+}
+
+fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> f64 {
+    let ab = b - a;
+    let t = ((point - a).dot(&ab) / ab.magnitude_squared()).clamp(0., 1.);
+
+    (point - (a + ab * t)).magnitude()
+}
+
+fn distance_to_line(point: Point<2>, [a, b]: [Point<2>; 2]) -> f64 {
+    let ab = b - a;
+    cross(ab, point - a).abs() / ab.magnitude()
+}
+
+fn cross(a: Vector<2>, b: Vector<2>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::kernel::{
+        geometry::Curve,
+        test_util::{
+            area, assert_approx, circle, cycles, rectangle, square, TOLERANCE,
+        },
+        topology::faces::Face,
+    };
+
+    use super::{difference, intersection, union};
+
+    #[test]
+    fn difference_of_contained_shape() {
+        let a = square([0., 0.], 2.);
+        let b = square([0.5, 0.5], 1.);
+
+        let result = difference(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 2);
+        assert_approx(area(&result), 3., 1e-9);
+    }
+
+    #[test]
+    fn difference_with_partial_overlap() {
+        let a = square([0., 0.], 2.);
+        let b = square([1., 1.], 2.);
+
+        let result = difference(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+        assert_approx(area(&result), 3., 1e-9);
+    }
+
+    #[test]
+    fn difference_that_splits_shape() {
+        let a = square([0., 0.], 3.);
+        let b = rectangle([1., -1.], [1., 5.]);
+
+        let result = difference(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 2);
+        assert_approx(area(&result), 6., 1e-9);
+    }
+
+    #[test]
+    fn difference_from_shape_with_hole() {
+        let a = square([0., 0.], 4.);
+        let hole = square([1., 1.], 2.);
+        let a = difference(a, hole, TOLERANCE);
+
+        // Overlaps both the outer boundary and the hole, connecting the two.
+        let b = rectangle([-1., 1.5], [3., 1.]);
+
+        let result = difference(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+        assert_approx(area(&result), 16. - 4. - 1., 1e-9);
+    }
+
+    #[test]
+    fn difference_preserves_exact_circles() {
+        let a = circle(1.);
+        let b = circle(0.5);
+
+        let result = difference(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        match &result.0[0] {
+            Face::Face { edges, .. } => {
+                assert_eq!(edges.cycles.len(), 2);
+                for cycle in &edges.cycles {
+                    assert!(matches!(cycle.edges[0].curve, Curve::Circle(_)));
+                }
+            }
+            Face::Triangles(_) => panic!("Expected face"),
+        }
+    }
+
+    #[test]
+    fn difference_of_circle_with_partial_overlap() {
+        let a = square([0., 0.], 2.);
+        let b = circle(1.);
+
+        let result = difference(a, b, TOLERANCE);
+
+        // The approximation of the quarter circle can deviate from the actual
+        // circle by up to the tolerance, along its whole length.
+        let max_error = PI / 2. * TOLERANCE;
+
+        assert_eq!(result.0.len(), 1);
+        assert!((area(&result) - (4. - PI / 4.)).abs() < max_error);
+    }
+
+    #[test]
+    fn intersection_of_overlapping_shapes() {
+        let a = square([0., 0.], 2.);
+        let b = square([1., 1.], 2.);
+
+        let result = intersection(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_approx(area(&result), 1., 1e-9);
+    }
+
+    #[test]
+    fn intersection_of_identical_shapes() {
+        let a = square([0., 0.], 2.);
+        let b = square([0., 0.], 2.);
+
+        let result = intersection(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_approx(area(&result), 4., 1e-9);
+    }
+
+    #[test]
+    fn union_of_overlapping_shapes() {
+        let a = square([0., 0.], 2.);
+        let b = square([1., 1.], 2.);
+
+        let result = union(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+        assert_approx(area(&result), 7., 1e-9);
+    }
+
+    #[test]
+    fn union_of_touching_shapes() {
+        let a = square([0., 0.], 1.);
+        let b = square([1., 0.], 1.);
+
+        let result = union(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+        assert_approx(area(&result), 2., 1e-9);
+    }
+
+    #[test]
+    fn union_of_disjoint_shapes() {
+        let a = square([0., 0.], 1.);
+        let b = square([2., 0.], 1.);
+
+        let result = union(a, b, TOLERANCE);
+
+        assert_eq!(result.0.len(), 2);
+        assert_approx(area(&result), 2., 1e-9);
+    }
+}
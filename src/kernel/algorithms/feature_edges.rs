@@ -0,0 +1,288 @@
+//! Recover the edges of a shape from its triangle mesh
+//!
+//! Shapes whose faces are represented as triangles, like the results of the
+//! boolean operations in [`super::csg`], no longer know which edges bound their
+//! faces. This module reconstructs those edges from the triangle mesh.
+//!
+//! Triangles that lie in the same plane are considered to be part of the same
+//! face. The boundary of each such face is made up of the triangle edges that
+//! are not cancelled out by an edge of a neighboring triangle in the same
+//! plane. This also works for triangle meshes that contain T-junctions, as
+//! created by the boolean operations, as edges are compared along their
+//! supporting lines, not vertex by vertex.
+
+use parry3d_f64::shape::Triangle;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{Curve, Line},
+        topology::edges::{Cycle, Edge, Edges},
+        util::default_tolerance,
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-7;
+
+/// Compute the edges of the faces that make up a triangle mesh
+///
+/// Returns one cycle per boundary of each planar face. Curved faces are
+/// represented by triangles in different planes, and will therefore result in
+/// one face per triangle (or group of coplanar triangles).
+pub fn feature_edges(triangles: &[Triangle]) -> Edges {
+    let mut cycles = Vec::new();
+
+    for face in planar_faces(triangles) {
+        let mut lines: Vec<SupportingLine> = Vec::new();
+
+        for &Triangle { a, b, c } in face {
+            for [a, b] in [[a, b], [b, c], [c, a]] {
+                match lines.iter_mut().find(|line| line.contains(a, b)) {
+                    Some(line) => line.segments.push([a, b]),
+                    None => lines.push(SupportingLine::new(a, b)),
+                }
+            }
+        }
+
+        let segments =
+            lines.into_iter().flat_map(|line| line.boundary()).collect();
+        cycles.extend(chain(segments));
+    }
+
+    Edges { cycles }
+}
+
+/// Compute the edges of a shape whose faces are triangles
+///
+/// Shapes don't get passed a tolerance when their edges are requested, but one
+/// is required to compute the triangle mesh. A default tolerance is derived
+/// from the shape's bounding volume.
+pub fn of_shape(shape: &impl Shape) -> Edges {
+    let tolerance = default_tolerance(&shape.bounding_volume());
+    let mut debug_info = DebugInfo::new();
+
+    let mut triangles = Vec::new();
+    shape.faces(tolerance, &mut debug_info).triangles(
+        tolerance,
+        &mut triangles,
+        &mut debug_info,
+    );
+
+    feature_edges(&triangles)
+}
+
+/// Group the triangles by the plane they are in
+fn planar_faces(triangles: &[Triangle]) -> Vec<Vec<&Triangle>> {
+    let mut faces: Vec<(Vector<3>, f64, Vec<&Triangle>)> = Vec::new();
+
+    for triangle in triangles {
+        let normal =
+            (triangle.b - triangle.a).cross(&(triangle.c - triangle.a));
+        if normal.magnitude() <= EPSILON * EPSILON {
+            // Degenerate triangles don't contribute to any face.
+            continue;
+        }
+
+        let normal = normal.normalize();
+        let distance = normal.dot(&triangle.a.coords);
+
+        let face = faces.iter_mut().find(|(n, d, _)| {
+            (n - normal).magnitude() < EPSILON && (d - distance).abs() < EPSILON
+        });
+        match face {
+            Some((_, _, triangles)) => triangles.push(triangle),
+            None => faces.push((normal, distance, vec![triangle])),
+        }
+    }
+
+    faces
+        .into_iter()
+        .map(|(_, _, triangles)| triangles)
+        .collect()
+}
+
+/// A line that supports a number of directed triangle edges
+struct SupportingLine {
+    origin: Point<3>,
+    direction: Vector<3>,
+    segments: Vec<[Point<3>; 2]>,
+}
+
+impl SupportingLine {
+    fn new(a: Point<3>, b: Point<3>) -> Self {
+        Self {
+            origin: a,
+            direction: (b - a).normalize(),
+            segments: vec![[a, b]],
+        }
+    }
+
+    fn contains(&self, a: Point<3>, b: Point<3>) -> bool {
+        self.distance(a) < EPSILON && self.distance(b) < EPSILON
+    }
+
+    fn distance(&self, point: Point<3>) -> f64 {
+        (point - self.origin).cross(&self.direction).magnitude()
+    }
+
+    fn coord(&self, point: Point<3>) -> f64 {
+        (point - self.origin).dot(&self.direction)
+    }
+
+    /// Compute the parts of the line that are part of the face boundary
+    ///
+    /// Directed segments that overlap and point in opposite directions cancel
+    /// each other out. What's left after that is the boundary.
+    fn boundary(&self) -> Vec<[Point<3>; 2]> {
+        // All points on the line where a segment starts or ends, sorted along
+        // the line.
+        let mut points: Vec<(f64, Point<3>)> = self
+            .segments
+            .iter()
+            .flatten()
+            .map(|&point| (self.coord(point), point))
+            .collect();
+        points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        points.dedup_by(|(a, _), (b, _)| (*a - *b).abs() < EPSILON);
+
+        let segments: Vec<_> = self
+            .segments
+            .iter()
+            .map(|&[a, b]| (self.coord(a), self.coord(b)))
+            .collect();
+
+        let mut boundary = Vec::new();
+        let mut current: Option<(i32, Point<3>, Point<3>)> = None;
+
+        for window in points.windows(2) {
+            let (t0, p0) = window[0];
+            let (t1, p1) = window[1];
+
+            let mut winding: i32 = 0;
+            for &(a, b) in &segments {
+                if a.min(b) <= t0 + EPSILON && a.max(b) >= t1 - EPSILON {
+                    winding += if b > a { 1 } else { -1 };
+                }
+            }
+            let winding = winding.signum();
+
+            match &mut current {
+                Some((w, _, end)) if *w == winding => {
+                    *end = p1;
+                }
+                _ => {
+                    if let Some(piece) = current.take() {
+                        boundary.extend(Self::directed(piece));
+                    }
+                    current = Some((winding, p0, p1));
+                }
+            }
+        }
+        if let Some(piece) = current {
+            boundary.extend(Self::directed(piece));
+        }
+
+        boundary
+    }
+
+    fn directed(
+        (winding, start, end): (i32, Point<3>, Point<3>),
+    ) -> Option<[Point<3>; 2]> {
+        match winding {
+            1 => Some([start, end]),
+            -1 => Some([end, start]),
+            _ => None,
+        }
+    }
+}
+
+/// Connect directed segments into cycles
+///
+/// Segments that don't end up in a closed cycle are ignored. This can only
+/// happen, if the triangle mesh was not closed to begin with.
+fn chain(mut segments: Vec<[Point<3>; 2]>) -> Vec<Cycle> {
+    let mut cycles = Vec::new();
+
+    while let Some([start, mut end]) = segments.pop() {
+        let mut edges = vec![line(start, end)];
+
+        while (end - start).magnitude() >= EPSILON {
+            let next = segments
+                .iter()
+                .position(|[a, _]| (a - end).magnitude() < EPSILON);
+
+            match next {
+                Some(i) => {
+                    let [a, b] = segments.swap_remove(i);
+                    edges.push(line(a, b));
+                    end = b;
+                }
+                None => break,
+            }
+        }
+
+        if (end - start).magnitude() < EPSILON {
+            cycles.push(Cycle { edges });
+        }
+    }
+
+    cycles
+}
+
+fn line(a: Point<3>, b: Point<3>) -> Edge {
+    Edge::new(Curve::Line(Line { a, b }))
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::point;
+    use parry3d_f64::shape::Triangle;
+
+    use super::feature_edges;
+
+    #[test]
+    fn square_with_t_junction() {
+        // A square, made up of three triangles. The point at the center of the
+        // bottom side is a T-junction.
+        let a = point![0., 0., 0.];
+        let b = point![1., 0., 0.];
+        let c = point![1., 1., 0.];
+        let d = point![0., 1., 0.];
+        let e = point![0.5, 0., 0.];
+
+        let triangles = [
+            Triangle::new(a, e, d),
+            Triangle::new(e, c, d),
+            Triangle::new(e, b, c),
+        ];
+
+        let edges = feature_edges(&triangles);
+
+        assert_eq!(edges.cycles.len(), 1);
+        assert_eq!(edges.cycles[0].edges.len(), 4);
+    }
+
+    #[test]
+    fn faces_in_different_planes() {
+        let a = point![0., 0., 0.];
+        let b = point![1., 0., 0.];
+        let c = point![0., 1., 0.];
+        let d = point![0., 0., 1.];
+
+        // A tetrahedron
+        let triangles = [
+            Triangle::new(a, c, b),
+            Triangle::new(a, b, d),
+            Triangle::new(a, d, c),
+            Triangle::new(b, c, d),
+        ];
+
+        let edges = feature_edges(&triangles);
+
+        assert_eq!(edges.cycles.len(), 4);
+        assert_eq!(edges.vertices().len(), 4);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod csg;
+pub mod csg_2d;
+pub mod feature_edges;
+pub mod offset_2d;
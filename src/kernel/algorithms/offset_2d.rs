@@ -0,0 +1,590 @@
+//! Offsetting of 2-dimensional faces
+//!
+//! Each edge of a face is moved perpendicular to itself. Lines stay lines, and
+//! arcs and circles stay concentric to the original ones, with their radius
+//! adjusted. Where the moved edges that meet at a corner leave a gap between
+//! them, the gap is closed by an arc around the original corner. Where they
+//! overlap, they are trimmed to their intersection. Ellipses and
+//! Bézier curves don't keep their kind of curve when they are offset, so they
+//! are approximated with lines first.
+//!
+//! Holes are offset separately from the outer boundary, and then subtracted
+//! from it. Faces are offset separately from each other, and then merged. This
+//! takes care of holes and faces that grow into each other.
+
+use nalgebra::vector;
+
+use crate::{
+    kernel::{
+        algorithms::csg_2d,
+        geometry::{Circle, Curve, Line, Surface},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+    },
+    math::{Point, Vector},
+};
+
+/// The distance below which points are considered to be identical
+const EPSILON: f64 = 1e-9;
+
+/// Grow or shrink a set of faces by a distance
+///
+/// Positive distances grow the faces, negative distances shrink them. Cycles
+/// that vanish entirely are removed.
+///
+/// # Panics
+///
+/// Panics, if only some edges of a cycle vanish. Resolving the intersections
+/// that this creates is not supported.
+pub fn offset(faces: Faces, distance: f64, tolerance: f64) -> Faces {
+    let faces = csg_2d::orient(faces, tolerance);
+
+    if distance == 0. {
+        return faces;
+    }
+
+    let mut offset_faces = Faces(Vec::new());
+
+    for face in faces.0 {
+        let (edges, surface) = match face {
+            Face::Face { edges, surface } => (edges, surface),
+            Face::Triangles(_) => {
+                // None of the 2D types use the triangles representation.
+                unreachable!()
+            }
+        };
+
+        let cycles: Vec<_> = edges
+            .cycles
+            .iter()
+            .map(|cycle| free_form_curves_to_lines(cycle, tolerance))
+            .collect();
+
+        // Oriented faces have their outer boundary first.
+        let mut cycles = cycles.iter();
+        let outer = match cycles
+            .next()
+            .and_then(|outer| offset_cycle(outer, distance))
+        {
+            Some(outer) => outer,
+            None => continue,
+        };
+
+        // Holes are turned into faces of their own, so they can be merged with
+        // each other, before they are subtracted from the outer boundary.
+        let mut holes = Faces(Vec::new());
+        for hole in cycles {
+            if let Some(mut hole) = offset_cycle(hole, distance) {
+                hole.reverse();
+                holes = csg_2d::union(
+                    holes,
+                    single_cycle_face(hole, &surface),
+                    tolerance,
+                );
+            }
+        }
+
+        let face = csg_2d::difference(
+            single_cycle_face(outer, &surface),
+            holes,
+            tolerance,
+        );
+        offset_faces = csg_2d::union(offset_faces, face, tolerance);
+    }
+
+    offset_faces
+}
+
+/// Offset a cycle of an oriented face
+///
+/// Returns `None`, if the cycle vanishes.
+fn offset_cycle(cycle: &Cycle, distance: f64) -> Option<Cycle> {
+    // A cycle without vertices consists of a single full circle.
+    if let [edge @ Edge { vertices: None, .. }] = cycle.edges.as_slice() {
+        let mut edge = edge.clone();
+        if let Curve::Circle(circle) = &mut edge.curve {
+            let radius = offset_radius(circle, distance, edge.reverse);
+            if radius < EPSILON {
+                return None;
+            }
+
+            circle.radius *= radius / circle.radius.magnitude();
+        }
+
+        return Some(Cycle { edges: vec![edge] });
+    }
+
+    let segments: Vec<_> = cycle
+        .edges
+        .iter()
+        .map(|edge| Segment::new(edge, distance))
+        .collect();
+    let n = segments.len();
+
+    // The points where the offset segments start and end, and the arcs that
+    // close the gaps after each of them.
+    let mut points: Vec<_> = segments
+        .iter()
+        .map(|segment| segment.offset_points(distance))
+        .collect();
+    let mut gaps = vec![None; n];
+
+    for i in 0..n {
+        let j = (i + 1) % n;
+        let [previous, next] = [&segments[i], &segments[j]];
+
+        let corner = previous.points[1];
+        let [a, b] = [points[i][1], points[j][0]];
+
+        if (b - a).magnitude() < EPSILON {
+            // The segments meet smoothly. Nothing to do.
+            continue;
+        }
+
+        let turn = cross(previous.tangents[1], next.tangents[0]);
+        if turn * distance.signum() > -EPSILON {
+            gaps[i] = Some(gap_arc(corner, [a, b], distance));
+        } else {
+            let point = intersection(&previous.curve, &next.curve, corner)
+                .unwrap_or_else(|| a + (b - a) / 2.);
+
+            points[i][1] = point;
+            points[j][0] = point;
+        }
+    }
+
+    let mut edges = Vec::new();
+    let mut is_flipped = Vec::new();
+
+    for ((segment, [a, b]), gap) in segments.iter().zip(&points).zip(gaps) {
+        if (b - a).magnitude() >= EPSILON {
+            let (edge, flipped) = segment.offset_edge([*a, *b]);
+            edges.push(edge);
+            is_flipped.push(flipped);
+        }
+        edges.extend(gap);
+    }
+
+    // If all edges are flipped, the cycle has turned inside out. The same is
+    // true, if its area has changed sign, which happens to cycles that are
+    // narrower in one direction than in the other.
+    let area = |points: &[[Point<3>; 2]]| {
+        let points: Vec<_> =
+            points.iter().flatten().map(|point| point.xy()).collect();
+        csg_2d::signed_area(
+            (0..points.len())
+                .map(|i| [points[i], points[(i + 1) % points.len()]]),
+        )
+    };
+    let original: Vec<_> =
+        segments.iter().map(|segment| segment.points).collect();
+
+    if is_flipped.iter().all(|&flipped| flipped)
+        || area(&points) * area(&original) <= 0.
+    {
+        return None;
+    }
+
+    // Panicking is not great, but as long as we don't have a real error
+    // handling mechanism, it will do.
+    assert!(
+        !is_flipped.contains(&true),
+        "Offset distance is too large for the shape. Some of its edges would \
+        vanish, which is not supported."
+    );
+
+    Some(Cycle { edges })
+}
+
+/// An edge of an oriented cycle, and the curve it is moved to
+struct Segment<'r> {
+    edge: &'r Edge,
+
+    /// The start and end point of the edge, in the direction of the cycle
+    points: [Point<3>; 2],
+
+    /// The direction of the edge at its start and end point
+    tangents: [Vector<3>; 2],
+
+    /// The curve that the edge is moved to
+    curve: OffsetCurve,
+}
+
+impl<'r> Segment<'r> {
+    fn new(edge: &'r Edge, distance: f64) -> Self {
+        let points = match edge.vertices {
+            Some([a, b]) if edge.reverse => [b, a],
+            Some(vertices) => vertices,
+            None => {
+                // Only cycles that consist of a single full circle have edges
+                // without vertices, and those are handled separately.
+                unreachable!()
+            }
+        };
+
+        let (tangents, curve) = match edge.curve {
+            Curve::Line(_) => {
+                let direction = (points[1] - points[0]).normalize();
+                let curve = OffsetCurve::Line {
+                    point: points[0] + outward(direction) * distance,
+                    direction,
+                };
+
+                ([direction; 2], curve)
+            }
+            Curve::Circle(circle) => {
+                let tangents = points.map(|point| {
+                    let tangent = Vector::<3>::z()
+                        .cross(&(point - circle.center))
+                        .normalize();
+                    if edge.reverse {
+                        -tangent
+                    } else {
+                        tangent
+                    }
+                });
+                let curve = OffsetCurve::Circle {
+                    center: circle.center,
+                    radius: offset_radius(&circle, distance, edge.reverse),
+                };
+
+                (tangents, curve)
+            }
+            Curve::Bezier(_) | Curve::Ellipse(_) => {
+                // Ellipses and Bézier curves have been approximated with
+                // lines.
+                unreachable!()
+            }
+        };
+
+        Self {
+            edge,
+            points,
+            tangents,
+            curve,
+        }
+    }
+
+    /// The start and end point of the edge, moved by `distance`
+    fn offset_points(&self, distance: f64) -> [Point<3>; 2] {
+        [0, 1].map(|i| self.points[i] + outward(self.tangents[i]) * distance)
+    }
+
+    /// Create the offset edge between the given points
+    ///
+    /// Also returns whether the edge has been flipped, meaning it has been
+    /// trimmed by so much, that it now goes in the opposite direction.
+    fn offset_edge(&self, [a, b]: [Point<3>; 2]) -> (Edge, bool) {
+        match (self.edge.curve, self.curve) {
+            (Curve::Line(_), _) => {
+                let flipped =
+                    (b - a).dot(&(self.points[1] - self.points[0])) < 0.;
+                (Edge::new(Curve::Line(Line { a, b })), flipped)
+            }
+            (Curve::Circle(circle), OffsetCurve::Circle { radius, .. }) => {
+                let offset_circle = Circle {
+                    radius: circle.radius
+                        * (radius.max(EPSILON) / circle.radius.magnitude()),
+                    ..circle
+                };
+
+                // The arc can only become shorter, unless it has been flipped.
+                let [original, vertices] = if self.edge.reverse {
+                    [[self.points[1], self.points[0]], [b, a]]
+                } else {
+                    [self.points, [a, b]]
+                };
+                let flipped = radius < EPSILON
+                    || offset_circle.arc_angle(vertices)
+                        > circle.arc_angle(original) + EPSILON;
+
+                let edge = Edge {
+                    curve: Curve::Circle(offset_circle),
+                    vertices: Some(vertices),
+                    reverse: self.edge.reverse,
+                };
+                (edge, flipped)
+            }
+            _ => {
+                // The offset curve is always of the same kind as the original
+                // one.
+                unreachable!()
+            }
+        }
+    }
+}
+
+/// A curve that an edge is moved to
+#[derive(Clone, Copy)]
+enum OffsetCurve {
+    Line {
+        point: Point<3>,
+        direction: Vector<3>,
+    },
+    Circle {
+        center: Point<3>,
+
+        /// The radius of the circle
+        ///
+        /// Can be negative, if the edge has been moved beyond the center.
+        radius: f64,
+    },
+}
+
+/// Compute the intersection of two curves that is closest to `near`
+fn intersection(
+    a: &OffsetCurve,
+    b: &OffsetCurve,
+    near: Point<3>,
+) -> Option<Point<3>> {
+    let candidates = match (*a, *b) {
+        (
+            OffsetCurve::Line {
+                point: p,
+                direction: u,
+            },
+            OffsetCurve::Line {
+                point: q,
+                direction: v,
+            },
+        ) => {
+            let denominator = cross(u, v);
+            if denominator.abs() < EPSILON {
+                return None;
+            }
+
+            vec![p + u * (cross(q - p, v) / denominator)]
+        }
+        (
+            OffsetCurve::Line { point, direction },
+            OffsetCurve::Circle { center, radius },
+        )
+        | (
+            OffsetCurve::Circle { center, radius },
+            OffsetCurve::Line { point, direction },
+        ) => {
+            // Solve `|point + direction * s - center| = radius` for `s`. If the
+            // line misses the circle, use the closest point instead.
+            let w = point - center;
+            let b = w.dot(&direction);
+            let discriminant =
+                (b * b - w.magnitude_squared() + radius * radius).max(0.);
+
+            [-1., 1.]
+                .map(|sign| {
+                    point + direction * (-b + sign * discriminant.sqrt())
+                })
+                .to_vec()
+        }
+        (
+            OffsetCurve::Circle {
+                center: c1,
+                radius: r1,
+            },
+            OffsetCurve::Circle {
+                center: c2,
+                radius: r2,
+            },
+        ) => {
+            let d = (c2 - c1).magnitude();
+            if d < EPSILON {
+                return None;
+            }
+
+            let e = (c2 - c1) / d;
+            let x = (r1 * r1 - r2 * r2 + d * d) / (2. * d);
+            let y = (r1 * r1 - x * x).max(0.).sqrt();
+
+            let base = c1 + e * x;
+            let perpendicular = vector![-e.y, e.x, 0.];
+            [-1., 1.]
+                .map(|sign| base + perpendicular * (sign * y))
+                .to_vec()
+        }
+    };
+
+    candidates.into_iter().min_by(|a, b| {
+        (a - near).magnitude().total_cmp(&(b - near).magnitude())
+    })
+}
+
+/// Create the arc around `corner` that closes the gap from `a` to `b`
+///
+/// The gap is on the outside of the corner, so the arc turns left if the cycle
+/// grows, and right if it shrinks.
+fn gap_arc(corner: Point<3>, [a, b]: [Point<3>; 2], distance: f64) -> Edge {
+    if distance > 0. {
+        Edge::with_vertices(
+            Curve::Circle(Circle {
+                center: corner,
+                radius: a - corner,
+                normal: Vector::z(),
+            }),
+            [a, b],
+        )
+    } else {
+        let mut edge = Edge::with_vertices(
+            Curve::Circle(Circle {
+                center: corner,
+                radius: b - corner,
+                normal: Vector::z(),
+            }),
+            [b, a],
+        );
+        edge.reverse();
+        edge
+    }
+}
+
+/// Compute the radius of a circle, after its edge has been moved outward
+fn offset_radius(circle: &Circle, distance: f64, reverse: bool) -> f64 {
+    let radius = circle.radius.magnitude();
+
+    // Circles are oriented counter-clockwise, which means their interior is to
+    // the left, and outward means away from the center. Unless they're
+    // reversed.
+    if reverse {
+        radius - distance
+    } else {
+        radius + distance
+    }
+}
+
+/// Replace the ellipses and Bézier curves in a cycle with approximating lines
+fn free_form_curves_to_lines(cycle: &Cycle, tolerance: f64) -> Cycle {
+    let edges = cycle
+        .edges
+        .iter()
+        .flat_map(|edge| match edge.curve {
+            Curve::Bezier(_) | Curve::Ellipse(_) => edge
+                .approx(tolerance)
+                .segments
+                .into_iter()
+                .map(|segment| {
+                    Edge::new(Curve::Line(Line {
+                        a: segment.a,
+                        b: segment.b,
+                    }))
+                })
+                .collect(),
+            _ => vec![edge.clone()],
+        })
+        .collect();
+
+    Cycle { edges }
+}
+
+fn single_cycle_face(cycle: Cycle, surface: &Surface) -> Faces {
+    Faces(vec![Face::Face {
+        edges: Edges {
+            cycles: vec![cycle],
+        },
+        surface: surface.clone(),
+    }])
+}
+
+/// The direction that points away from the interior of an oriented cycle
+///
+/// The interior is always to the left of the cycle's edges.
+fn outward(tangent: Vector<3>) -> Vector<3> {
+    vector![tangent.y, -tangent.x, 0.]
+}
+
+fn cross(a: Vector<3>, b: Vector<3>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::kernel::{
+        algorithms::csg_2d::difference,
+        geometry::Curve,
+        test_util::{
+            arcs, area, assert_approx, circle, cycles, rectangle, square,
+            TOLERANCE,
+        },
+        topology::faces::{Face, Faces},
+    };
+
+    use super::offset;
+
+    #[test]
+    fn offset_grows_and_rounds_convex_corners() {
+        let result = offset(square([0., 0.], 2.), 1., TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(arcs(&result), 4);
+        assert_approx(area(&result), 4. + 8. + PI, 0.01);
+    }
+
+    #[test]
+    fn offset_shrinks_without_rounding_corners() {
+        let result = offset(square([0., 0.], 4.), -1., TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(arcs(&result), 0);
+        assert_approx(area(&result), 4., 1e-9);
+    }
+
+    #[test]
+    fn offset_removes_vanishing_holes() {
+        let shape = difference(
+            square([0., 0.], 4.),
+            rectangle([1., 1.], [1., 2.]),
+            TOLERANCE,
+        );
+
+        let result = offset(shape, 0.6, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+    }
+
+    #[test]
+    fn offset_grows_holes_when_shrinking() {
+        let shape =
+            difference(square([0., 0.], 6.), square([2., 2.], 2.), TOLERANCE);
+
+        let result = offset(shape, -0.5, TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 2);
+        assert_approx(area(&result), 25. - (4. + 4. + PI / 4.), 0.01);
+    }
+
+    #[test]
+    fn offset_merges_faces() {
+        let shape = Faces(
+            square([0., 0.], 1.)
+                .0
+                .into_iter()
+                .chain(square([2., 0.], 1.).0)
+                .collect(),
+        );
+
+        let result = offset(shape, 1., TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(cycles(&result), 1);
+    }
+
+    #[test]
+    fn offset_changes_radius_of_circles() {
+        let result = offset(circle(1.), 1., TOLERANCE);
+
+        assert_eq!(result.0.len(), 1);
+        match &result.0[0] {
+            Face::Face { edges, .. } => match edges.cycles[0].edges[0].curve {
+                Curve::Circle(circle) => {
+                    assert_approx(circle.radius.magnitude(), 2., 1e-9)
+                }
+                _ => panic!("Expected circle"),
+            },
+            Face::Triangles(_) => panic!("Expected face"),
+        }
+    }
+}
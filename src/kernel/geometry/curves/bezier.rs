@@ -0,0 +1,279 @@
+use parry3d_f64::math::Isometry;
+
+use crate::math::Point;
+
+/// The maximum number of times a curve is split in half during approximation
+///
+/// This is only reached with tolerances that are far below the precision of
+/// floating point numbers. It makes sure that the approximation terminates
+/// regardless.
+const MAX_DEPTH: u32 = 24;
+
+/// A cubic Bézier curve
+///
+/// The curve starts at the first of its four control points, and ends at the
+/// last one. The curve coordinate of the start is `0.0`, that of the end is
+/// `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bezier {
+    /// The control points of the curve
+    pub points: [Point<3>; 4],
+}
+
+impl Bezier {
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        Self {
+            points: self.points.map(|point| transform.transform_point(&point)),
+        }
+    }
+
+    /// Scale the curve uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the curve through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            points: self.points.map(|point| point * factor),
+        }
+    }
+
+    /// Convert a point in curve coordinates to model coordinates
+    pub fn point_curve_to_model(&self, point: f64) -> Point<3> {
+        self.split(point)[1].points[0]
+    }
+
+    /// Convert a point in model coordinates to curve coordinates
+    ///
+    /// Projects the point onto the curve, if it is not on the curve already.
+    pub fn point_model_to_curve(&self, point: Point<3>) -> f64 {
+        // The ends of the curve are often used as vertices, and need to be
+        // recognized exactly.
+        if point == self.points[0] {
+            return 0.;
+        }
+        if point == self.points[3] {
+            return 1.;
+        }
+
+        let distance = |t: f64| (self.point_curve_to_model(t) - point).norm();
+
+        // Find the closest of a number of samples, then refine the result by
+        // repeatedly searching around it in smaller steps.
+        let samples = 32;
+        let mut t = (0..=samples)
+            .map(|i| i as f64 / samples as f64)
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(0.);
+
+        let mut step = 1. / samples as f64;
+        while step > 1e-15 {
+            t = [t - step, t, t + step]
+                .into_iter()
+                .map(|t| t.clamp(0., 1.))
+                .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+                .unwrap_or(t);
+            step /= 2.;
+        }
+
+        t
+    }
+
+    /// Approximate the whole curve, from its start to its end
+    pub fn approx(&self, tolerance: f64, out: &mut Vec<Point<3>>) {
+        self.approx_between(tolerance, [self.points[0], self.points[3]], out);
+    }
+
+    /// Approximate the section of the curve from `a` to `b`
+    ///
+    /// `a` and `b` must be on the curve. They are part of the approximation,
+    /// exactly as provided.
+    ///
+    /// The approximation is adaptive: The curve is split in half, until each
+    /// part is flat enough to be represented by a line.
+    pub fn approx_between(
+        &self,
+        tolerance: f64,
+        [a, b]: [Point<3>; 2],
+        out: &mut Vec<Point<3>>,
+    ) {
+        let section = self.section_between([a, b]);
+
+        out.push(a);
+        section.approx_inner(tolerance, MAX_DEPTH, out);
+        out.push(b);
+    }
+
+    /// Split the curve into two at the given curve coordinate
+    pub fn split(&self, t: f64) -> [Self; 2] {
+        let [a, b, c, d] = self.points;
+
+        // De Casteljau's algorithm
+        let ab = a + (b - a) * t;
+        let bc = b + (c - b) * t;
+        let cd = c + (d - c) * t;
+        let abc = ab + (bc - ab) * t;
+        let bcd = bc + (cd - bc) * t;
+        let abcd = abc + (bcd - abc) * t;
+
+        [
+            Self {
+                points: [a, ab, abc, abcd],
+            },
+            Self {
+                points: [abcd, bcd, cd, d],
+            },
+        ]
+    }
+
+    /// Compute the section of the curve between two curve coordinates
+    pub fn section(&self, [t0, t1]: [f64; 2]) -> Self {
+        let [before_t1, _] = self.split(t1);
+        if t1 == 0. {
+            return before_t1;
+        }
+
+        let [_, section] = before_t1.split(t0 / t1);
+        section
+    }
+
+    /// Compute the section of the curve between two points on the curve
+    pub fn section_between(&self, [a, b]: [Point<3>; 2]) -> Self {
+        // If the curve loops back to its start, its ends are at the same
+        // point. Which end is meant depends on whether it's the start or the
+        // end of the section.
+        let t0 = if a == self.points[0] {
+            0.
+        } else {
+            self.point_model_to_curve(a)
+        };
+        let t1 = if b == self.points[3] {
+            1.
+        } else {
+            self.point_model_to_curve(b)
+        };
+
+        self.section([t0, t1])
+    }
+
+    /// Add the points between the ends of the approximation
+    fn approx_inner(
+        &self,
+        tolerance: f64,
+        depth: u32,
+        out: &mut Vec<Point<3>>,
+    ) {
+        // The curve is within the convex hull of its control points. If both
+        // of the inner control points are close enough to the line between the
+        // ends, so is the whole curve.
+        let [a, b, c, d] = self.points;
+        let is_flat = distance_to_segment(b, [a, d]) <= tolerance
+            && distance_to_segment(c, [a, d]) <= tolerance;
+
+        if is_flat || depth == 0 {
+            return;
+        }
+
+        let [left, right] = self.split(0.5);
+        left.approx_inner(tolerance, depth - 1, out);
+        out.push(right.points[0]);
+        right.approx_inner(tolerance, depth - 1, out);
+    }
+}
+
+fn distance_to_segment(point: Point<3>, [a, b]: [Point<3>; 2]) -> f64 {
+    let ab = b - a;
+    let length_squared = ab.norm_squared();
+
+    let t = if length_squared == 0. {
+        0.
+    } else {
+        ((point - a).dot(&ab) / length_squared).clamp(0., 1.)
+    };
+
+    (point - (a + ab * t)).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use nalgebra::point;
+
+    use super::{distance_to_segment, Bezier};
+
+    fn bezier() -> Bezier {
+        Bezier {
+            points: [
+                point![0., 0., 0.],
+                point![1., 2., 0.],
+                point![3., 2., 0.],
+                point![4., 0., 0.],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_point_model_to_curve() {
+        let bezier = bezier();
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.;
+            let point = bezier.point_curve_to_model(t);
+
+            assert_abs_diff_eq!(
+                bezier.point_model_to_curve(point),
+                t,
+                epsilon = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_approx_between() {
+        let bezier = bezier();
+        let tolerance = 0.001;
+
+        let a = bezier.point_curve_to_model(0.25);
+        let b = bezier.points[3];
+
+        let mut points = Vec::new();
+        bezier.approx_between(tolerance, [a, b], &mut points);
+
+        // The vertices must be preserved exactly.
+        assert_eq!(points.first(), Some(&a));
+        assert_eq!(points.last(), Some(&b));
+
+        // The points are in order, and the lines between them are close
+        // enough to the curve.
+        for window in points.windows(2) {
+            let [t0, t1] =
+                [window[0], window[1]].map(|p| bezier.point_model_to_curve(p));
+            assert!(t0 < t1);
+
+            for i in 0..=10 {
+                let t = t0 + (t1 - t0) * i as f64 / 10.;
+                let on_curve = bezier.point_curve_to_model(t);
+
+                let distance =
+                    distance_to_segment(on_curve, [window[0], window[1]]);
+                assert!(distance <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_section() {
+        let bezier = bezier();
+        let section = bezier.section([0.2, 0.6]);
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.;
+
+            assert_abs_diff_eq!(
+                section.point_curve_to_model(t),
+                bezier.point_curve_to_model(0.2 + 0.4 * t),
+                epsilon = 1e-12
+            );
+        }
+    }
+}
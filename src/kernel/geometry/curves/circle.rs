@@ -1,12 +1,16 @@
 use std::f64::consts::PI;
 
-use nalgebra::vector;
 use parry3d_f64::math::Isometry;
 
 use crate::math::{Point, Vector};
 
 /// A circle
-#[derive(Clone, Copy, Debug)]
+///
+/// The circle has a local frame: `radius` defines the reference direction
+/// within the circle's plane, `normal` the orientation of that plane. Together
+/// with their cross product, `normal × radius`, they define where the circle is
+/// and which way it goes.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Circle {
     /// The center point of the circle
     pub center: Point<3>,
@@ -17,6 +21,12 @@ pub struct Circle {
     /// circumference. The point on the circumference that it points to defines
     /// the origin of the circle's 1-dimensional curve coordinate system.
     pub radius: Vector<3>,
+
+    /// The normal of the plane that the circle is in
+    ///
+    /// Must be a unit vector that is perpendicular to `radius`. The circle goes
+    /// counter-clockwise around it, from `radius` towards `normal × radius`.
+    pub normal: Vector<3>,
 }
 
 impl Circle {
@@ -25,9 +35,50 @@ impl Circle {
         Self {
             center: transform.transform_point(&self.center),
             radius: transform.transform_vector(&self.radius),
+            normal: transform.transform_vector(&self.normal),
         }
     }
 
+    /// Scale the circle uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the circle through the origin. This
+    /// reverses `radius` and `normal × radius`, so the normal stays the same.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            center: self.center * factor,
+            radius: self.radius * factor,
+            normal: self.normal,
+        }
+    }
+
+    /// Convert a point in curve coordinates to model coordinates
+    ///
+    /// The curve coordinate is the angle from the circle's origin (as defined
+    /// by `radius`), in radians.
+    pub fn point_curve_to_model(&self, point: f64) -> Point<3> {
+        let (sin, cos) = point.sin_cos();
+        self.center + self.radius * cos + self.binormal() * sin
+    }
+
+    /// Convert a point in model coordinates to curve coordinates
+    ///
+    /// Projects the point onto the circle, if it is not on the circle already.
+    pub fn point_model_to_curve(&self, point: Point<3>) -> f64 {
+        let v = point - self.center;
+
+        let angle = v.dot(&self.binormal()).atan2(v.dot(&self.radius));
+        angle.rem_euclid(2. * PI)
+    }
+
+    /// Compute the vector that points to curve coordinate π/2
+    ///
+    /// Together with `radius` and `normal`, it completes the circle's local
+    /// frame.
+    pub fn binormal(&self) -> Vector<3> {
+        self.normal.cross(&self.radius)
+    }
+
     pub fn approx(&self, tolerance: f64, out: &mut Vec<Point<3>>) {
         let radius = self.radius.magnitude();
 
@@ -41,19 +92,57 @@ impl Circle {
 
         for i in 0..n {
             let angle = 2. * PI / n as f64 * i as f64;
+            out.push(self.point_curve_to_model(angle));
+        }
+    }
+
+    /// Approximate the arc from `a` to `b`, in the direction of the circle
+    ///
+    /// `a` and `b` must be on the circle. They are part of the approximation,
+    /// exactly as provided. If they are identical, the whole circle is
+    /// approximated, starting and ending at that point.
+    pub fn approx_between(
+        &self,
+        tolerance: f64,
+        [a, b]: [Point<3>; 2],
+        out: &mut Vec<Point<3>>,
+    ) {
+        let start = self.point_model_to_curve(a);
+        let angle = self.arc_angle([a, b]);
 
-            let (sin, cos) = angle.sin_cos();
+        let n = Circle::number_of_vertices(tolerance, self.radius.magnitude());
+        let n = (n as f64 * angle / (2. * PI)).ceil().max(1.) as u64;
 
-            let x = cos * radius;
-            let y = sin * radius;
+        out.push(a);
+        for i in 1..n {
+            out.push(
+                self.point_curve_to_model(start + angle / n as f64 * i as f64),
+            );
+        }
+        out.push(b);
+    }
 
-            let point = self.center + vector![x, y, 0.];
+    /// Compute the angle of the arc from `a` to `b`, in radians
+    ///
+    /// The arc goes into the direction of the circle. If `a` and `b` are
+    /// identical, it covers the whole circle.
+    pub fn arc_angle(&self, [a, b]: [Point<3>; 2]) -> f64 {
+        let angle = (self.point_model_to_curve(b)
+            - self.point_model_to_curve(a))
+        .rem_euclid(2. * PI);
 
-            out.push(point);
+        if angle == 0. {
+            2. * PI
+        } else {
+            angle
         }
     }
 
-    fn number_of_vertices(tolerance: f64, radius: f64) -> u64 {
+    /// Compute the number of vertices required to approximate a circle
+    ///
+    /// Returns the number of vertices of the regular polygon that deviates from
+    /// a circle with the given radius by no more than `tolerance`.
+    pub fn number_of_vertices(tolerance: f64, radius: f64) -> u64 {
         assert!(tolerance > 0.);
         if tolerance > radius / 2. {
             3
@@ -67,8 +156,89 @@ impl Circle {
 mod tests {
     use std::f64::consts::PI;
 
+    use approx::assert_abs_diff_eq;
+    use nalgebra::{point, vector};
+
+    use crate::math::Vector;
+
     use super::Circle;
 
+    #[test]
+    fn test_arc_angle() {
+        let circle = Circle {
+            center: point![1., 1., 0.],
+            radius: vector![1., 0., 0.],
+            normal: Vector::z(),
+        };
+
+        let a = point![2., 1., 0.];
+        let b = point![1., 2., 0.];
+
+        assert_abs_diff_eq!(circle.arc_angle([a, b]), PI / 2.);
+        assert_abs_diff_eq!(circle.arc_angle([b, a]), PI * 3. / 2.);
+        assert_abs_diff_eq!(circle.arc_angle([a, a]), PI * 2.);
+    }
+
+    #[test]
+    fn test_approx_between() {
+        let circle = Circle {
+            center: point![0., 0., 0.],
+            radius: vector![0., 1., 0.],
+            normal: Vector::z(),
+        };
+
+        let a = point![0., 1., 0.];
+        let b = point![0., -1., 0.];
+
+        let mut points = Vec::new();
+        circle.approx_between(0.01, [a, b], &mut points);
+
+        // The vertices must be preserved exactly.
+        assert_eq!(points.first(), Some(&a));
+        assert_eq!(points.last(), Some(&b));
+
+        // Counter-clockwise from the top to the bottom means going through the
+        // left half of the circle.
+        for point in &points {
+            assert_abs_diff_eq!(point.coords.magnitude(), 1., epsilon = 1e-12);
+            assert!(point.x <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_approx_in_local_frame() {
+        // A circle in the y-z plane, around the x-axis.
+        let circle = Circle {
+            center: point![1., 2., 3.],
+            radius: vector![0., 0., 2.],
+            normal: Vector::x(),
+        };
+
+        let mut points = Vec::new();
+        circle.approx(0.01, &mut points);
+
+        assert_eq!(points.first(), Some(&point![1., 2., 5.]));
+        for &point in &points {
+            let v = point - circle.center;
+            assert_abs_diff_eq!(v.x, 0., epsilon = 1e-12);
+            assert_abs_diff_eq!(v.magnitude(), 2., epsilon = 1e-12);
+
+            let t = circle.point_model_to_curve(point);
+            assert_abs_diff_eq!(
+                circle.point_curve_to_model(t),
+                point,
+                epsilon = 1e-12
+            );
+        }
+
+        // The circle goes counter-clockwise around its normal.
+        assert_abs_diff_eq!(
+            circle.point_curve_to_model(PI / 2.),
+            point![1., 0., 3.],
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_vertices_counting() {
         verify_result(50., 100., 3);
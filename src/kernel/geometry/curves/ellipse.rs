@@ -0,0 +1,256 @@
+use std::f64::consts::PI;
+
+use parry3d_f64::math::Isometry;
+
+use crate::math::{Point, Vector};
+
+use super::Circle;
+
+/// An ellipse
+///
+/// The ellipse is defined by its center and two vectors, `a` and `b`, that
+/// point from the center to the circumference. A point on the ellipse is given
+/// by `center + a * cos(t) + b * sin(t)`, where `t` is the point's curve
+/// coordinate. The ellipse goes from `a` towards `b`.
+///
+/// If `a` and `b` are perpendicular, they are the semi-axes of the ellipse.
+/// They don't have to be, however. That way, the result of any affine
+/// transformation of an ellipse is still an ellipse, without having to compute
+/// its new semi-axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse {
+    /// The center point of the ellipse
+    pub center: Point<3>,
+
+    /// The vector that points to the origin of the curve coordinate system
+    pub a: Vector<3>,
+
+    /// The vector that points to curve coordinate π/2
+    pub b: Vector<3>,
+}
+
+impl Ellipse {
+    /// Create an ellipse that is identical to a circle
+    pub fn from_circle(circle: Circle) -> Self {
+        Self {
+            center: circle.center,
+            a: circle.radius,
+            b: circle.binormal(),
+        }
+    }
+
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        Self {
+            center: transform.transform_point(&self.center),
+            a: transform.transform_vector(&self.a),
+            b: transform.transform_vector(&self.b),
+        }
+    }
+
+    /// Scale the ellipse uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the ellipse through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            center: self.center * factor,
+            a: self.a * factor,
+            b: self.b * factor,
+        }
+    }
+
+    /// Convert a point in curve coordinates to model coordinates
+    ///
+    /// The curve coordinate is the angle that is passed to the sine and cosine
+    /// in the definition of the ellipse, in radians.
+    pub fn point_curve_to_model(&self, point: f64) -> Point<3> {
+        let (sin, cos) = point.sin_cos();
+        self.center + self.a * cos + self.b * sin
+    }
+
+    /// Convert a point in model coordinates to curve coordinates
+    ///
+    /// If the point is not on the ellipse, the point on the ellipse that is in
+    /// the same direction from the center is used instead.
+    pub fn point_model_to_curve(&self, point: Point<3>) -> f64 {
+        let v = point - self.center;
+
+        // Express `v` in terms of `a` and `b`. The point is expected to be in
+        // the plane of the ellipse, but it doesn't hurt to use the least
+        // squares solution, in case it isn't.
+        let [aa, ab, bb] = [
+            self.a.dot(&self.a),
+            self.a.dot(&self.b),
+            self.b.dot(&self.b),
+        ];
+        let [av, bv] = [self.a.dot(&v), self.b.dot(&v)];
+
+        let det = aa * bb - ab * ab;
+        let cos = (bb * av - ab * bv) / det;
+        let sin = (aa * bv - ab * av) / det;
+
+        sin.atan2(cos).rem_euclid(2. * PI)
+    }
+
+    pub fn approx(&self, tolerance: f64, out: &mut Vec<Point<3>>) {
+        let n = self.number_of_vertices(tolerance);
+
+        for i in 0..n {
+            let angle = 2. * PI / n as f64 * i as f64;
+            out.push(self.point_curve_to_model(angle));
+        }
+    }
+
+    /// Approximate the arc from `a` to `b`, in the direction of the ellipse
+    ///
+    /// `a` and `b` must be on the ellipse. They are part of the approximation,
+    /// exactly as provided. If they are identical, the whole ellipse is
+    /// approximated, starting and ending at that point.
+    pub fn approx_between(
+        &self,
+        tolerance: f64,
+        [a, b]: [Point<3>; 2],
+        out: &mut Vec<Point<3>>,
+    ) {
+        let start = self.point_model_to_curve(a);
+        let angle = self.arc_angle([a, b]);
+
+        let n = self.number_of_vertices(tolerance);
+        let n = (n as f64 * angle / (2. * PI)).ceil().max(1.) as u64;
+
+        out.push(a);
+        for i in 1..n {
+            out.push(
+                self.point_curve_to_model(start + angle / n as f64 * i as f64),
+            );
+        }
+        out.push(b);
+    }
+
+    /// Compute the angle of the arc from `a` to `b`, in curve coordinates
+    ///
+    /// The arc goes into the direction of the ellipse. If `a` and `b` are
+    /// identical, it covers the whole ellipse.
+    pub fn arc_angle(&self, [a, b]: [Point<3>; 2]) -> f64 {
+        let angle = (self.point_model_to_curve(b)
+            - self.point_model_to_curve(a))
+        .rem_euclid(2. * PI);
+
+        if angle == 0. {
+            2. * PI
+        } else {
+            angle
+        }
+    }
+
+    /// Compute the number of vertices required to approximate the ellipse
+    ///
+    /// The vertices are evenly spaced in curve coordinates. The ellipse is an
+    /// affine transformation of a circle, and so is its approximation. The
+    /// distance between the two is largest along the major axis, where it is
+    /// the same as for a circle with the radius of the semi-major axis.
+    pub fn number_of_vertices(&self, tolerance: f64) -> u64 {
+        Circle::number_of_vertices(tolerance, self.semi_major_axis())
+    }
+
+    /// Compute the length of the semi-major axis
+    pub fn semi_major_axis(&self) -> f64 {
+        // The semi-major axis is the largest singular value of the matrix with
+        // the columns `a` and `b`.
+        let [aa, ab, bb] = [
+            self.a.dot(&self.a),
+            self.a.dot(&self.b),
+            self.b.dot(&self.b),
+        ];
+        let discriminant = ((aa - bb).powi(2) + 4. * ab * ab).sqrt();
+
+        ((aa + bb + discriminant) / 2.).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use approx::assert_abs_diff_eq;
+    use nalgebra::{point, vector};
+
+    use super::Ellipse;
+
+    #[test]
+    fn test_point_model_to_curve() {
+        // The vectors don't need to be perpendicular.
+        let ellipse = Ellipse {
+            center: point![1., 2., 3.],
+            a: vector![2., 0., 0.],
+            b: vector![1., 1., 0.],
+        };
+
+        for i in 0..8 {
+            let t = PI / 4. * i as f64;
+            let point = ellipse.point_curve_to_model(t);
+
+            assert_abs_diff_eq!(
+                ellipse.point_model_to_curve(point),
+                t,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_approx_between() {
+        let ellipse = Ellipse {
+            center: point![0., 0., 0.],
+            a: vector![2., 0., 0.],
+            b: vector![0., 1., 0.],
+        };
+
+        let a = point![0., 1., 0.];
+        let b = point![0., -1., 0.];
+
+        let mut points = Vec::new();
+        ellipse.approx_between(0.01, [a, b], &mut points);
+
+        // The vertices must be preserved exactly.
+        assert_eq!(points.first(), Some(&a));
+        assert_eq!(points.last(), Some(&b));
+
+        // Going from the top to the bottom means going through the left half of
+        // the ellipse.
+        for point in &points {
+            let [x, y] = [point.x / 2., point.y];
+            assert_abs_diff_eq!(x * x + y * y, 1., epsilon = 1e-12);
+            assert!(point.x <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_approx_tolerance() {
+        let ellipse = Ellipse {
+            center: point![0., 0., 0.],
+            a: vector![3., 0., 0.],
+            b: vector![0., 1., 0.],
+        };
+        let tolerance = 0.01;
+
+        let mut points = Vec::new();
+        ellipse.approx(tolerance, &mut points);
+
+        // The segments of the approximation are furthest from the ellipse at
+        // their center.
+        let n = points.len();
+        for i in 0..n {
+            let [a, b] = [points[i], points[(i + 1) % n]];
+            let t = 2. * PI / n as f64 * (i as f64 + 0.5);
+
+            let distance = (ellipse.point_curve_to_model(t)
+                - (a + (b - a) / 2.))
+                .magnitude();
+            assert!(distance <= tolerance);
+        }
+
+        assert_eq!(ellipse.semi_major_axis(), 3.);
+    }
+}
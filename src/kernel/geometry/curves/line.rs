@@ -26,6 +26,30 @@ impl Line {
             b: transform.transform_point(&self.b),
         }
     }
+
+    /// Scale the line uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the line through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            a: self.a * factor,
+            b: self.b * factor,
+        }
+    }
+
+    /// Convert a point in curve coordinates to model coordinates
+    pub fn point_curve_to_model(&self, point: f64) -> Point<3> {
+        self.a + (self.b - self.a) * point
+    }
+
+    /// Convert a point in model coordinates to curve coordinates
+    ///
+    /// Projects the point onto the line, if it is not on the line already.
+    pub fn point_model_to_curve(&self, point: Point<3>) -> f64 {
+        let ab = self.b - self.a;
+        (point - self.a).dot(&ab) / ab.magnitude_squared()
+    }
 }
 
 impl AbsDiffEq for Line {
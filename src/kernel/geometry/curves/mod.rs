@@ -1,9 +1,11 @@
+mod bezier;
 mod circle;
+mod ellipse;
 mod line;
 
 use parry3d_f64::math::Isometry;
 
-pub use self::{circle::Circle, line::Line};
+pub use self::{bezier::Bezier, circle::Circle, ellipse::Ellipse, line::Line};
 
 use crate::math::Point;
 
@@ -19,11 +21,17 @@ use crate::math::Point;
 ///
 /// This distinction is not observed here, but moving things into that direction
 /// is the intention.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Curve {
+    /// A cubic Bézier curve
+    Bezier(Bezier),
+
     /// A circle
     Circle(Circle),
 
+    /// An ellipse
+    Ellipse(Ellipse),
+
     /// A line
     Line(Line),
 }
@@ -32,31 +40,92 @@ impl Curve {
     #[must_use]
     pub fn transform(self, transform: &Isometry<f64>) -> Self {
         match self {
+            Self::Bezier(bezier) => Self::Bezier(bezier.transform(transform)),
             Self::Circle(circle) => Self::Circle(circle.transform(transform)),
+            Self::Ellipse(ellipse) => {
+                Self::Ellipse(ellipse.transform(transform))
+            }
             Self::Line(line) => Self::Line(line.transform(transform)),
         }
     }
 
-    /// Compute an approximation of the curve
+    /// Scale the curve uniformly, relative to the origin
     ///
-    /// `tolerance` defines how far the approximation is allowed to deviate from
-    /// the actual edge.
+    /// A negative factor also mirrors the curve through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        match self {
+            Self::Bezier(bezier) => Self::Bezier(bezier.scale(factor)),
+            Self::Circle(circle) => Self::Circle(circle.scale(factor)),
+            Self::Ellipse(ellipse) => Self::Ellipse(ellipse.scale(factor)),
+            Self::Line(line) => Self::Line(line.scale(factor)),
+        }
+    }
+
+    /// Convert a point in curve coordinates to model coordinates
+    pub fn point_curve_to_model(&self, point: f64) -> Point<3> {
+        match self {
+            Self::Bezier(bezier) => bezier.point_curve_to_model(point),
+            Self::Circle(circle) => circle.point_curve_to_model(point),
+            Self::Ellipse(ellipse) => ellipse.point_curve_to_model(point),
+            Self::Line(line) => line.point_curve_to_model(point),
+        }
+    }
+
+    /// Convert a point in model coordinates to curve coordinates
     ///
-    /// # Implementation Note
+    /// Projects the point onto the curve, if it is not on the curve already.
+    pub fn point_model_to_curve(&self, point: Point<3>) -> f64 {
+        match self {
+            Self::Bezier(bezier) => bezier.point_model_to_curve(point),
+            Self::Circle(circle) => circle.point_model_to_curve(point),
+            Self::Ellipse(ellipse) => ellipse.point_model_to_curve(point),
+            Self::Line(line) => line.point_model_to_curve(point),
+        }
+    }
+
+    /// Compute an approximation of the curve
     ///
-    /// This only works as it is, because edges are severely limited and don't
-    /// define which section of the curve they inhabit. Once they do that, we
-    /// need an `approximate_between(a, b)` method instead, where `a` and `b`
-    /// are the vertices that bound the edge on the curve.
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual curve.
     ///
-    /// The `approx` methods of the curves then need to make sure to return
-    /// those exact vertices as part of the approximation, and not accidentally
-    /// compute some almost but not quite identical points for those vertices
-    /// instead.
+    /// Lines are approximated from `a` to `b`, Bézier curves from their first
+    /// to their last control point, circles and ellipses in their entirety.
     pub fn approx(&self, tolerance: f64, out: &mut Vec<Point<3>>) {
         match self {
+            Self::Bezier(bezier) => bezier.approx(tolerance, out),
             Self::Circle(circle) => circle.approx(tolerance, out),
+            Self::Ellipse(ellipse) => ellipse.approx(tolerance, out),
             Self::Line(Line { a, b }) => out.extend([*a, *b]),
         }
     }
+
+    /// Compute an approximation of the section of the curve between two points
+    ///
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual curve.
+    ///
+    /// The section goes from `a` to `b`, in the direction of the curve. Both
+    /// points must be on the curve, and are part of the approximation, exactly
+    /// as provided. This makes sure that the approximations of connected edges
+    /// connect exactly too.
+    pub fn approx_between(
+        &self,
+        tolerance: f64,
+        [a, b]: [Point<3>; 2],
+        out: &mut Vec<Point<3>>,
+    ) {
+        match self {
+            Self::Bezier(bezier) => {
+                bezier.approx_between(tolerance, [a, b], out)
+            }
+            Self::Circle(circle) => {
+                circle.approx_between(tolerance, [a, b], out)
+            }
+            Self::Ellipse(ellipse) => {
+                ellipse.approx_between(tolerance, [a, b], out)
+            }
+            Self::Line(_) => out.extend([a, b]),
+        }
+    }
 }
@@ -3,6 +3,6 @@ pub mod points;
 pub mod surfaces;
 
 pub use self::{
-    curves::{Circle, Curve, Line},
+    curves::{Bezier, Circle, Curve, Ellipse, Line},
     surfaces::Surface,
 };
@@ -0,0 +1,127 @@
+mod plane;
+mod revolved_curve;
+mod sphere;
+mod torus;
+
+use nalgebra::vector;
+use parry3d_f64::math::Isometry;
+
+use crate::math::Point;
+
+pub use self::{
+    plane::Plane, revolved_curve::RevolvedCurve, sphere::Sphere, torus::Torus,
+};
+
+use super::points::SurfacePoint;
+
+/// A two-dimensional shape
+#[derive(Clone, Debug, PartialEq)]
+pub enum Surface {
+    /// A plane
+    Plane(Plane),
+
+    /// A surface created by revolving a curve around an axis
+    Revolved(RevolvedCurve),
+
+    /// A sphere
+    Sphere(Sphere),
+
+    /// A torus
+    Torus(Torus),
+}
+
+impl Surface {
+    /// Construct a `Surface` that represents the x-y plane
+    pub fn x_y_plane() -> Self {
+        Self::Plane(Plane {
+            origin: Point::origin(),
+            u: vector![1., 0., 0.],
+            v: vector![0., 1., 0.],
+        })
+    }
+
+    /// Transform the surface
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        match self {
+            Self::Plane(plane) => Self::Plane(plane.transform(transform)),
+            Self::Revolved(surface) => {
+                Self::Revolved(surface.transform(transform))
+            }
+            Self::Sphere(sphere) => Self::Sphere(sphere.transform(transform)),
+            Self::Torus(torus) => Self::Torus(torus.transform(transform)),
+        }
+    }
+
+    /// Scale the surface uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the surface through the origin. The
+    /// normal of the scaled surface points away from the same side as before.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        match self {
+            Self::Plane(plane) => Self::Plane(plane.scale(factor)),
+            Self::Revolved(surface) => Self::Revolved(surface.scale(factor)),
+            Self::Sphere(sphere) => Self::Sphere(sphere.scale(factor)),
+            Self::Torus(torus) => Self::Torus(torus.scale(factor)),
+        }
+    }
+
+    /// Reverse the orientation of the surface
+    ///
+    /// This flips the surface's normal, which means that faces on the reversed
+    /// surface face the other way.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Plane(plane) => Self::Plane(Plane {
+                v: -plane.v,
+                ..plane
+            }),
+            Self::Revolved(surface) => Self::Revolved(RevolvedCurve {
+                reverse: !surface.reverse,
+                ..surface
+            }),
+            Self::Sphere(sphere) => Self::Sphere(Sphere {
+                reverse: !sphere.reverse,
+                ..sphere
+            }),
+            Self::Torus(torus) => Self::Torus(Torus {
+                reverse: !torus.reverse,
+                ..torus
+            }),
+        }
+    }
+
+    /// Convert a point in model coordinates to surface coordinates
+    ///
+    /// Returns an error, if the provided point is not in the surface.
+    pub fn point_model_to_surface(
+        &self,
+        point_3d: Point<3>,
+    ) -> Result<SurfacePoint, ()> {
+        let point_2d = match self {
+            Self::Plane(plane) => plane.point_model_to_surface(point_3d)?,
+            Self::Revolved(surface) => {
+                surface.point_model_to_surface(point_3d)?
+            }
+            Self::Sphere(sphere) => sphere.point_model_to_surface(point_3d)?,
+            Self::Torus(torus) => torus.point_model_to_surface(point_3d)?,
+        };
+
+        Ok(SurfacePoint {
+            value: point_2d,
+            from: point_3d,
+        })
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_surface_to_model(&self, point: Point<2>) -> Point<3> {
+        match self {
+            Self::Plane(plane) => plane.point_surface_to_model(point),
+            Self::Revolved(surface) => surface.point_surface_to_model(point),
+            Self::Sphere(sphere) => sphere.point_surface_to_model(point),
+            Self::Torus(torus) => torus.point_surface_to_model(point),
+        }
+    }
+}
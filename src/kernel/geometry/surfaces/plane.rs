@@ -1,72 +1,13 @@
-use nalgebra::{point, vector};
+use nalgebra::point;
 use parry3d_f64::math::Isometry;
 
 use crate::math::{Point, Vector};
 
-use super::points::SurfacePoint;
-
-/// A two-dimensional shape
-#[derive(Clone, Debug, PartialEq)]
-pub enum Surface {
-    /// A plane
-    Plane(Plane),
-}
-
-impl Surface {
-    /// Construct a `Surface` that represents the x-y plane
-    pub fn x_y_plane() -> Self {
-        Self::Plane(Plane {
-            origin: Point::origin(),
-            u: vector![1., 0., 0.],
-            v: vector![0., 1., 0.],
-        })
-    }
-
-    /// Transform the surface
-    #[must_use]
-    pub fn transform(self, transform: &Isometry<f64>) -> Self {
-        match self {
-            Self::Plane(plane) => Self::Plane(plane.transform(transform)),
-        }
-    }
-
-    /// Convert a point in model coordinates to surface coordinates
-    ///
-    /// Returns an error, if the provided point is not in the surface.
-    pub fn point_model_to_surface(
-        &self,
-        point_3d: Point<3>,
-    ) -> Result<SurfacePoint, ()> {
-        let point_2d = match self {
-            Self::Plane(plane) => plane.point_model_to_surface(point_3d)?,
-        };
-
-        Ok(SurfacePoint {
-            value: point_2d,
-            from: point_3d,
-        })
-    }
-
-    /// Convert a point in surface coordinates to model coordinates
-    pub fn point_surface_to_model(&self, point: Point<2>) -> Point<3> {
-        match self {
-            Self::Plane(plane) => plane.point_surface_to_model(point),
-        }
-    }
-
-    /// Convert a vector in surface coordinates to model coordinates
-    pub fn vector_surface_to_model(&self, vector: Vector<2>) -> Vector<3> {
-        match self {
-            Self::Plane(plane) => plane.vector_surface_to_model(vector),
-        }
-    }
-}
-
 /// A plane
 ///
-/// For the time being, only planes parallel to the x-y plane are supported.
-/// Making this code more flexible to support all planes is subject of an
-/// ongoing effort.
+/// The plane can have any orientation. It is defined by its origin and two
+/// vectors within the plane, `u` and `v`. The normal of the plane, which
+/// defines the direction that faces on the plane point to, is `u × v`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Plane {
     /// The origin point of the plane
@@ -78,19 +19,16 @@ pub struct Plane {
     /// First direction that defines the plane orientation
     ///
     /// It might be most reasonable, if this were a unit vector that is
-    /// orthogonal to `v`. As an experiment, this isn't required right now,
-    /// to allow for the definition of interesting coordinate systems. It's
-    /// unclear how well all algorithms will handle those though.
+    /// orthogonal to `v`. This isn't required, to allow for the definition of
+    /// interesting coordinate systems. Conversions between model and surface
+    /// coordinates are exact either way.
     ///
     /// Must not be parallel to `v`.
     pub u: Vector<3>,
 
     /// Second direction that defines the plane orientation
     ///
-    /// It might be most reasonable, if this were a unit vector that is
-    /// orthogonal to `u`. As an experiment, this isn't required right now,
-    /// to allow for the definition of interesting coordinate systems. It's
-    /// unclear how well all algorithms will handle those though.
+    /// See `u`.
     ///
     /// Must not be parallel to `u`.
     pub v: Vector<3>,
@@ -107,30 +45,58 @@ impl Plane {
         }
     }
 
+    /// Scale the plane uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the plane through the origin. Its normal
+    /// is flipped along with it, to keep pointing away from the same side.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            origin: self.origin * factor,
+            u: self.u * factor,
+            v: self.v * factor.abs(),
+        }
+    }
+
+    /// Compute the normal of the plane
+    ///
+    /// The normal is a unit vector.
+    pub fn normal(&self) -> Vector<3> {
+        self.u.cross(&self.v).normalize()
+    }
+
     /// Convert a point in model coordinates to surface coordinates
+    ///
+    /// This is the inverse of [`Plane::point_surface_to_model`]. Returns an
+    /// error, if the point is not in the plane.
     pub fn point_model_to_surface(
         &self,
         point: Point<3>,
     ) -> Result<Point<2>, ()> {
-        let normal = self.u.cross(&self.v);
-
-        let a = normal.x;
-        let b = normal.y;
-        let c = normal.z;
-        let d = -(a * self.origin.x + b * self.origin.y + c * self.origin.z);
-
-        let distance = (a * point.x + b * point.y + c * point.z + d).abs()
-            / (a * a + b * b + c * c).sqrt();
-
-        if distance > <f64 as approx::AbsDiffEq>::default_epsilon() {
+        let p = point - self.origin;
+        let distance = p.dot(&self.normal()).abs();
+
+        // Points that have been transformed together with the plane are
+        // subject to floating point inaccuracies that grow with their distance
+        // from the origin. The allowed distance needs to reflect that.
+        let scale = point.coords.magnitude().max(1.);
+        let epsilon = <f64 as approx::AbsDiffEq>::default_epsilon() * 16.;
+        if distance > epsilon * scale {
             return Err(());
         }
 
-        let p = point - self.origin;
+        // Express `p` in terms of `u` and `v`. They don't need to be
+        // perpendicular or normalized, so a simple projection won't do.
+        let [uu, uv, vv] = [
+            self.u.dot(&self.u),
+            self.u.dot(&self.v),
+            self.v.dot(&self.v),
+        ];
+        let [up, vp] = [self.u.dot(&p), self.v.dot(&p)];
 
-        // scalar projection
-        let s = p.dot(&self.u.normalize());
-        let t = p.dot(&self.v.normalize());
+        let det = uu * vv - uv * uv;
+        let s = (vv * up - uv * vp) / det;
+        let t = (uu * vp - uv * up) / det;
 
         Ok(point![s, t])
     }
@@ -243,6 +209,69 @@ mod tests {
         assert_eq!(plane.point_model_to_surface(invalid_model_point), Err(()));
     }
 
+    #[test]
+    fn test_point_conversion_with_arbitrary_orientation() {
+        // The vectors are neither normalized, nor perpendicular.
+        let plane = Plane {
+            origin: point![1., 2., 3.],
+            u: vector![2., 0., 1.],
+            v: vector![1., 1., -1.],
+        };
+
+        let point_2d = point![3., -2.];
+        let point_3d = plane.point_surface_to_model(point_2d);
+
+        assert_relative_eq!(
+            plane.point_model_to_surface(point_3d).unwrap(),
+            point_2d,
+            epsilon = 1e-12,
+        );
+        assert_eq!(
+            plane.point_model_to_surface(point_3d + plane.normal()),
+            Err(()),
+        );
+    }
+
+    #[test]
+    fn test_point_conversion_after_transform() {
+        let plane = Plane {
+            origin: point![0., 0., 0.],
+            u: vector![1., 0., 0.],
+            v: vector![0., 1., 0.],
+        };
+
+        // Shapes that are moved far away from the origin, like the caps of a
+        // sweep along a path, are transformed together with their planes.
+        // Their points end up off the transformed plane by several times
+        // `f64::EPSILON`, which must still be accepted.
+        let transform = Isometry::from_parts(
+            Translation::from([100., -50., 30.]),
+            UnitQuaternion::from_axis_angle(&Vector::y_axis(), 0.7)
+                * UnitQuaternion::from_axis_angle(&Vector::x_axis(), 1.3),
+        );
+        let transformed = plane.clone().transform(&transform);
+
+        for i in 0..20 {
+            for j in 0..20 {
+                let point_2d = point![i as f64 * 0.37, j as f64 * -0.53];
+                let point_3d = transform
+                    .transform_point(&plane.point_surface_to_model(point_2d));
+
+                assert_relative_eq!(
+                    transformed.point_model_to_surface(point_3d).unwrap(),
+                    point_2d,
+                    epsilon = 1e-9,
+                );
+                assert_eq!(
+                    transformed.point_model_to_surface(
+                        point_3d + transformed.normal() * 1e-9
+                    ),
+                    Err(()),
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_surface_to_model_point_conversion() {
         let plane = Plane {
@@ -0,0 +1,372 @@
+use std::f64::consts::PI;
+
+use nalgebra::{point, UnitQuaternion};
+use parry3d_f64::{math::Isometry, shape::Triangle};
+
+use crate::{
+    kernel::geometry::{Circle, Curve, Line},
+    math::{Point, Vector},
+};
+
+/// A surface that is created by revolving a curve around an axis
+///
+/// The surface coordinates are the angle of the revolution (in radians) and the
+/// curve coordinate of the revolved curve, in that order.
+///
+/// # Limitations
+///
+/// Faces on this surface are assumed to cover the whole surface, from angle
+/// `0` to `angle`, along the whole curve. Lines are revolved from curve
+/// coordinate `0` to `1`, circles in their entirety.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevolvedCurve {
+    /// The curve that is being revolved
+    ///
+    /// The curve should be in a plane that contains the axis, and must not
+    /// cross the axis.
+    pub curve: Curve,
+
+    /// A point on the axis of the revolution
+    pub origin: Point<3>,
+
+    /// The direction of the axis of the revolution
+    ///
+    /// The revolution follows the right-hand rule: Looking into the direction
+    /// of the axis, the curve is revolved clockwise.
+    ///
+    /// Must be a unit vector.
+    pub axis: Vector<3>,
+
+    /// The angle of the revolution, in radians
+    ///
+    /// Must be positive, and no larger than a full revolution.
+    pub angle: f64,
+
+    /// Indicates whether the orientation of the surface is reversed
+    ///
+    /// By default, the surface's normal is the cross product of the direction
+    /// of the revolution and the direction of the curve.
+    pub reverse: bool,
+}
+
+impl RevolvedCurve {
+    /// Indicates whether this is a full revolution
+    pub fn is_full(&self) -> bool {
+        self.angle >= 2. * PI
+    }
+
+    /// Transform the surface
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        Self {
+            curve: self.curve.transform(transform),
+            origin: transform.transform_point(&self.origin),
+            axis: transform.transform_vector(&self.axis),
+            ..self
+        }
+    }
+
+    /// Scale the surface uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the surface through the origin. Its
+    /// normal is flipped along with it, to keep pointing away from the same
+    /// side.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            curve: self.curve.scale(factor),
+            origin: self.origin * factor,
+            reverse: self.reverse != (factor < 0.),
+            ..self
+        }
+    }
+
+    /// Convert a point in model coordinates to surface coordinates
+    pub fn point_model_to_surface(
+        &self,
+        point: Point<3>,
+    ) -> Result<Point<2>, ()> {
+        // The direction from the axis to the curve, at angle `0`. The curve
+        // lies in a plane with the axis, so any point on it can be used.
+        let reference = self.radial(self.curve.point_curve_to_model(0.));
+        let reference = match reference.try_normalize(f64::EPSILON) {
+            Some(reference) => reference,
+            // The curve starts on the axis. Try its other end.
+            None => self.radial(self.curve.point_curve_to_model(0.5)),
+        }
+        .normalize();
+
+        let radial = self.radial(point);
+        let angle = radial
+            .dot(&self.axis.cross(&reference))
+            .atan2(radial.dot(&reference))
+            .rem_euclid(2. * PI);
+
+        let point_on_curve = self.rotation(-angle).transform_point(&point);
+        let t = self.curve.point_model_to_curve(point_on_curve);
+
+        let distance =
+            (self.curve.point_curve_to_model(t) - point_on_curve).magnitude();
+        if distance > <f64 as approx::AbsDiffEq>::default_epsilon() * 8. {
+            return Err(());
+        }
+
+        Ok(point![angle, t])
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_surface_to_model(&self, point: Point<2>) -> Point<3> {
+        let point_on_curve = self.curve.point_curve_to_model(point.y);
+        self.rotation(point.x).transform_point(&point_on_curve)
+    }
+
+    /// Compute a triangle mesh that approximates the surface
+    ///
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual surface.
+    ///
+    /// The approximation consists of rows of points that are the result of
+    /// revolving the points of the curve's approximation. Each row is
+    /// approximated in the same way as a circle of the same radius would be.
+    /// This makes sure that the approximations of surfaces that share a curve
+    /// (and, therefore, the rows of points where they meet) fit together.
+    pub fn approx(&self, tolerance: f64, out: &mut Vec<Triangle>) {
+        let mut curve = Vec::new();
+        self.curve.approx(tolerance, &mut curve);
+
+        if let Curve::Circle(_) | Curve::Ellipse(_) = self.curve {
+            // Circles and ellipses connect to themselves, which needs to be
+            // reflected in the approximation.
+            if let Some(&first) = curve.first() {
+                curve.push(first);
+            }
+        }
+
+        let rows: Vec<_> = curve
+            .into_iter()
+            .map(|point| self.row(point, tolerance))
+            .collect();
+
+        for window in rows.windows(2) {
+            for [a, b, c] in zip_rows(&window[0], &window[1]) {
+                let triangle = if self.reverse {
+                    Triangle::new(a, c, b)
+                } else {
+                    Triangle::new(a, b, c)
+                };
+
+                // Rows that consist of a single point (because they are on the
+                // axis) result in degenerate triangles. Those can be ignored.
+                if triangle.area() > 0. {
+                    out.push(triangle);
+                }
+            }
+        }
+    }
+
+    /// Compute the row of points that results from revolving a single point
+    fn row(&self, point: Point<3>, tolerance: f64) -> Vec<Point<3>> {
+        let radius = self.radial(point).magnitude();
+        if radius <= tolerance {
+            // The point is on the axis (or close enough, given the tolerance).
+            return vec![point];
+        }
+
+        let n = Circle::number_of_vertices(tolerance, radius);
+
+        if self.is_full() {
+            let mut row: Vec<_> = (0..n)
+                .map(|i| self.rotated(point, 2. * PI / n as f64 * i as f64))
+                .collect();
+
+            // Close the row with the exact first point, to avoid any gaps due
+            // to floating point inaccuracies.
+            row.push(point);
+
+            row
+        } else {
+            let n = (n as f64 * self.angle / (2. * PI)).ceil().max(1.) as u64;
+
+            (0..=n)
+                .map(|i| self.rotated(point, self.angle / n as f64 * i as f64))
+                .collect()
+        }
+    }
+
+    /// Rotate a point around the axis
+    fn rotated(&self, point: Point<3>, angle: f64) -> Point<3> {
+        if angle == 0. {
+            // Make sure the original point is preserved exactly.
+            return point;
+        }
+
+        self.rotation(angle).transform_point(&point)
+    }
+
+    fn rotation(&self, angle: f64) -> Isometry<f64> {
+        let origin = self.origin.coords;
+        let rotation = UnitQuaternion::from_scaled_axis(self.axis * angle);
+
+        Isometry::translation(origin.x, origin.y, origin.z)
+            * Isometry::from_parts(Default::default(), rotation)
+            * Isometry::translation(-origin.x, -origin.y, -origin.z)
+    }
+
+    /// Compute the vector from the axis to a point, perpendicular to the axis
+    fn radial(&self, point: Point<3>) -> Vector<3> {
+        let v = point - self.origin;
+        v - self.axis * self.axis.dot(&v)
+    }
+}
+
+/// Approximate the surface created by fully revolving a polyline
+///
+/// Each segment of the polyline is revolved separately, as a line. The rows of
+/// points created from the points that neighboring segments share are
+/// identical, so the approximations of the segments fit together.
+pub(super) fn revolve_polyline(
+    polyline: &[Point<3>],
+    origin: Point<3>,
+    axis: Vector<3>,
+    reverse: bool,
+    tolerance: f64,
+    out: &mut Vec<Triangle>,
+) {
+    for segment in polyline.windows(2) {
+        let surface = RevolvedCurve {
+            curve: Curve::Line(Line {
+                a: segment[0],
+                b: segment[1],
+            }),
+            origin,
+            axis,
+            angle: 2. * PI,
+            reverse,
+        };
+
+        surface.approx(tolerance, out);
+    }
+}
+
+/// Connect two rows of points with triangles
+///
+/// Both rows are expected to span the same angle of the revolution, but might
+/// consist of different numbers of points. Rows that consist of only a single
+/// point are supported.
+fn zip_rows(a: &[Point<3>], b: &[Point<3>]) -> Vec<[Point<3>; 3]> {
+    let position = |row: &[Point<3>], i: usize| {
+        if row.len() > 1 {
+            i as f64 / (row.len() - 1) as f64
+        } else {
+            0.
+        }
+    };
+
+    let mut triangles = Vec::new();
+
+    let (mut i, mut j) = (0, 0);
+    loop {
+        let can_advance_a = i + 1 < a.len();
+        let can_advance_b = j + 1 < b.len();
+
+        let advance_a = match (can_advance_a, can_advance_b) {
+            (false, false) => break,
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => position(a, i + 1) <= position(b, j + 1),
+        };
+
+        if advance_a {
+            triangles.push([a[i], a[i + 1], b[j]]);
+            i += 1;
+        } else {
+            triangles.push([a[i], b[j + 1], b[j]]);
+            j += 1;
+        }
+    }
+
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use approx::assert_abs_diff_eq;
+    use nalgebra::{point, vector};
+
+    use crate::kernel::geometry::{Curve, Line};
+
+    use super::RevolvedCurve;
+
+    #[test]
+    fn test_surface_model_point_conversion() {
+        let surface = cylinder(2. * PI);
+
+        let point = point![PI / 2., 0.5];
+        let model = surface.point_surface_to_model(point);
+
+        assert_abs_diff_eq!(model, point![0., 0.5, -1.], epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            surface.point_model_to_surface(model).unwrap(),
+            point,
+            epsilon = 1e-12,
+        );
+        assert_eq!(
+            surface.point_model_to_surface(point![0., 0.5, 0.]),
+            Err(())
+        );
+    }
+
+    #[test]
+    fn test_approx_full_revolution() {
+        let surface = cylinder(2. * PI);
+
+        let mut triangles = Vec::new();
+        surface.approx(0.01, &mut triangles);
+
+        // All normals must point away from the axis.
+        for triangle in &triangles {
+            let normal = triangle.normal().unwrap();
+            let center = triangle.center();
+            assert!(normal.dot(&vector![center.x, 0., center.z]) > 0.);
+        }
+
+        // The area of the approximation is a bit smaller than the actual area,
+        // as the approximation is inscribed.
+        let area: f64 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert!(area < 2. * PI && area > 2. * PI * 0.99);
+    }
+
+    #[test]
+    fn test_approx_partial_revolution() {
+        let surface = cylinder(PI);
+
+        let mut triangles = Vec::new();
+        surface.approx(0.01, &mut triangles);
+
+        // The cylinder is revolved in negative z direction.
+        for triangle in &triangles {
+            for vertex in triangle.vertices() {
+                assert!(vertex.z < 1e-12);
+            }
+        }
+
+        let area: f64 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert!(area < PI && area > PI * 0.99);
+    }
+
+    /// A cylinder with radius 1 and height 1 around the y axis
+    fn cylinder(angle: f64) -> RevolvedCurve {
+        RevolvedCurve {
+            curve: Curve::Line(Line {
+                a: point![1., 0., 0.],
+                b: point![1., 1., 0.],
+            }),
+            origin: point![0., 0., 0.],
+            axis: vector![0., 1., 0.],
+            angle,
+            reverse: false,
+        }
+    }
+}
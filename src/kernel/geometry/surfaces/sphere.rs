@@ -0,0 +1,190 @@
+use std::f64::consts::PI;
+
+use nalgebra::point;
+use parry3d_f64::{math::Isometry, shape::Triangle};
+
+use crate::{
+    kernel::geometry::Circle,
+    math::{Point, Vector},
+};
+
+use super::revolved_curve::revolve_polyline;
+
+/// A sphere
+///
+/// The surface coordinates are the longitude and the latitude (both in
+/// radians), in that order. The longitude is measured around the z-axis,
+/// starting at the x-axis. The latitude is `0` at the equator, `PI / 2` at the
+/// north pole (in positive z direction).
+///
+/// # Limitations
+///
+/// Faces on this surface are assumed to cover the whole surface.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sphere {
+    /// The center of the sphere
+    pub center: Point<3>,
+
+    /// The radius of the sphere
+    pub radius: f64,
+
+    /// Indicates whether the orientation of the surface is reversed
+    ///
+    /// By default, the surface's normal points outward.
+    pub reverse: bool,
+}
+
+impl Sphere {
+    /// Transform the surface
+    ///
+    /// The surface coordinates of a sphere are always aligned with the model
+    /// coordinate axes, so only the center is affected by the transformation.
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        Self {
+            center: transform.transform_point(&self.center),
+            ..self
+        }
+    }
+
+    /// Scale the surface uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the surface through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            center: self.center * factor,
+            radius: self.radius * factor.abs(),
+            ..self
+        }
+    }
+
+    /// Convert a point in model coordinates to surface coordinates
+    pub fn point_model_to_surface(
+        &self,
+        point: Point<3>,
+    ) -> Result<Point<2>, ()> {
+        let v = point - self.center;
+
+        let distance = (v.magnitude() - self.radius).abs();
+        if distance
+            > <f64 as approx::AbsDiffEq>::default_epsilon()
+                * 8.
+                * self.radius.max(1.)
+        {
+            return Err(());
+        }
+
+        let longitude = v.y.atan2(v.x).rem_euclid(2. * PI);
+        let latitude = (v.z / self.radius).clamp(-1., 1.).asin();
+
+        Ok(point![longitude, latitude])
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_surface_to_model(&self, point: Point<2>) -> Point<3> {
+        let (sin_lon, cos_lon) = point.x.sin_cos();
+        let (sin_lat, cos_lat) = point.y.sin_cos();
+
+        self.center
+            + Vector::from([cos_lat * cos_lon, cos_lat * sin_lon, sin_lat])
+                * self.radius
+    }
+
+    /// Compute a triangle mesh that approximates the surface
+    ///
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual surface.
+    ///
+    /// The sphere is approximated by revolving an approximated half circle
+    /// around the z-axis. Each of the resulting rows of points is approximated
+    /// in the same way as a circle of the same radius would be.
+    pub fn approx(&self, tolerance: f64, out: &mut Vec<Triangle>) {
+        let n = Circle::number_of_vertices(tolerance, self.radius);
+        let n = (n as f64 / 2.).ceil() as u64;
+
+        // The half circle goes from the south pole to the north pole, which
+        // results in outward-facing triangles.
+        let meridian: Vec<_> = (0..=n)
+            .map(|i| {
+                let latitude = PI / n as f64 * i as f64 - PI / 2.;
+                self.point_surface_to_model(point![0., latitude])
+            })
+            .collect();
+
+        revolve_polyline(
+            &meridian,
+            self.center,
+            Vector::z(),
+            self.reverse,
+            tolerance,
+            out,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use approx::assert_abs_diff_eq;
+    use nalgebra::point;
+
+    use super::Sphere;
+
+    #[test]
+    fn test_surface_model_point_conversion() {
+        let sphere = Sphere {
+            center: point![1., 2., 3.],
+            radius: 2.,
+            reverse: false,
+        };
+
+        let point = point![PI / 2., PI / 4.];
+        let model = sphere.point_surface_to_model(point);
+
+        assert_abs_diff_eq!(
+            model,
+            point![1., 2. + 2f64.sqrt(), 3. + 2f64.sqrt()],
+            epsilon = 1e-12,
+        );
+        assert_abs_diff_eq!(
+            sphere.point_model_to_surface(model).unwrap(),
+            point,
+            epsilon = 1e-12,
+        );
+        assert_eq!(sphere.point_model_to_surface(point![1., 2., 3.]), Err(()));
+    }
+
+    #[test]
+    fn test_approx() {
+        let sphere = Sphere {
+            center: point![0., 0., 0.],
+            radius: 1.,
+            reverse: false,
+        };
+
+        let mut triangles = Vec::new();
+        sphere.approx(0.01, &mut triangles);
+
+        // All normals must point outward, and all vertices must be on the
+        // sphere.
+        for triangle in &triangles {
+            let normal = triangle.normal().unwrap();
+            assert!(normal.dot(&triangle.center().coords) > 0.);
+
+            for vertex in triangle.vertices() {
+                assert_abs_diff_eq!(
+                    vertex.coords.magnitude(),
+                    1.,
+                    epsilon = 1e-12
+                );
+            }
+        }
+
+        // The approximation is inscribed, so its area is a bit smaller than
+        // the actual area.
+        let area: f64 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert!(area < 4. * PI && area > 4. * PI * 0.98);
+    }
+}
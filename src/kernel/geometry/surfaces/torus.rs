@@ -0,0 +1,221 @@
+use std::f64::consts::PI;
+
+use nalgebra::point;
+use parry3d_f64::{math::Isometry, shape::Triangle};
+
+use crate::{
+    kernel::geometry::Circle,
+    math::{Point, Vector},
+};
+
+use super::revolved_curve::revolve_polyline;
+
+/// A torus
+///
+/// The surface coordinates are the angle around the axis of the torus and the
+/// angle around the tube (both in radians), in that order. The angle around
+/// the tube is `0` at the outermost points of the torus, and increases in the
+/// direction of the axis from there.
+///
+/// # Limitations
+///
+/// Faces on this surface are assumed to cover the whole surface.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Torus {
+    /// The center of the torus
+    pub center: Point<3>,
+
+    /// The direction of the axis of the torus
+    ///
+    /// Must be a unit vector.
+    pub axis: Vector<3>,
+
+    /// The distance from the center of the torus to the center of the tube
+    pub major_radius: f64,
+
+    /// The radius of the tube
+    ///
+    /// Must be smaller than `major_radius`.
+    pub minor_radius: f64,
+
+    /// Indicates whether the orientation of the surface is reversed
+    ///
+    /// By default, the surface's normal points outward.
+    pub reverse: bool,
+}
+
+impl Torus {
+    /// Transform the surface
+    #[must_use]
+    pub fn transform(self, transform: &Isometry<f64>) -> Self {
+        Self {
+            center: transform.transform_point(&self.center),
+            axis: transform.transform_vector(&self.axis),
+            ..self
+        }
+    }
+
+    /// Scale the surface uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the surface through the origin.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self {
+            center: self.center * factor,
+            major_radius: self.major_radius * factor.abs(),
+            minor_radius: self.minor_radius * factor.abs(),
+            ..self
+        }
+    }
+
+    /// Convert a point in model coordinates to surface coordinates
+    pub fn point_model_to_surface(
+        &self,
+        point: Point<3>,
+    ) -> Result<Point<2>, ()> {
+        let [u, v] = self.reference_directions();
+
+        let p = point - self.center;
+        let height = p.dot(&self.axis);
+        let radial = p - self.axis * height;
+
+        let tube = radial.magnitude() - self.major_radius;
+        let distance = (tube.hypot(height) - self.minor_radius).abs();
+        if distance
+            > <f64 as approx::AbsDiffEq>::default_epsilon()
+                * 8.
+                * self.major_radius.max(1.)
+        {
+            return Err(());
+        }
+
+        let angle = radial.dot(&v).atan2(radial.dot(&u)).rem_euclid(2. * PI);
+        let tube_angle = height.atan2(tube).rem_euclid(2. * PI);
+
+        Ok(point![angle, tube_angle])
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_surface_to_model(&self, point: Point<2>) -> Point<3> {
+        let [u, v] = self.reference_directions();
+
+        let (sin, cos) = point.x.sin_cos();
+        let (tube_sin, tube_cos) = point.y.sin_cos();
+
+        let radial = u * cos + v * sin;
+
+        self.center
+            + radial * (self.major_radius + self.minor_radius * tube_cos)
+            + self.axis * (self.minor_radius * tube_sin)
+    }
+
+    /// Compute a triangle mesh that approximates the surface
+    ///
+    /// `tolerance` defines how far the approximation is allowed to deviate from
+    /// the actual surface.
+    ///
+    /// The torus is approximated by revolving an approximated circle around its
+    /// axis. Each of the resulting rows of points is approximated in the same
+    /// way as a circle of the same radius would be.
+    pub fn approx(&self, tolerance: f64, out: &mut Vec<Triangle>) {
+        let n = Circle::number_of_vertices(tolerance, self.minor_radius);
+
+        // Going around the tube in the direction of the axis, starting at the
+        // outside, results in outward-facing triangles.
+        let mut tube: Vec<_> = (0..n)
+            .map(|i| {
+                let angle = 2. * PI / n as f64 * i as f64;
+                self.point_surface_to_model(point![0., angle])
+            })
+            .collect();
+
+        // Close the circle with the exact first point, to avoid any gaps due to
+        // floating point inaccuracies.
+        tube.push(tube[0]);
+
+        revolve_polyline(
+            &tube,
+            self.center,
+            self.axis,
+            self.reverse,
+            tolerance,
+            out,
+        );
+    }
+
+    /// Compute two directions that are orthogonal to the axis and each other
+    ///
+    /// Together with the axis, those form a right-handed coordinate system.
+    fn reference_directions(&self) -> [Vector<3>; 2] {
+        let other = if self.axis.x.abs() < 0.9 {
+            Vector::x()
+        } else {
+            Vector::y()
+        };
+
+        let v = self.axis.cross(&other).normalize();
+        let u = v.cross(&self.axis);
+
+        [u, v]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use approx::assert_abs_diff_eq;
+    use nalgebra::{point, vector};
+
+    use super::Torus;
+
+    #[test]
+    fn test_surface_model_point_conversion() {
+        let torus = torus();
+
+        let point = point![PI / 2., PI / 2.];
+        let model = torus.point_surface_to_model(point);
+
+        assert_abs_diff_eq!(model, point![0., 2., 1.], epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            torus.point_model_to_surface(model).unwrap(),
+            point,
+            epsilon = 1e-12,
+        );
+        assert_eq!(torus.point_model_to_surface(point![0., 0., 0.]), Err(()));
+    }
+
+    #[test]
+    fn test_approx() {
+        let torus = torus();
+
+        let mut triangles = Vec::new();
+        torus.approx(0.01, &mut triangles);
+
+        // All normals must point away from the center of the tube.
+        for triangle in &triangles {
+            let normal = triangle.normal().unwrap();
+            let center = triangle.center();
+
+            let radial = vector![center.x, center.y, 0.].normalize() * 2.;
+            assert!(normal.dot(&(center.coords - radial)) > 0.);
+        }
+
+        // The approximation is inscribed, so its area is a bit smaller than
+        // the actual area.
+        let expected = 4. * PI * PI * 2.;
+        let area: f64 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert!(area < expected && area > expected * 0.98);
+    }
+
+    /// A torus around the z-axis, with a tube of radius 1
+    fn torus() -> Torus {
+        Torus {
+            center: point![0., 0., 0.],
+            axis: vector![0., 0., 1.],
+            major_radius: 2.,
+            minor_radius: 1.,
+            reverse: false,
+        }
+    }
+}
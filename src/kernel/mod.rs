@@ -1,8 +1,12 @@
+pub mod algorithms;
 pub mod geometry;
 pub mod shapes;
 pub mod topology;
 pub mod util;
 
+#[cfg(test)]
+mod test_util;
+
 use parry3d_f64::bounding_volume::AABB;
 
 use crate::{debug::DebugInfo, math::Point};
@@ -10,6 +14,9 @@ use crate::{debug::DebugInfo, math::Point};
 use self::topology::{edges::Edges, faces::Faces};
 
 /// Implemented by all shapes
+///
+/// Shapes that contain shapes on planes must be placed using
+/// [`shapes::on_plane::place`], before they are evaluated.
 pub trait Shape {
     /// Access the axis-aligned bounding box of a shape
     ///
@@ -51,8 +58,17 @@ macro_rules! dispatch {
                 fn $method(&self, $($arg_name: $arg_ty,)*) -> $ret {
                     match self {
                         Self::Circle(shape) => shape.$method($($arg_name,)*),
+                        Self::CircularPattern(shape) => shape.$method($($arg_name,)*),
                         Self::Difference(shape) => shape.$method($($arg_name,)*),
+                        Self::Ellipse(shape) => shape.$method($($arg_name,)*),
+                        Self::Intersection(shape) => shape.$method($($arg_name,)*),
+                        Self::LinearPattern(shape) => shape.$method($($arg_name,)*),
+                        Self::Offset(shape) => shape.$method($($arg_name,)*),
+                        Self::OnPlane(shape) => shape.$method($($arg_name,)*),
                         Self::Sketch(shape) => shape.$method($($arg_name,)*),
+                        Self::Text(shape) => shape.$method($($arg_name,)*),
+                        Self::Transform(shape) => shape.$method($($arg_name,)*),
+                        Self::Union(shape) => shape.$method($($arg_name,)*),
                     }
                 }
             )*
@@ -62,8 +78,21 @@ macro_rules! dispatch {
             $(
                 fn $method(&self, $($arg_name: $arg_ty,)*) -> $ret {
                     match self {
+                        Self::Chamfer(shape) => shape.$method($($arg_name,)*),
+                        Self::CircularPattern(shape) => shape.$method($($arg_name,)*),
+                        Self::Cone(shape) => shape.$method($($arg_name,)*),
+                        Self::Cylinder(shape) => shape.$method($($arg_name,)*),
                         Self::Difference(shape) => shape.$method($($arg_name,)*),
+                        Self::Fillet(shape) => shape.$method($($arg_name,)*),
+                        Self::Intersection(shape) => shape.$method($($arg_name,)*),
+                        Self::LinearPattern(shape) => shape.$method($($arg_name,)*),
+                        Self::Loft(shape) => shape.$method($($arg_name,)*),
+                        Self::PathSweep(shape) => shape.$method($($arg_name,)*),
+                        Self::Revolve(shape) => shape.$method($($arg_name,)*),
+                        Self::Shell(shape) => shape.$method($($arg_name,)*),
+                        Self::Sphere(shape) => shape.$method($($arg_name,)*),
                         Self::Sweep(shape) => shape.$method($($arg_name,)*),
+                        Self::Torus(shape) => shape.$method($($arg_name,)*),
                         Self::Transform(shape) => shape.$method($($arg_name,)*),
                         Self::Union(shape) => shape.$method($($arg_name,)*),
                     }
@@ -0,0 +1,166 @@
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        topology::{edges::Edges, faces::Faces},
+        util::default_tolerance,
+        Shape,
+    },
+    math::Point,
+};
+
+use super::sweep::{
+    layers_edges, layers_faces, modify_edges, EdgeModification,
+};
+
+impl Shape for fj::Chamfer {
+    fn bounding_volume(&self) -> AABB {
+        // Beveling edges removes material at convex edges, and adds material
+        // within concave corners. Neither extends the shape.
+        self.shape.bounding_volume()
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let (shape, layers) =
+            modify_edges(&self.shape, modification(self), tolerance);
+        layers_faces(&shape, &layers, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let tolerance = default_tolerance(&self.bounding_volume());
+
+        let (shape, layers) =
+            modify_edges(&self.shape, modification(self), tolerance);
+        layers_edges(&shape, &layers)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Compute how a chamfer modifies the edges of a sweep
+pub fn modification(chamfer: &fj::Chamfer) -> EdgeModification {
+    let distance = chamfer.distance;
+
+    EdgeModification {
+        edges: chamfer.edges,
+        profile: vec![[distance, 0.], [0., distance]],
+        modify_corners: Box::new(move |sketch| {
+            sketch.chamfer(distance, &fj::Corners::All)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::prelude::*;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{assert_approx, volume, TOLERANCE},
+            Shape as _,
+        },
+    };
+
+    #[test]
+    fn chamfer_of_top_face() {
+        let shape: fj::Shape3d =
+            cube().chamfer(0.25, fj::EdgeSelection::TopFace).into();
+
+        // Two caps, and four side faces for each of the two sections of the
+        // sweep.
+        assert_eq!(faces(&shape), 2 + 2 * 4);
+        assert_approx(volume(&shape), 3. + frustum(0.25), 1e-9);
+        assert_bounding_volume(&shape);
+    }
+
+    #[test]
+    fn chamfer_of_edges_parallel_to_z() {
+        let shape: fj::Shape3d =
+            cube().chamfer(0.25, fj::EdgeSelection::ParallelToZ).into();
+
+        // The swept square has become an octagon.
+        assert_eq!(faces(&shape), 2 + 8);
+        assert_approx(volume(&shape), 4. - 4. * 0.25 * 0.25 / 2., 1e-9);
+        assert_bounding_volume(&shape);
+    }
+
+    #[test]
+    fn chamfer_of_top_and_bottom_face() {
+        let shape: fj::Shape3d = cube()
+            .chamfer(0.25, fj::EdgeSelection::BottomFace)
+            .chamfer(0.25, fj::EdgeSelection::TopFace)
+            .into();
+
+        assert_eq!(faces(&shape), 2 + 3 * 4);
+        assert_approx(volume(&shape), 2. + 2. * frustum(0.25), 1e-9);
+        assert_bounding_volume(&shape);
+    }
+
+    #[test]
+    fn chamfer_of_chamfered_edges() {
+        // The edges parallel to the z-axis are beveled first, even though
+        // that's the outer modification.
+        let shape: fj::Shape3d = cube()
+            .chamfer(0.25, fj::EdgeSelection::TopFace)
+            .chamfer(0.25, fj::EdgeSelection::ParallelToZ)
+            .into();
+
+        assert_eq!(faces(&shape), 2 + 2 * 8);
+        assert_bounding_volume(&shape);
+    }
+
+    #[test]
+    fn chamfer_of_edges_parallel_to_z_of_difference() {
+        let square = |min: f64, size: f64| {
+            fj::Sketch::from_points(vec![
+                [min, min],
+                [min + size, min],
+                [min + size, min + size],
+                [min, min + size],
+            ])
+        };
+        let difference = fj::Difference2d {
+            a: square(0., 2.).into(),
+            b: square(0.5, 1.).into(),
+        };
+        let shape: fj::Shape3d = difference
+            .sweep(1.)
+            .chamfer(0.25, fj::EdgeSelection::ParallelToZ)
+            .into();
+
+        // The corners of the hole are beveled too, which adds as much material
+        // as the corners of the outer boundary remove.
+        assert_approx(volume(&shape), 3., 1e-9);
+        assert_bounding_volume(&shape);
+    }
+
+    fn cube() -> fj::Sweep {
+        fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .sweep(1.)
+    }
+
+    /// The volume of the beveled part of the cube
+    ///
+    /// That is a frustum with a height of `distance`, whose bottom is the
+    /// original square, and whose top is moved inward by `distance`.
+    fn frustum(distance: f64) -> f64 {
+        let bottom = 4.;
+        let top = (2. - 2. * distance) * (2. - 2. * distance);
+
+        distance / 3. * (bottom + top + (bottom * top).sqrt())
+    }
+
+    fn faces(shape: &fj::Shape3d) -> usize {
+        shape.faces(TOLERANCE, &mut DebugInfo::new()).0.len()
+    }
+
+    fn assert_bounding_volume(shape: &fj::Shape3d) {
+        let aabb = shape.bounding_volume();
+        assert_eq!(aabb.mins, [0., 0., 0.].into());
+        assert_eq!(aabb.maxs, [2., 2., 1.].into());
+    }
+}
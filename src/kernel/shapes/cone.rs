@@ -0,0 +1,70 @@
+use std::f64::consts::PI;
+
+use nalgebra::point;
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{surfaces::RevolvedCurve, Curve, Line, Surface},
+        topology::{
+            edges::{Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Cone {
+    fn bounding_volume(&self) -> AABB {
+        AABB {
+            mins: point![-self.radius, -self.radius, 0.],
+            maxs: point![self.radius, self.radius, self.height],
+        }
+    }
+
+    fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(
+            self.radius > 0. && self.height > 0.,
+            "A cone must have a positive radius and height."
+        );
+
+        let base = Face::Face {
+            edges: Edges::single_cycle([Edge::arc(self.radius)]),
+            surface: Surface::x_y_plane(),
+        };
+
+        // The base is approximated in the same way as the bottom row of points
+        // of the side face, so they fit together. The top row consists only of
+        // the apex.
+        let side = RevolvedCurve {
+            curve: Curve::Line(Line {
+                a: point![self.radius, 0., 0.],
+                b: point![0., 0., self.height],
+            }),
+            origin: Point::origin(),
+            axis: Vector::z(),
+            angle: 2. * PI,
+            reverse: false,
+        };
+
+        Faces(vec![
+            base.reverse(),
+            Face::Face {
+                edges: Edges { cycles: Vec::new() },
+                surface: Surface::Revolved(side),
+            },
+        ])
+    }
+
+    fn edges(&self) -> Edges {
+        Edges::single_cycle([Edge::arc(self.radius)])
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        vec![point![0., 0., self.height]]
+    }
+}
@@ -0,0 +1,83 @@
+use std::f64::consts::PI;
+
+use nalgebra::point;
+use parry3d_f64::{bounding_volume::AABB, math::Isometry};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{surfaces::RevolvedCurve, Curve, Line, Surface},
+        topology::{
+            edges::{Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Cylinder {
+    fn bounding_volume(&self) -> AABB {
+        AABB {
+            mins: point![-self.radius, -self.radius, 0.],
+            maxs: point![self.radius, self.radius, self.height],
+        }
+    }
+
+    fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(
+            self.radius > 0. && self.height > 0.,
+            "A cylinder must have a positive radius and height."
+        );
+
+        let cap = Faces(vec![Face::Face {
+            edges: Edges::single_cycle([Edge::arc(self.radius)]),
+            surface: Surface::x_y_plane(),
+        }]);
+
+        // The caps are approximated in the same way as the rows of points of
+        // the side face, so they fit together.
+        let side = RevolvedCurve {
+            curve: Curve::Line(Line {
+                a: point![self.radius, 0., 0.],
+                b: point![self.radius, 0., self.height],
+            }),
+            origin: Point::origin(),
+            axis: Vector::z(),
+            angle: 2. * PI,
+            reverse: false,
+        };
+
+        let mut faces = Vec::new();
+        faces.extend(cap.clone().reverse().0);
+        faces.extend(
+            cap.transform(&Isometry::translation(0., 0., self.height)).0,
+        );
+        faces.push(Face::Face {
+            edges: Edges { cycles: Vec::new() },
+            surface: Surface::Revolved(side),
+        });
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        let bottom = Edges::single_cycle([Edge::arc(self.radius)]);
+        let top = bottom.clone().transform(&Isometry::translation(
+            0.,
+            0.,
+            self.height,
+        ));
+
+        Edges {
+            cycles: bottom.cycles.into_iter().chain(top.cycles).collect(),
+        }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        // Cylinders have just round edges with no vertices.
+        Vec::new()
+    }
+}
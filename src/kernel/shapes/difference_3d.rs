@@ -3,6 +3,7 @@ use parry3d_f64::bounding_volume::AABB;
 use crate::{
     debug::DebugInfo,
     kernel::{
+        algorithms::{csg, feature_edges},
         topology::{edges::Edges, faces::Faces},
         Shape,
     },
@@ -17,15 +18,18 @@ impl Shape for fj::Difference {
         self.a.bounding_volume()
     }
 
-    fn faces(&self, _tolerance: f64, _: &mut DebugInfo) -> Faces {
-        todo!()
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let a = self.a.faces(tolerance, debug_info);
+        let b = self.b.faces(tolerance, debug_info);
+
+        csg::difference(a, b, tolerance, debug_info)
     }
 
     fn edges(&self) -> Edges {
-        todo!()
+        feature_edges::of_shape(self)
     }
 
     fn vertices(&self) -> Vec<Point<3>> {
-        todo!()
+        self.edges().vertices()
     }
 }
@@ -0,0 +1,46 @@
+use nalgebra::{point, vector};
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{Curve, Ellipse, Surface},
+        topology::{
+            edges::{Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::Point,
+};
+
+impl Shape for fj::Ellipse {
+    fn bounding_volume(&self) -> AABB {
+        AABB {
+            mins: point![-self.radius_x, -self.radius_y, 0.0],
+            maxs: point![self.radius_x, self.radius_y, 0.0],
+        }
+    }
+
+    fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
+        Faces(vec![Face::Face {
+            edges: self.edges(),
+            surface: Surface::x_y_plane(),
+        }])
+    }
+
+    fn edges(&self) -> Edges {
+        let ellipse = Ellipse {
+            center: Point::origin(),
+            a: vector![self.radius_x, 0., 0.],
+            b: vector![0., self.radius_y, 0.],
+        };
+
+        Edges::single_cycle([Edge::new(Curve::Ellipse(ellipse))])
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        // Ellipses have just a single round edge with no vertices.
+        Vec::new()
+    }
+}
@@ -0,0 +1,154 @@
+use std::f64::consts::PI;
+
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::Circle,
+        topology::{edges::Edges, faces::Faces},
+        util::default_tolerance,
+        Shape,
+    },
+    math::Point,
+};
+
+use super::sweep::{
+    layers_edges, layers_faces, modify_edges, EdgeModification,
+};
+
+impl Shape for fj::Fillet {
+    fn bounding_volume(&self) -> AABB {
+        // Rounding edges removes material at convex edges, and adds material
+        // within concave corners. Neither extends the shape.
+        self.shape.bounding_volume()
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let (shape, layers) =
+            modify_edges(&self.shape, modification(self, tolerance), tolerance);
+        layers_faces(&shape, &layers, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let tolerance = default_tolerance(&self.bounding_volume());
+
+        let (shape, layers) =
+            modify_edges(&self.shape, modification(self, tolerance), tolerance);
+        layers_edges(&shape, &layers)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Compute how a fillet modifies the edges of a sweep
+pub fn modification(fillet: &fj::Fillet, tolerance: f64) -> EdgeModification {
+    let radius = fillet.radius;
+
+    // Rounded edges of the top or bottom face are approximated by a number of
+    // layers, each of which is moved inward a bit further. The layers follow a
+    // quarter circle, approximated in the same way as a full circle would be.
+    let n = Circle::number_of_vertices(tolerance, radius);
+    let n = (n as f64 / 4.).ceil() as u64;
+
+    let profile = (0..=n)
+        .map(|i| {
+            let angle = PI / 2. / n as f64 * i as f64;
+            [radius * (1. - angle.sin()), radius * (1. - angle.cos())]
+        })
+        .collect();
+
+    EdgeModification {
+        edges: fillet.edges,
+        profile,
+        modify_corners: Box::new(move |sketch| {
+            sketch.fillet(radius, &fj::Corners::All)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{assert_approx, volume, TOLERANCE},
+            Shape as _,
+        },
+    };
+
+    #[test]
+    fn fillet_of_edges_parallel_to_z() {
+        let shape: fj::Shape3d =
+            cube().fillet(0.5, fj::EdgeSelection::ParallelToZ).into();
+
+        // Two caps, four flat side faces, and four round ones.
+        assert_eq!(faces(&shape), 2 + 4 + 4);
+        assert_approx(volume(&shape), rounded_square(0.5), 0.01);
+        assert_bounding_volume(&shape, [[0., 0., 0.], [2., 2., 1.]]);
+    }
+
+    #[test]
+    fn fillet_of_top_face_of_cylinder() {
+        let shape: fj::Shape3d = fj::Cylinder {
+            radius: 1.,
+            height: 1.,
+        }
+        .fillet(0.25, fj::EdgeSelection::TopFace)
+        .into();
+
+        // The material that is removed is the area between the edge and the
+        // rounded face, revolved around the axis of the cylinder at the
+        // distance of its centroid (Pappus's theorem).
+        let area = 0.25 * 0.25 * (1. - PI / 4.);
+        let centroid = 1. - 0.25 * (10. - 3. * PI) / (3. * (4. - PI));
+        let removed = 2. * PI * centroid * area;
+
+        assert_approx(volume(&shape), PI - removed, 0.01);
+        assert_bounding_volume(&shape, [[-1., -1., 0.], [1., 1., 1.]]);
+    }
+
+    #[test]
+    fn fillet_of_filleted_edges() {
+        let shape: fj::Shape3d = cube()
+            .fillet(0.5, fj::EdgeSelection::ParallelToZ)
+            .fillet(0.25, fj::EdgeSelection::TopFace)
+            .into();
+
+        // The rounded top edges only remove material from the top of the
+        // shape.
+        let volume = volume(&shape);
+        assert!(volume < rounded_square(0.5));
+        assert!(volume > rounded_square(0.5) * 0.75);
+        assert_bounding_volume(&shape, [[0., 0., 0.], [2., 2., 1.]]);
+    }
+
+    fn cube() -> fj::Sweep {
+        fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .sweep(1.)
+    }
+
+    /// The area of a 2x2 square with rounded corners
+    fn rounded_square(radius: f64) -> f64 {
+        4. - 4. * radius * radius * (1. - PI / 4.)
+    }
+
+    fn faces(shape: &fj::Shape3d) -> usize {
+        shape.faces(TOLERANCE, &mut DebugInfo::new()).0.len()
+    }
+
+    fn assert_bounding_volume(
+        shape: &fj::Shape3d,
+        [mins, maxs]: [[f64; 3]; 2],
+    ) {
+        let aabb = shape.bounding_volume();
+        assert_eq!(aabb.mins, mins.into());
+        assert_eq!(aabb.maxs, maxs.into());
+    }
+}
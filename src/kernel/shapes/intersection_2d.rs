@@ -0,0 +1,38 @@
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        topology::{edges::Edges, faces::Faces},
+        util::aabb_intersection,
+        Shape,
+    },
+    math::Point,
+};
+
+impl Shape for fj::Intersection2d {
+    fn bounding_volume(&self) -> AABB {
+        let a = self.a.bounding_volume();
+        let b = self.b.bounding_volume();
+
+        // This is a conservative estimate of the bounding box: The intersection
+        // can't be bigger than the area that both bounding boxes share.
+        aabb_intersection(&a, &b)
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let a = self.a.faces(tolerance, debug_info);
+        let b = self.b.faces(tolerance, debug_info);
+
+        csg_2d::intersection(a, b, tolerance)
+    }
+
+    fn edges(&self) -> Edges {
+        csg_2d::shape_edges(self)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
@@ -0,0 +1,732 @@
+use parry3d_f64::{
+    bounding_volume::{BoundingVolume as _, AABB},
+    math::Isometry,
+    shape::Triangle,
+};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        geometry::{Curve, Line},
+        shapes::on_plane,
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        util::default_tolerance,
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Loft {
+    fn bounding_volume(&self) -> AABB {
+        // The side faces connect the profiles in straight lines, so they can't
+        // extend beyond the bounding volumes of the profiles.
+        self.to_profiles()
+            .iter()
+            .map(|profile| {
+                let (shape, isometry) = placement(profile);
+                shape.bounding_volume().transform_by(&isometry)
+            })
+            .reduce(|a, b| a.merged(&b))
+            .unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let profiles: Vec<_> = self
+            .to_profiles()
+            .iter()
+            .map(|profile| Profile::new(profile, tolerance, debug_info))
+            .collect();
+
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(
+            profiles.len() >= 2,
+            "A loft requires at least two profiles."
+        );
+
+        // The original faces point in positive z direction, in the profiles'
+        // local coordinates. Whether the loft continues in that direction
+        // decides the orientation of all faces.
+        let upwards = {
+            let [a, b] = [&profiles[0], &profiles[1]];
+            let normal = a.isometry.transform_vector(&Vector::z());
+            (b.center() - a.center()).dot(&normal) >= 0.
+        };
+
+        let mut faces = Vec::new();
+
+        for pair in profiles.windows(2) {
+            let [a, b] = [&pair[0], &pair[1]];
+
+            let mut triangles = Vec::new();
+            for (cycle_a, cycle_b) in a.match_cycles(b) {
+                for [row_a, row_b] in cycle_a.connect(cycle_b) {
+                    let row_a = transform(row_a, &a.isometry);
+                    let row_b = transform(row_b, &b.isometry);
+
+                    for [v0, v1, v2] in zip(&row_a, &row_b) {
+                        let triangle = if upwards {
+                            Triangle::new(v0, v1, v2)
+                        } else {
+                            Triangle::new(v0, v2, v1)
+                        };
+
+                        // Connecting points that are identical in both
+                        // profiles results in degenerate triangles. Those can
+                        // be ignored.
+                        if triangle.area() > 0. {
+                            triangles.push(triangle);
+                        }
+                    }
+                }
+            }
+
+            faces.push(Face::Triangles(triangles));
+        }
+
+        // Can't panic. We made sure there are at least two profiles above.
+        let first = profiles.first().unwrap();
+        let last = profiles.last().unwrap();
+
+        let bottom_faces = first.faces.clone().transform(&first.isometry);
+        let top_faces = last.faces.clone().transform(&last.isometry);
+
+        let (bottom_faces, top_faces) = if upwards {
+            (bottom_faces.reverse(), top_faces)
+        } else {
+            (bottom_faces, top_faces.reverse())
+        };
+
+        faces.extend(bottom_faces.0);
+        faces.extend(top_faces.0);
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        let tolerance = default_tolerance(&self.bounding_volume());
+
+        let profiles: Vec<_> = self
+            .to_profiles()
+            .iter()
+            .map(|profile| {
+                Profile::new(profile, tolerance, &mut DebugInfo::new())
+            })
+            .collect();
+
+        let mut cycles = Vec::new();
+
+        for profile in &profiles {
+            cycles.extend(profile.edges().transform(&profile.isometry).cycles);
+        }
+
+        // The side faces meet at seams that start at the corners of the
+        // profiles.
+        for pair in profiles.windows(2) {
+            let [a, b] = [&pair[0], &pair[1]];
+            let corners = [a.edges().vertices(), b.edges().vertices()];
+
+            for (cycle_a, cycle_b) in a.match_cycles(b) {
+                for [row_a, row_b] in cycle_a.connect(cycle_b) {
+                    for [start, end] in seams([&row_a, &row_b], &corners) {
+                        let line = Line {
+                            a: a.isometry.transform_point(&start),
+                            b: b.isometry.transform_point(&end),
+                        };
+
+                        cycles.push(Cycle {
+                            edges: vec![Edge::new(Curve::Line(line))],
+                        });
+                    }
+                }
+            }
+        }
+
+        Edges { cycles }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// A profile of the loft, in its local coordinates
+struct Profile {
+    faces: Faces,
+    cycles: Vec<ProfileCycle>,
+    isometry: Isometry<f64>,
+}
+
+impl Profile {
+    fn new(
+        profile: &fj::LoftProfile,
+        tolerance: f64,
+        debug_info: &mut DebugInfo,
+    ) -> Self {
+        let (shape, isometry) = placement(profile);
+
+        // The side faces are created from the edges of the faces, and rely on
+        // their consistent orientation.
+        let faces =
+            csg_2d::orient(shape.faces(tolerance, debug_info), tolerance);
+
+        let mut cycles = Vec::new();
+        for face in &faces.0 {
+            match face {
+                Face::Face { edges, .. } => {
+                    cycles.extend(edges.cycles.iter().map(|cycle| {
+                        ProfileCycle::new(&cycle.edges, tolerance)
+                    }));
+                }
+                Face::Triangles(_) => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
+            }
+        }
+
+        Self {
+            faces,
+            cycles,
+            isometry,
+        }
+    }
+
+    /// The edges of the profile, in its local coordinates
+    fn edges(&self) -> Edges {
+        let cycles = self
+            .faces
+            .0
+            .iter()
+            .flat_map(|face| match face {
+                Face::Face { edges, .. } => edges.cycles.clone(),
+                Face::Triangles(_) => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
+            })
+            .collect();
+
+        Edges { cycles }
+    }
+
+    /// The center of the profile, in model coordinates
+    fn center(&self) -> Point<3> {
+        let points: Vec<_> = self
+            .cycles
+            .iter()
+            .flat_map(|cycle| cycle.edges.iter().flatten().copied())
+            .collect();
+        let center = AABB::from_points(&points).center();
+
+        self.isometry.transform_point(&center)
+    }
+
+    /// Find the matching cycle of the other profile, for each cycle
+    ///
+    /// Outer boundaries are matched with outer boundaries, holes with holes.
+    /// Of those, the cycle with the closest center is chosen.
+    fn match_cycles<'r>(
+        &'r self,
+        other: &'r Self,
+    ) -> Vec<(&'r ProfileCycle, &'r ProfileCycle)> {
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert_eq!(
+            self.cycles.len(),
+            other.cycles.len(),
+            "Loft profiles must have the same number of boundaries."
+        );
+
+        let mut unmatched: Vec<_> = other.cycles.iter().collect();
+        let mut matches = Vec::new();
+
+        for cycle in &self.cycles {
+            let closest = unmatched
+                .iter()
+                .enumerate()
+                .filter(|(_, other)| other.is_hole == cycle.is_hole)
+                .map(|(i, other)| {
+                    (i, (other.center() - cycle.center()).magnitude())
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            match closest {
+                Some((i, _)) => matches.push((cycle, unmatched.remove(i))),
+                None => {
+                    panic!("Loft profiles must have the same number of holes.")
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// A cycle of a profile
+struct ProfileCycle {
+    /// The approximated edges of the cycle, in the profile's local coordinates
+    ///
+    /// Each edge is approximated by a polyline. The last point of each edge is
+    /// the first point of the next one.
+    edges: Vec<Vec<Point<3>>>,
+
+    /// Indicates whether the cycle is the boundary of a hole
+    is_hole: bool,
+}
+
+impl ProfileCycle {
+    fn new(edges: &[Edge], tolerance: f64) -> Self {
+        let edges: Vec<Vec<_>> = edges
+            .iter()
+            .map(|edge| {
+                let segments = edge.approx(tolerance).segments;
+
+                segments
+                    .first()
+                    .map(|segment| segment.a)
+                    .into_iter()
+                    .chain(segments.iter().map(|segment| segment.b))
+                    .collect()
+            })
+            .collect();
+
+        // Outer boundaries are oriented counter-clockwise, holes clockwise.
+        let signed_area: f64 = edges
+            .iter()
+            .flat_map(|edge| edge.windows(2))
+            .map(|segment| {
+                let [a, b] = [segment[0], segment[1]];
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+
+        Self {
+            edges,
+            is_hole: signed_area < 0.,
+        }
+    }
+
+    fn center(&self) -> Point<3> {
+        let points: Vec<_> = self.edges.iter().flatten().copied().collect();
+        AABB::from_points(&points).center()
+    }
+
+    /// The whole cycle as a single, closed polyline
+    fn polyline(&self) -> Vec<Point<3>> {
+        let mut polyline = Vec::new();
+
+        for edge in &self.edges {
+            // The first point of each edge is the last point of the previous
+            // edge.
+            let skip = if polyline.is_empty() { 0 } else { 1 };
+            polyline.extend(edge.iter().skip(skip));
+        }
+
+        polyline
+    }
+
+    /// Compute the pairs of polylines that need to be connected
+    ///
+    /// If both cycles have the same number of edges, each edge is connected to
+    /// an edge of the other cycle. Otherwise, both cycles are connected as a
+    /// whole.
+    ///
+    /// In both cases, the polylines are chosen such, that their start points
+    /// are as close as possible to each other. This prevents the loft from
+    /// being twisted.
+    fn connect(&self, other: &Self) -> Vec<[Vec<Point<3>>; 2]> {
+        let n = self.edges.len();
+
+        if n == other.edges.len() {
+            let start = |edge: &Vec<Point<3>>| edge.first().copied();
+
+            let offset = (0..n)
+                .map(|offset| {
+                    let distance: f64 = (0..n)
+                        .filter_map(|i| {
+                            let a = start(&self.edges[i])?;
+                            let b = start(&other.edges[(i + offset) % n])?;
+                            Some((a - b).magnitude())
+                        })
+                        .sum();
+                    (offset, distance)
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(offset, _)| offset)
+                .unwrap_or(0);
+
+            return (0..n)
+                .map(|i| {
+                    [
+                        self.edges[i].clone(),
+                        other.edges[(i + offset) % n].clone(),
+                    ]
+                })
+                .collect();
+        }
+
+        let a = self.polyline();
+        let b = other.polyline();
+
+        let a_coords = arc_length_coords(&a);
+
+        // Try every point of `b` as the start point. The one that results in
+        // the smallest distance between corresponding points is chosen.
+        let b = (0..b.len().saturating_sub(1))
+            .map(|start| {
+                let b = rotate(&b, start);
+                let b_coords = arc_length_coords(&b);
+
+                let distance: f64 = a
+                    .iter()
+                    .zip(&a_coords)
+                    .map(|(point, &t)| {
+                        (point - point_at(&b, &b_coords, t)).magnitude()
+                    })
+                    .sum();
+
+                (b, distance)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(b, _)| b)
+            .unwrap_or(b);
+
+        vec![[a, b]]
+    }
+}
+
+/// Rotate a closed polyline, so it starts at the point with index `start`
+fn rotate(polyline: &[Point<3>], start: usize) -> Vec<Point<3>> {
+    let n = polyline.len() - 1;
+    (0..=n).map(|i| polyline[(start + i) % n]).collect()
+}
+
+/// Compute the normalized arc length of each point of a polyline
+///
+/// The first point has the coordinate `0`, the last one has the coordinate
+/// `1`.
+fn arc_length_coords(polyline: &[Point<3>]) -> Vec<f64> {
+    let mut coords = Vec::with_capacity(polyline.len());
+    let mut length = 0.;
+
+    for (i, point) in polyline.iter().enumerate() {
+        if i > 0 {
+            length += (point - polyline[i - 1]).magnitude();
+        }
+        coords.push(length);
+    }
+
+    if length > 0. {
+        for coord in &mut coords {
+            *coord /= length;
+        }
+    }
+
+    coords
+}
+
+/// Compute the point at the given normalized arc length of a polyline
+fn point_at(polyline: &[Point<3>], coords: &[f64], t: f64) -> Point<3> {
+    let i = coords
+        .partition_point(|&coord| coord < t)
+        .clamp(1, polyline.len() - 1);
+
+    let [t0, t1] = [coords[i - 1], coords[i]];
+    let [p0, p1] = [polyline[i - 1], polyline[i]];
+
+    if t1 > t0 {
+        p0 + (p1 - p0) * ((t - t0) / (t1 - t0)).clamp(0., 1.)
+    } else {
+        p0
+    }
+}
+
+/// Compute the seams between two polylines that are connected
+///
+/// Returns a line from each corner of either polyline to the point of the other
+/// one at the same normalized arc length. Each polyline is in the local
+/// coordinates of its profile, and so are the respective ends of the lines.
+fn seams(
+    [a, b]: [&[Point<3>]; 2],
+    [corners_a, corners_b]: &[Vec<Point<3>>; 2],
+) -> Vec<[Point<3>; 2]> {
+    let a_coords = arc_length_coords(a);
+    let b_coords = arc_length_coords(b);
+
+    // The last point of a polyline is either the first point of the next one,
+    // or, if the polyline is closed, its own first point.
+    let corners = |polyline: &[Point<3>], coords: &[f64], corners: &[_]| {
+        polyline
+            .iter()
+            .zip(coords)
+            .take(polyline.len().saturating_sub(1))
+            .filter(|(point, _)| {
+                corners.iter().any(|corner: &Point<3>| {
+                    (corner - *point).magnitude() < 1e-7
+                })
+            })
+            .map(|(&point, &t)| (point, t))
+            .collect::<Vec<_>>()
+    };
+    let corners_a = corners(a, &a_coords, corners_a);
+    let corners_b = corners(b, &b_coords, corners_b);
+
+    let mut seams: Vec<_> = corners_a
+        .iter()
+        .map(|&(point, t)| [point, point_at(b, &b_coords, t)])
+        .collect();
+    for (point, t) in corners_b {
+        // Corners at the same position of both polylines share a seam.
+        if corners_a.iter().any(|&(_, other)| (other - t).abs() < 1e-9) {
+            continue;
+        }
+
+        seams.push([point_at(a, &a_coords, t), point]);
+    }
+
+    seams
+}
+
+/// Connect two polylines with triangles
+///
+/// Points are connected to the points of the other polyline, that are closest
+/// to them in terms of normalized arc length.
+fn zip(a: &[Point<3>], b: &[Point<3>]) -> Vec<[Point<3>; 3]> {
+    let a_coords = arc_length_coords(a);
+    let b_coords = arc_length_coords(b);
+
+    let mut triangles = Vec::new();
+
+    let (mut i, mut j) = (0, 0);
+    loop {
+        let can_advance_a = i + 1 < a.len();
+        let can_advance_b = j + 1 < b.len();
+
+        let advance_a = match (can_advance_a, can_advance_b) {
+            (false, false) => break,
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => a_coords[i + 1] <= b_coords[j + 1],
+        };
+
+        if advance_a {
+            triangles.push([a[i], a[i + 1], b[j]]);
+            i += 1;
+        } else {
+            triangles.push([a[i], b[j + 1], b[j]]);
+            j += 1;
+        }
+    }
+
+    triangles
+}
+
+fn transform(points: Vec<Point<3>>, isometry: &Isometry<f64>) -> Vec<Point<3>> {
+    points
+        .into_iter()
+        .map(|point| isometry.transform_point(&point))
+        .collect()
+}
+
+/// Compute the shape of a profile in its local coordinates, and its placement
+///
+/// The placement of a profile whose shape is on a plane is relative to that
+/// plane.
+fn placement(profile: &fj::LoftProfile) -> (&fj::Shape2d, Isometry<f64>) {
+    let axis = Vector::from(profile.axis).normalize();
+    let isometry =
+        Isometry::new(Vector::from(profile.offset), axis * profile.angle);
+
+    let (shape, plane) = on_plane::unplace(&profile.shape);
+    (shape, plane * isometry)
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::point;
+
+    use crate::{debug::DebugInfo, kernel::Shape as _, math::Point};
+
+    use super::{arc_length_coords, Profile};
+
+    const TOLERANCE: f64 = 0.001;
+
+    #[test]
+    fn match_cycles_by_hole_flag() {
+        // The hole of the first profile is closer to the outer boundary of the
+        // second profile, than to its hole.
+        let a = profile(square([0., 0.], 20.).with_hole(hole([0., 0.], 2.)));
+        let b = profile(square([5., 0.], 20.).with_hole(hole([2., 0.], 2.)));
+
+        let matches = a.match_cycles(&b);
+
+        assert_eq!(matches.len(), 2);
+        for (cycle_a, cycle_b) in matches {
+            assert_eq!(cycle_a.is_hole, cycle_b.is_hole);
+
+            let expected = if cycle_a.is_hole {
+                point![2., 0., 0.]
+            } else {
+                point![5., 0., 0.]
+            };
+            assert_approx(cycle_b.center(), expected);
+        }
+    }
+
+    #[test]
+    fn match_cycles_by_nearest_center() {
+        let a = profile(
+            square([0., 0.], 20.)
+                .with_hole(hole([-5., 0.], 2.))
+                .with_hole(hole([5., 0.], 2.)),
+        );
+        let b = profile(
+            square([0., 0.], 20.)
+                .with_hole(hole([6., 0.], 2.))
+                .with_hole(hole([-4., 0.], 2.)),
+        );
+
+        let holes: Vec<_> = a
+            .match_cycles(&b)
+            .into_iter()
+            .filter(|(cycle, _)| cycle.is_hole)
+            .collect();
+
+        assert_eq!(holes.len(), 2);
+        for (cycle_a, cycle_b) in holes {
+            let offset = cycle_b.center() - cycle_a.center();
+            assert_approx(Point::from(offset), point![1., 0., 0.]);
+        }
+    }
+
+    #[test]
+    fn connect_square_to_circle() {
+        let a = profile(square([0., 0.], 2.));
+        let b = profile(fj::Circle { radius: 1. });
+
+        // The cycles have different numbers of edges, so they are connected
+        // as a whole.
+        let connections = a.cycles[0].connect(&b.cycles[0]);
+        assert_eq!(connections.len(), 1);
+
+        let [a, b] = &connections[0];
+        assert_eq!(a.first(), a.last());
+        assert_approx(*b.first().unwrap(), *b.last().unwrap());
+
+        // The polyline of the circle starts at its point closest to the
+        // square's start, which keeps the loft from being twisted.
+        let distance = (a[0] - b[0]).magnitude();
+        assert!((distance - (2f64.sqrt() - 1.)).abs() < 0.01);
+
+        // Points at the same normalized arc length are close to each other.
+        let [a_coords, b_coords] = [a, b].map(|p| arc_length_coords(p));
+        for (point, t) in a.iter().zip(a_coords) {
+            let point_b = super::point_at(b, &b_coords, t);
+            assert!((point - point_b).magnitude() < 2f64.sqrt() - 1. + 0.01);
+        }
+    }
+
+    #[test]
+    fn arc_length_coords_of_square() {
+        let square = [
+            point![0., 0., 0.],
+            point![1., 0., 0.],
+            point![1., 1., 0.],
+            point![0., 1., 0.],
+            point![0., 0., 0.],
+        ];
+
+        let coords = arc_length_coords(&square);
+        assert_eq!(coords, vec![0., 0.25, 0.5, 0.75, 1.]);
+
+        let point = super::point_at(&square, &coords, 0.625);
+        assert_approx(point, point![0.5, 1., 0.]);
+    }
+
+    #[test]
+    fn zip_connects_all_points() {
+        let a = [point![0., 0., 0.], point![1., 0., 0.], point![2., 0., 0.]];
+        let b = [point![0., 0., 1.], point![2., 0., 1.]];
+
+        let triangles = super::zip(&a, &b);
+
+        // Every segment of both polylines is the side of one triangle.
+        assert_eq!(triangles.len(), (a.len() - 1) + (b.len() - 1));
+    }
+
+    #[test]
+    fn edges_of_square_to_circle_loft() {
+        let loft = fj::Loft::from_profiles(vec![
+            fj::LoftProfile::at_height(square([0., 0.], 2.), 0.),
+            fj::LoftProfile::at_height(fj::Circle { radius: 1. }, 2.),
+        ]);
+
+        let aabb = loft.edges().bounding_volume();
+
+        assert_approx(aabb.mins, point![-1., -1., 0.]);
+        assert_approx(aabb.maxs, point![1., 1., 2.]);
+
+        // Both profiles, and a seam from each corner of the square.
+        let edges = loft.edges();
+        assert_eq!(edges.cycles.len(), 2 + 4);
+        assert_eq!(loft.vertices().len(), 4 + 4);
+    }
+
+    #[test]
+    fn edges_of_square_to_square_loft() {
+        let loft = fj::Loft::from_profiles(vec![
+            fj::LoftProfile::at_height(square([0., 0.], 2.), 0.),
+            fj::LoftProfile::at_height(square([0., 0.], 1.), 2.),
+        ]);
+
+        // The corners of both squares are connected by the same seams.
+        assert_eq!(loft.edges().cycles.len(), 2 + 4);
+        assert_eq!(loft.vertices().len(), 4 + 4);
+    }
+
+    #[test]
+    fn edges_of_circle_to_circle_loft() {
+        let loft = fj::Loft::from_profiles(vec![
+            fj::LoftProfile::at_height(fj::Circle { radius: 1. }, 0.),
+            fj::LoftProfile::at_height(fj::Circle { radius: 2. }, 2.),
+        ]);
+
+        // Without corners, there are no seams.
+        assert_eq!(loft.edges().cycles.len(), 2);
+        assert!(loft.vertices().is_empty());
+    }
+
+    fn profile(shape: impl Into<fj::Shape2d>) -> Profile {
+        let profile = fj::LoftProfile::at_height(shape, 0.);
+        Profile::new(&profile, TOLERANCE, &mut DebugInfo::new())
+    }
+
+    fn square([x, y]: [f64; 2], size: f64) -> fj::Sketch {
+        fj::Sketch::from_points(square_points([x, y], size))
+    }
+
+    fn hole([x, y]: [f64; 2], size: f64) -> fj::SketchCycle {
+        let mut points = square_points([x, y], size);
+        points.reverse();
+        fj::SketchCycle::from_points(points)
+    }
+
+    fn square_points([x, y]: [f64; 2], size: f64) -> Vec<[f64; 2]> {
+        let h = size / 2.;
+        vec![
+            [x - h, y - h],
+            [x + h, y - h],
+            [x + h, y + h],
+            [x - h, y + h],
+        ]
+    }
+
+    fn assert_approx(a: Point<3>, b: Point<3>) {
+        assert!((a - b).magnitude() < 1e-6, "{a} != {b}");
+    }
+}
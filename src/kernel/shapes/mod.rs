@@ -1,7 +1,27 @@
+pub mod chamfer;
 pub mod circle;
+pub mod cone;
+pub mod cylinder;
 pub mod difference_2d;
 pub mod difference_3d;
+pub mod ellipse;
+pub mod fillet;
+pub mod intersection;
+pub mod intersection_2d;
+pub mod loft;
+pub mod offset_2d;
+pub mod on_plane;
+pub mod path_sweep;
+pub mod pattern;
+pub mod pattern_2d;
+pub mod revolve;
+pub mod shell;
 pub mod sketch;
+pub mod sphere;
 pub mod sweep;
+pub mod text;
+pub mod torus;
 pub mod transform;
+pub mod transform_2d;
 pub mod union;
+pub mod union_2d;
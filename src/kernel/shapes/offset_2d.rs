@@ -0,0 +1,36 @@
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::{csg_2d, offset_2d},
+        topology::{edges::Edges, faces::Faces},
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Offset2d {
+    fn bounding_volume(&self) -> AABB {
+        // This is a conservative estimate: The offset shape never extends
+        // further from the original one than the offset distance.
+        let aabb = self.shape.bounding_volume();
+        let margin =
+            Vector::from([self.distance.max(0.), self.distance.max(0.), 0.]);
+
+        AABB::new(aabb.mins - margin, aabb.maxs + margin)
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let faces = self.shape.faces(tolerance, debug_info);
+        offset_2d::offset(faces, self.distance, tolerance)
+    }
+
+    fn edges(&self) -> Edges {
+        csg_2d::shape_edges(self)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
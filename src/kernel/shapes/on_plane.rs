@@ -0,0 +1,467 @@
+use nalgebra::{Matrix3, Rotation3, Translation3, UnitQuaternion};
+use parry3d_f64::{
+    bounding_volume::AABB, math::Isometry, query::PointQuery as _,
+};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        topology::{edges::Edges, faces::Faces},
+        util::default_tolerance,
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::OnPlane {
+    fn bounding_volume(&self) -> AABB {
+        self.shape
+            .bounding_volume()
+            .transform_by(&isometry(&self.plane))
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        self.shape
+            .faces(tolerance, debug_info)
+            .transform(&isometry(&self.plane))
+    }
+
+    fn edges(&self) -> Edges {
+        self.shape.edges().transform(&isometry(&self.plane))
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Place all shapes on planes, before a shape is evaluated
+///
+/// The plane of a face can only be found by evaluating the shape it belongs
+/// to, and shapes created from a shape on a plane are evaluated by moving them
+/// onto the plane (see [`lift`]). Doing that whenever one of the methods of
+/// [`Shape`] is called would repeat it many times over, for every evaluation
+/// of a model.
+///
+/// This function does it once for the whole shape, by replacing the planes of
+/// faces with their frames, and shapes created from shapes on planes with
+/// their lifted versions. Shapes must be placed, before they are evaluated.
+pub fn place(shape: &mut fj::Shape) {
+    match shape {
+        fj::Shape::Shape2d(shape) => resolve_2d(shape),
+        fj::Shape::Shape3d(shape) => {
+            resolve_3d(shape);
+            lift_3d(shape);
+        }
+    }
+}
+
+/// Compute the isometry that moves the x-y plane onto a plane
+pub fn isometry(plane: &fj::Plane) -> Isometry<f64> {
+    let (origin, normal, x_axis) = match plane {
+        fj::Plane::Frame(frame) => (
+            Point::from(frame.origin),
+            Vector::from(frame.normal),
+            Vector::from(frame.x_axis),
+        ),
+        fj::Plane::Face(face) => face_frame(face),
+    };
+
+    let normal = normal.normalize();
+    let x_axis = x_axis - normal * normal.dot(&x_axis);
+
+    // Panicking is not great, but as long as we don't have a real error
+    // handling mechanism, it will do.
+    assert!(
+        x_axis.magnitude() > 1e-9,
+        "The x-axis of a plane must not be parallel to its normal."
+    );
+
+    let x_axis = x_axis.normalize();
+    let y_axis = normal.cross(&x_axis);
+
+    let rotation = UnitQuaternion::from_rotation_matrix(
+        &Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[
+            x_axis, y_axis, normal,
+        ])),
+    );
+    let translation = Translation3::from(origin.coords);
+
+    Isometry::from_parts(translation, rotation)
+}
+
+/// Separate a shape on a plane from the plane
+///
+/// Returns the shape in the x-y plane, and the isometry that moves it onto its
+/// plane. Shapes that aren't on a plane are returned as they are, together
+/// with the identity.
+pub fn unplace(shape: &fj::Shape2d) -> (&fj::Shape2d, Isometry<f64>) {
+    match shape {
+        fj::Shape2d::OnPlane(shape) => {
+            let (inner, isometry) = unplace(&shape.shape);
+            (inner, self::isometry(&shape.plane) * isometry)
+        }
+        shape => (shape, Isometry::identity()),
+    }
+}
+
+/// Create a 3-dimensional shape from a shape on a plane in the x-y plane
+///
+/// 3-dimensional shapes are created from a shape on a plane as if that shape
+/// were in the x-y plane, then moved onto the plane. This function separates
+/// the two steps, by returning the shape created from the shape in the x-y
+/// plane, transformed onto the plane.
+///
+/// Returns `None`, if the shape isn't created from a shape on a plane.
+pub fn lift(shape: &fj::Shape3d) -> Option<fj::Transform> {
+    let (shape, isometry) = lift_inner(shape)?;
+    let matrix = isometry.to_homogeneous();
+
+    let mut column_major = [0.; 16];
+    column_major.copy_from_slice(matrix.as_slice());
+
+    Some(fj::Transform::from_matrix(shape, column_major))
+}
+
+fn lift_inner(shape: &fj::Shape3d) -> Option<(fj::Shape3d, Isometry<f64>)> {
+    let placed = |shape: &fj::Shape2d| match shape {
+        fj::Shape2d::OnPlane(_) => {
+            let (shape, isometry) = unplace(shape);
+            Some((shape.clone(), isometry))
+        }
+        _ => None,
+    };
+
+    match shape {
+        fj::Shape3d::PathSweep(sweep) => {
+            let (shape, isometry) = placed(&sweep.shape)?;
+            let sweep = fj::PathSweep {
+                shape,
+                ..sweep.clone()
+            };
+            Some((sweep.into(), isometry))
+        }
+        fj::Shape3d::Revolve(revolve) => {
+            let (shape, isometry) = placed(&revolve.shape)?;
+            let revolve = fj::Revolve {
+                shape,
+                ..revolve.clone()
+            };
+            Some((revolve.into(), isometry))
+        }
+        fj::Shape3d::Sweep(sweep) => {
+            let (shape, isometry) = placed(&sweep.shape)?;
+            let sweep = fj::Sweep {
+                shape,
+                ..sweep.clone()
+            };
+            Some((sweep.into(), isometry))
+        }
+
+        // These shapes refer to the edges of the sweeps they modify, which are
+        // relative to the plane too.
+        fj::Shape3d::Chamfer(chamfer) => {
+            let (shape, isometry) = lift_inner(&chamfer.shape)?;
+            let chamfer = fj::Chamfer {
+                shape,
+                ..(**chamfer).clone()
+            };
+            Some((chamfer.into(), isometry))
+        }
+        fj::Shape3d::Fillet(fillet) => {
+            let (shape, isometry) = lift_inner(&fillet.shape)?;
+            let fillet = fj::Fillet {
+                shape,
+                ..(**fillet).clone()
+            };
+            Some((fillet.into(), isometry))
+        }
+        fj::Shape3d::Shell(shell) => {
+            let (shape, isometry) = lift_inner(&shell.shape)?;
+            let shell = fj::Shell {
+                shape,
+                ..(**shell).clone()
+            };
+            Some((shell.into(), isometry))
+        }
+
+        _ => None,
+    }
+}
+
+/// Replace the planes of faces with their frames
+fn resolve_2d(shape: &mut fj::Shape2d) {
+    match shape {
+        fj::Shape2d::Circle(_)
+        | fj::Shape2d::Ellipse(_)
+        | fj::Shape2d::Sketch(_)
+        | fj::Shape2d::Text(_) => {}
+        fj::Shape2d::CircularPattern(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape2d::Difference(shape) => {
+            resolve_2d(&mut shape.a);
+            resolve_2d(&mut shape.b);
+        }
+        fj::Shape2d::Intersection(shape) => {
+            resolve_2d(&mut shape.a);
+            resolve_2d(&mut shape.b);
+        }
+        fj::Shape2d::LinearPattern(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape2d::Offset(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape2d::OnPlane(shape) => {
+            resolve_2d(&mut shape.shape);
+
+            if let fj::Plane::Face(face) = &mut shape.plane {
+                // The shape the face belongs to is evaluated to find the
+                // face, so it needs to be placed too.
+                resolve_3d(&mut face.shape);
+                lift_3d(&mut face.shape);
+
+                let (origin, normal, x_axis) = face_frame(face);
+                shape.plane =
+                    fj::Plane::new(origin.into(), normal.into(), x_axis.into());
+            }
+        }
+        fj::Shape2d::Transform(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape2d::Union(shape) => {
+            resolve_2d(&mut shape.a);
+            resolve_2d(&mut shape.b);
+        }
+    }
+}
+
+fn resolve_3d(shape: &mut fj::Shape3d) {
+    match shape {
+        fj::Shape3d::Cone(_)
+        | fj::Shape3d::Cylinder(_)
+        | fj::Shape3d::Sphere(_)
+        | fj::Shape3d::Torus(_) => {}
+        fj::Shape3d::Chamfer(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::CircularPattern(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::Difference(shape) => {
+            resolve_3d(&mut shape.a);
+            resolve_3d(&mut shape.b);
+        }
+        fj::Shape3d::Fillet(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::Intersection(shape) => {
+            resolve_3d(&mut shape.a);
+            resolve_3d(&mut shape.b);
+        }
+        fj::Shape3d::LinearPattern(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::Loft(loft) => {
+            let mut profiles = loft.to_profiles();
+            for profile in &mut profiles {
+                resolve_2d(&mut profile.shape);
+            }
+            *loft = fj::Loft::from_profiles(profiles);
+        }
+        fj::Shape3d::PathSweep(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape3d::Revolve(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape3d::Shell(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::Sweep(shape) => resolve_2d(&mut shape.shape),
+        fj::Shape3d::Transform(shape) => resolve_3d(&mut shape.shape),
+        fj::Shape3d::Union(shape) => {
+            resolve_3d(&mut shape.a);
+            resolve_3d(&mut shape.b);
+        }
+    }
+}
+
+/// Replace shapes created from shapes on planes with their lifted versions
+///
+/// Shapes are lifted from the outside in, as chamfers, fillets, and shells
+/// need to be lifted together with the sweeps they modify.
+fn lift_3d(shape: &mut fj::Shape3d) {
+    if let Some(lifted) = lift(shape) {
+        *shape = lifted.into();
+    }
+
+    match shape {
+        fj::Shape3d::Chamfer(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::CircularPattern(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::Difference(shape) => {
+            lift_3d(&mut shape.a);
+            lift_3d(&mut shape.b);
+        }
+        fj::Shape3d::Fillet(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::Intersection(shape) => {
+            lift_3d(&mut shape.a);
+            lift_3d(&mut shape.b);
+        }
+        fj::Shape3d::LinearPattern(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::Shell(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::Transform(shape) => lift_3d(&mut shape.shape),
+        fj::Shape3d::Union(shape) => {
+            lift_3d(&mut shape.a);
+            lift_3d(&mut shape.b);
+        }
+        fj::Shape3d::Cone(_)
+        | fj::Shape3d::Cylinder(_)
+        | fj::Shape3d::Loft(_)
+        | fj::Shape3d::PathSweep(_)
+        | fj::Shape3d::Revolve(_)
+        | fj::Shape3d::Sphere(_)
+        | fj::Shape3d::Sweep(_)
+        | fj::Shape3d::Torus(_) => {}
+    }
+}
+
+/// Compute the origin, normal, and x-axis of the plane of a face
+fn face_frame(face: &fj::FacePlane) -> (Point<3>, Vector<3>, Vector<3>) {
+    let point = Point::from(face.point);
+
+    let tolerance = default_tolerance(&face.shape.bounding_volume());
+    let mut triangles = Vec::new();
+    face.shape
+        .faces(tolerance, &mut DebugInfo::new())
+        .triangles(tolerance, &mut triangles, &mut DebugInfo::new());
+
+    // The triangles are oriented such that their normal points outward.
+    // Triangles that are too small to have a normal can't tell us anything
+    // about the plane.
+    let closest = triangles
+        .iter()
+        .filter_map(|triangle| Some((triangle, triangle.normal()?)))
+        .min_by(|(a, _), (b, _)| {
+            let [a, b] = [a, b]
+                .map(|triangle| triangle.distance_to_local_point(&point, true));
+            a.total_cmp(&b)
+        });
+
+    // Panicking is not great, but as long as we don't have a real error
+    // handling mechanism, it will do.
+    let (triangle, normal) =
+        closest.expect("Can't find a face to place the plane on.");
+    let normal = normal.into_inner();
+
+    let origin = point - normal * (point - triangle.a).dot(&normal);
+
+    let x_axis = if normal.cross(&Vector::x()).magnitude() > 1e-9 {
+        Vector::x()
+    } else {
+        Vector::y()
+    };
+
+    (origin, normal, x_axis)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::prelude::*;
+    use nalgebra::{point, vector};
+
+    use crate::{
+        kernel::Shape as _,
+        math::{Point, Vector},
+    };
+
+    use super::{isometry, place};
+
+    #[test]
+    fn isometry_of_plane_frame() {
+        // Neither the normal nor the x-axis are normalized, and the x-axis is
+        // not perpendicular to the normal.
+        let plane = fj::Plane::new([1., 2., 3.], [0., -2., 0.], [1., 1., 0.]);
+        let isometry = isometry(&plane);
+
+        assert_points(isometry * point![0., 0., 0.], [1., 2., 3.]);
+        assert_points(isometry * point![1., 0., 0.], [2., 2., 3.]);
+        assert_points(isometry * point![0., 1., 0.], [1., 2., 4.]);
+        assert_vectors(isometry * vector![0., 0., 1.], [0., -1., 0.]);
+    }
+
+    #[test]
+    fn isometry_of_face_plane() {
+        let plane = fj::Plane::face(cube(), [1., 1., 2.5]);
+        let isometry = isometry(&plane);
+
+        assert_points(isometry * point![0., 0., 0.], [1., 1., 2.]);
+        assert_points(isometry * point![1., 0., 0.], [2., 1., 2.]);
+        assert_vectors(isometry * vector![0., 0., 1.], [0., 0., 1.]);
+    }
+
+    #[test]
+    fn isometry_of_face_plane_perpendicular_to_x_axis() {
+        // The plane's x-axis can't be the x-axis of the model, so it's the
+        // y-axis instead.
+        let plane = fj::Plane::face(cube(), [2.5, 1., 1.]);
+        let isometry = isometry(&plane);
+
+        assert_points(isometry * point![0., 0., 0.], [2., 1., 1.]);
+        assert_points(isometry * point![1., 0., 0.], [2., 2., 1.]);
+        assert_points(isometry * point![0., 1., 0.], [2., 1., 2.]);
+        assert_vectors(isometry * vector![0., 0., 1.], [1., 0., 0.]);
+    }
+
+    #[test]
+    fn place_replaces_face_planes_with_frames() {
+        let plane = fj::Plane::face(cube(), [1., 1., 2.5]);
+        let mut shape: fj::Shape = square().on_plane(plane).into();
+
+        place(&mut shape);
+
+        match shape {
+            fj::Shape::Shape2d(fj::Shape2d::OnPlane(shape)) => {
+                assert!(matches!(shape.plane, fj::Plane::Frame(_)));
+            }
+            _ => panic!("Expected shape on plane"),
+        }
+    }
+
+    #[test]
+    fn place_lifts_shapes_created_from_shapes_on_planes() {
+        let plane = fj::Plane::face(cube(), [1., 1., 2.5]);
+        let mut shape: fj::Shape = square().on_plane(plane).sweep(1.).into();
+
+        place(&mut shape);
+
+        let shape = match shape {
+            fj::Shape::Shape3d(shape) => shape,
+            fj::Shape::Shape2d(_) => panic!("Expected 3-dimensional shape"),
+        };
+        match &shape {
+            fj::Shape3d::Transform(transform) => {
+                assert!(matches!(
+                    &transform.shape,
+                    fj::Shape3d::Sweep(fj::Sweep {
+                        shape: fj::Shape2d::Sketch(_),
+                        ..
+                    })
+                ));
+            }
+            _ => panic!("Expected transform"),
+        }
+
+        let aabb = shape.bounding_volume();
+        assert_points(aabb.mins, [1., 1., 2.]);
+        assert_points(aabb.maxs, [2., 2., 3.]);
+    }
+
+    fn cube() -> fj::Sweep {
+        fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .sweep(2.)
+    }
+
+    fn square() -> fj::Sketch {
+        fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+    }
+
+    fn assert_points(actual: Point<3>, expected: [f64; 3]) {
+        let expected = Point::from(expected);
+        assert!(
+            (actual - expected).magnitude() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_vectors(actual: Vector<3>, expected: [f64; 3]) {
+        let expected = Vector::from(expected);
+        assert!(
+            (actual - expected).magnitude() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::f64::consts::PI;
+
+use nalgebra::{point, UnitQuaternion};
+use parry3d_f64::{
+    bounding_volume::{BoundingVolume as _, AABB},
+    math::Isometry,
+    shape::Triangle,
+};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        geometry::{Circle, Curve, Line},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        util::default_tolerance,
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::PathSweep {
+    fn bounding_volume(&self) -> AABB {
+        let aabb = match &self.path {
+            fj::Path::Arc(arc) => {
+                // Use the bounding volume of the full circle. Not tight, but
+                // good enough.
+                let (center, radius, axis) = arc_geometry(arc);
+                let extent = axis.map(|a| radius * (1. - a * a).max(0.).sqrt());
+
+                AABB::new(center - extent, center + extent)
+            }
+            fj::Path::Helix(helix) => {
+                let r = helix.radius.abs();
+                let height = helix.pitch * helix.turns;
+
+                AABB::new(
+                    point![-r, -r, height.min(0.)],
+                    point![r, r, height.max(0.)],
+                )
+            }
+            fj::Path::Polyline(polyline) => {
+                let points: Vec<Point<3>> =
+                    polyline.to_points().into_iter().map(Point::from).collect();
+
+                AABB::from_points(&points)
+            }
+        };
+
+        // The profile can be rotated in any direction along the way.
+        aabb.loosened(profile_radius(&self.shape.bounding_volume()))
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        // The orientation of the side faces is derived from the orientation of
+        // the original edges, so we need to make sure those are oriented
+        // consistently.
+        let original_faces =
+            csg_2d::orient(self.shape.faces(tolerance, debug_info), tolerance);
+
+        // The original faces are going to be rotated in arbitrary ways. Curved
+        // edges don't support that yet, so we replace all edges with their
+        // approximation. This also guarantees that the end faces fit the side
+        // faces exactly.
+        let original_faces = original_faces.to_lines(tolerance);
+
+        let path = SweepPath::new(
+            &self.path,
+            tolerance,
+            profile_radius(&self.shape.bounding_volume()),
+        );
+
+        let mut side_faces = Vec::new();
+        for face in &original_faces.0 {
+            let edges = match face {
+                Face::Face { edges, .. } => edges,
+                Face::Triangles(_) => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
+            };
+
+            for cycle in &edges.cycles {
+                let profile: Vec<_> = cycle
+                    .approx(tolerance)
+                    .segments
+                    .into_iter()
+                    .map(|segment| [segment.a, segment.b])
+                    .collect();
+
+                for i in 0..path.points.len() - 1 {
+                    for [a, b] in &profile {
+                        let [v0, v1] = [path.place(i, *a), path.place(i, *b)];
+                        let [v3, v2] =
+                            [path.place(i + 1, *a), path.place(i + 1, *b)];
+
+                        for triangle in [
+                            Triangle::new(v0, v1, v2),
+                            Triangle::new(v0, v2, v3),
+                        ] {
+                            if triangle.area() > 0. {
+                                side_faces.push(triangle);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // The original faces point in positive z direction, which is where the
+        // path goes, after they have been moved to its start.
+        let start_faces = original_faces
+            .clone()
+            .transform(&path.isometry(0))
+            .reverse();
+        let end_faces =
+            original_faces.transform(&path.isometry(path.points.len() - 1));
+
+        let mut faces = Vec::new();
+        faces.extend(start_faces.0);
+        faces.extend(end_faces.0);
+        faces.push(Face::Triangles(side_faces));
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        let tolerance = default_tolerance(&self.bounding_volume());
+        let path = SweepPath::new(
+            &self.path,
+            tolerance,
+            profile_radius(&self.shape.bounding_volume()),
+        );
+
+        let profile = self.shape.edges();
+        let last = path.points.len() - 1;
+
+        let mut cycles = Vec::new();
+        cycles.extend(profile.clone().transform(&path.isometry(0)).cycles);
+        cycles.extend(profile.clone().transform(&path.isometry(last)).cycles);
+
+        // Each vertex of the profile traces a seam between two side faces.
+        for vertex in profile.vertices() {
+            let points: Vec<_> =
+                (0..=last).map(|i| path.place(i, vertex)).collect();
+            let edges = points
+                .windows(2)
+                .map(|segment| {
+                    Edge::new(Curve::Line(Line {
+                        a: segment[0],
+                        b: segment[1],
+                    }))
+                })
+                .collect();
+
+            cycles.push(Cycle { edges });
+        }
+
+        Edges { cycles }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// The approximated path of a sweep
+struct SweepPath {
+    /// The points of the path
+    points: Vec<Point<3>>,
+
+    /// The rotation of the profile along each segment of the path
+    ///
+    /// Each rotation rotates the z-axis into the direction of its segment.
+    /// Each rotation differs from the previous one by the minimal rotation
+    /// required to follow the path.
+    rotations: Vec<UnitQuaternion<f64>>,
+}
+
+impl SweepPath {
+    /// Approximate a path
+    ///
+    /// `profile_radius` is the maximum distance of the swept profile from the
+    /// path. The approximation needs to take it into account, as the outer
+    /// parts of the profile move further than the path itself.
+    fn new(path: &fj::Path, tolerance: f64, profile_radius: f64) -> Self {
+        let number_of_segments = |radius: f64, angle: f64| {
+            let n = Circle::number_of_vertices(tolerance, radius);
+            (n as f64 * angle.abs() / (2. * PI)).ceil().max(1.) as u64
+        };
+
+        let mut points: Vec<Point<3>> = match path {
+            fj::Path::Arc(arc) => {
+                let (_, radius, axis) = arc_geometry(arc);
+                let center = Point::from(arc.center);
+                let start = Point::from(arc.start);
+
+                let n = number_of_segments(radius + profile_radius, arc.angle);
+                (0..=n)
+                    .map(|i| {
+                        let angle = arc.angle / n as f64 * i as f64;
+                        let rotation = Isometry::rotation(axis * angle);
+                        center + rotation.transform_vector(&(start - center))
+                    })
+                    .collect()
+            }
+            fj::Path::Helix(helix) => {
+                let angle = 2. * PI * helix.turns;
+
+                let n = number_of_segments(
+                    helix.radius.abs() + profile_radius,
+                    angle,
+                );
+                (0..=n)
+                    .map(|i| {
+                        let angle = angle / n as f64 * i as f64;
+                        let (sin, cos) = angle.sin_cos();
+
+                        point![
+                            helix.radius * cos,
+                            helix.radius * sin,
+                            helix.pitch * angle / (2. * PI)
+                        ]
+                    })
+                    .collect()
+            }
+            fj::Path::Polyline(polyline) => {
+                polyline.to_points().into_iter().map(Point::from).collect()
+            }
+        };
+
+        // Segments of zero length have no direction, and can't be used to
+        // rotate the profile.
+        points.dedup_by(|a, b| (*a - *b).magnitude() < f64::EPSILON);
+
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(points.len() >= 2, "A sweep path must have a length.");
+
+        let mut rotations: Vec<UnitQuaternion<f64>> = Vec::new();
+        for segment in points.windows(2) {
+            let direction = segment[1] - segment[0];
+
+            let rotation = match rotations.last() {
+                Some(previous) => {
+                    let previous_direction = previous * Vector::z();
+                    let rotation = UnitQuaternion::rotation_between(
+                        &previous_direction,
+                        &direction,
+                    )
+                    .expect("A sweep path must not reverse its direction.");
+
+                    rotation * previous
+                }
+                None => {
+                    UnitQuaternion::rotation_between(&Vector::z(), &direction)
+                        .unwrap_or_else(|| {
+                            // The path points in negative z direction.
+                            UnitQuaternion::from_axis_angle(
+                                &Vector::x_axis(),
+                                PI,
+                            )
+                        })
+                }
+            };
+
+            rotations.push(rotation);
+        }
+
+        Self { points, rotations }
+    }
+
+    /// Place a point of the profile at a point of the path
+    ///
+    /// Between two segments, the profile is placed in the plane that bisects
+    /// the angle between them. This miters the swept shape at corners.
+    fn place(&self, i: usize, point: Point<3>) -> Point<3> {
+        let segment = i.saturating_sub(1);
+        let point = self.points[i] + self.rotations[segment] * point.coords;
+
+        if i == 0 || i == self.points.len() - 1 {
+            return point;
+        }
+
+        // Project the point along the incoming segment into the miter plane.
+        let incoming = self.direction(i - 1);
+        let normal = incoming + self.direction(i);
+        let distance = (point - self.points[i]).dot(&normal);
+
+        point - incoming * (distance / incoming.dot(&normal))
+    }
+
+    /// The isometry that moves the profile to a point of the path
+    ///
+    /// Only valid for the first and last point, as there's no miter at those.
+    fn isometry(&self, i: usize) -> Isometry<f64> {
+        let segment = i.saturating_sub(1);
+        Isometry::from_parts(
+            self.points[i].coords.into(),
+            self.rotations[segment],
+        )
+    }
+
+    fn direction(&self, segment: usize) -> Vector<3> {
+        (self.points[segment + 1] - self.points[segment]).normalize()
+    }
+}
+
+/// Compute the circle that an arc is a part of
+///
+/// Returns its center, its radius, and the normalized axis.
+fn arc_geometry(arc: &fj::ArcPath) -> (Point<3>, f64, Vector<3>) {
+    let axis = Vector::from(arc.axis).normalize();
+
+    let center = Point::from(arc.center);
+    let start = Point::from(arc.start) - center;
+
+    let center = center + axis * axis.dot(&start);
+    let radius = (start - axis * axis.dot(&start)).magnitude();
+
+    (center, radius, axis)
+}
+
+/// Compute the maximum distance of a profile from the path
+fn profile_radius(aabb: &AABB) -> f64 {
+    let x = aabb.mins.x.abs().max(aabb.maxs.x.abs());
+    let y = aabb.mins.y.abs().max(aabb.maxs.y.abs());
+
+    (x * x + y * y).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::point;
+
+    use crate::{
+        kernel::Shape as _,
+        math::{Point, Vector},
+    };
+
+    use super::SweepPath;
+
+    const TOLERANCE: f64 = 0.001;
+
+    #[test]
+    fn frame_follows_path_with_minimal_rotation() {
+        let path = SweepPath::new(&corner(), TOLERANCE, 0.);
+
+        // The profile starts out in the x-y plane, as the path starts in
+        // positive z direction.
+        assert_approx_vector(path.rotations[0] * Vector::z(), Vector::z());
+        assert_approx_vector(path.rotations[0] * Vector::x(), Vector::x());
+
+        // At the corner, the profile is rotated around the y-axis only. Its
+        // y-axis isn't affected, so the profile isn't twisted.
+        let rotation = path.rotations[1];
+        assert_approx_vector(rotation * Vector::z(), Vector::x());
+        assert_approx_vector(rotation * Vector::y(), Vector::y());
+        assert_approx_vector(rotation * Vector::x(), -Vector::z());
+    }
+
+    #[test]
+    fn frame_along_helix_does_not_twist() {
+        let helix = fj::Helix {
+            radius: 2.,
+            pitch: 1.,
+            turns: 1.,
+        };
+        let path = SweepPath::new(&helix.into(), TOLERANCE, 0.);
+
+        // Each rotation differs from the previous one only by the angle
+        // between their segments.
+        for i in 1..path.rotations.len() {
+            let difference =
+                path.rotations[i] * path.rotations[i - 1].inverse();
+            let angle = path.direction(i - 1).angle(&path.direction(i));
+
+            assert!((difference.angle() - angle).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn profile_is_mitered_at_corners() {
+        let path = SweepPath::new(&corner(), TOLERANCE, 0.);
+
+        // The miter plane bisects the corner. Points on the inside and outside
+        // of the corner are moved along the incoming segment, onto that plane.
+        assert_approx(path.place(1, point![0.5, 0., 0.]), point![0.5, 0., 0.5]);
+        assert_approx(
+            path.place(1, point![-0.5, 0., 0.]),
+            point![-0.5, 0., 1.5],
+        );
+
+        // Points on the axis of the corner aren't moved.
+        assert_approx(path.place(1, point![0., 0.5, 0.]), point![0., 0.5, 1.]);
+
+        // There's no miter at the ends of the path.
+        assert_approx(path.place(0, point![0.5, 0., 0.]), point![0.5, 0., 0.]);
+        assert_approx(path.place(2, point![0.5, 0., 0.]), point![1., 0., 0.5]);
+    }
+
+    #[test]
+    fn edges_of_sweep_around_corner() {
+        let square = fj::Sketch::from_points(vec![
+            [-0.5, -0.5],
+            [0.5, -0.5],
+            [0.5, 0.5],
+            [-0.5, 0.5],
+        ]);
+        let sweep = fj::PathSweep {
+            shape: square.into(),
+            path: corner(),
+        };
+
+        let aabb = sweep.edges().bounding_volume();
+
+        assert_approx(aabb.mins, point![-0.5, -0.5, 0.]);
+        assert_approx(aabb.maxs, point![1., 0.5, 1.5]);
+
+        // The square at the start and end, and the seams its corners trace.
+        // The seams bend at the corner of the path.
+        assert_eq!(sweep.edges().cycles.len(), 2 + 4);
+        assert_eq!(sweep.vertices().len(), 4 + 4 + 4);
+    }
+
+    /// A path that goes up along the z-axis, then along the x-axis
+    fn corner() -> fj::Path {
+        fj::Polyline::from_points(vec![
+            [0., 0., 0.],
+            [0., 0., 1.],
+            [1., 0., 1.],
+        ])
+        .into()
+    }
+
+    fn assert_approx(a: Point<3>, b: Point<3>) {
+        assert!((a - b).magnitude() < 1e-9, "{a} != {b}");
+    }
+
+    fn assert_approx_vector(a: Vector<3>, b: Vector<3>) {
+        assert_approx(Point::from(a), Point::from(b));
+    }
+}
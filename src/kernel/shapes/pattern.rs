@@ -0,0 +1,262 @@
+use parry3d_f64::{
+    bounding_volume::{BoundingVolume as _, AABB},
+    math::Isometry,
+};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::{csg, feature_edges},
+        topology::{edges::Edges, faces::Faces},
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::CircularPattern {
+    fn bounding_volume(&self) -> AABB {
+        bounding_volume(
+            &self.shape,
+            &circular(self.axis.into(), self.angle, self.count),
+        )
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let copies = circular(self.axis.into(), self.angle, self.count);
+        faces(&self.shape, &copies, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let copies = circular(self.axis.into(), self.angle, self.count);
+        edges(self, &self.shape, &copies)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+impl Shape for fj::LinearPattern {
+    fn bounding_volume(&self) -> AABB {
+        bounding_volume(&self.shape, &linear(self.offset.into(), self.count))
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let copies = linear(self.offset.into(), self.count);
+        faces(&self.shape, &copies, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let copies = linear(self.offset.into(), self.count);
+        edges(self, &self.shape, &copies)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Compute the transformations of the copies in a circular pattern
+pub fn circular(axis: Vector<3>, angle: f64, count: u32) -> Vec<Isometry<f64>> {
+    let axis = axis.normalize();
+    (0..count)
+        .map(|i| Isometry::new(Vector::zeros(), axis * angle * f64::from(i)))
+        .collect()
+}
+
+/// Compute the transformations of the copies in a linear pattern
+pub fn linear(offset: Vector<3>, count: u32) -> Vec<Isometry<f64>> {
+    (0..count)
+        .map(|i| Isometry::new(offset * f64::from(i), Vector::zeros()))
+        .collect()
+}
+
+/// Compute the bounding volume of all copies of a shape
+pub fn bounding_volume(shape: &impl Shape, copies: &[Isometry<f64>]) -> AABB {
+    let aabb = shape.bounding_volume();
+
+    copies
+        .iter()
+        .map(|copy| aabb.transform_by(copy))
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+}
+
+/// Indicates whether any copies of a shape might overlap
+///
+/// Copies whose bounding volumes don't intersect can't overlap, which means
+/// they don't need to be merged.
+pub fn copies_overlap(shape: &impl Shape, copies: &[Isometry<f64>]) -> bool {
+    let aabb = shape.bounding_volume();
+    let aabbs: Vec<_> =
+        copies.iter().map(|copy| aabb.transform_by(copy)).collect();
+
+    aabbs
+        .iter()
+        .enumerate()
+        .any(|(i, a)| aabbs[i + 1..].iter().any(|b| a.intersects(b)))
+}
+
+/// Compute the faces of all copies of a shape
+///
+/// The faces of the shape are computed once, then transformed for each copy.
+fn faces(
+    shape: &impl Shape,
+    copies: &[Isometry<f64>],
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    let faces = shape.faces(tolerance, debug_info);
+
+    if !copies_overlap(shape, copies) {
+        return Faces(
+            copies
+                .iter()
+                .flat_map(|copy| faces.clone().transform(copy).0)
+                .collect(),
+        );
+    }
+
+    // Copies that overlap need to be merged.
+    let mut copies = copies.iter().map(|copy| faces.clone().transform(copy));
+    let first = copies.next().unwrap_or(Faces(Vec::new()));
+
+    copies.fold(first, |a, b| csg::union(a, b, tolerance, debug_info))
+}
+
+/// Compute the edges of all copies of a shape
+fn edges(
+    pattern: &impl Shape,
+    shape: &impl Shape,
+    copies: &[Isometry<f64>],
+) -> Edges {
+    if copies_overlap(shape, copies) {
+        return feature_edges::of_shape(pattern);
+    }
+
+    let edges = shape.edges();
+    let cycles = copies
+        .iter()
+        .flat_map(|copy| edges.clone().transform(copy).cycles)
+        .collect();
+
+    Edges { cycles }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{assert_approx, volume, TOLERANCE},
+            topology::faces::{Face, Faces},
+            Shape as _,
+        },
+        math::Point,
+    };
+
+    #[test]
+    fn linear_pattern_of_separate_copies() {
+        let shape: fj::Shape3d = cuboid([0., 0.], [1., 1.])
+            .linear_pattern([2., 0., 0.], 3)
+            .into();
+
+        // The faces of the copies are kept as they are.
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 3 * 6);
+        assert!(!has_triangles(&faces));
+        assert_eq!(shape.edges().cycles.len(), 3 * 2);
+
+        assert_approx(volume(&shape), 3., 1e-9);
+        assert_bounding_volume(&shape, [[0., 0., 0.], [5., 1., 1.]]);
+    }
+
+    #[test]
+    fn linear_pattern_of_overlapping_copies() {
+        let shape: fj::Shape3d = cuboid([0., 0.], [1., 1.])
+            .linear_pattern([0.5, 0., 0.], 3)
+            .into();
+
+        // The copies are merged. Only the faces at both ends aren't touched by
+        // another copy, and are kept. The rest becomes a triangle mesh.
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 2 + 1);
+        assert!(has_triangles(&faces));
+
+        assert_approx(volume(&shape), 2., 1e-9);
+        assert_bounding_volume(&shape, [[0., 0., 0.], [2., 1., 1.]]);
+    }
+
+    #[test]
+    fn circular_pattern_of_separate_copies() {
+        let shape: fj::Shape3d = cuboid([2., -0.5], [1., 1.])
+            .circular_pattern([0., 0., 1.], PI / 2., 4)
+            .into();
+
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 4 * 6);
+        assert!(!has_triangles(&faces));
+
+        assert_approx(volume(&shape), 4., 1e-9);
+        assert_bounding_volume(&shape, [[-3., -3., 0.], [3., 3., 1.]]);
+    }
+
+    #[test]
+    fn circular_pattern_of_overlapping_copies() {
+        // Four bars that meet at the axis, each overlapping its neighbors in a
+        // 0.5x0.5 square.
+        let shape: fj::Shape3d = cuboid([0., -0.5], [2., 1.])
+            .circular_pattern([0., 0., 1.], PI / 2., 4)
+            .into();
+
+        // The outer ends of the bars are kept, everything else becomes a
+        // triangle mesh.
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 4 + 1);
+        assert!(has_triangles(&faces));
+
+        assert_approx(volume(&shape), 4. * 2. - 4. * 0.25, 1e-9);
+        assert_bounding_volume(&shape, [[-2., -2., 0.], [2., 2., 1.]]);
+    }
+
+    fn cuboid([x, y]: [f64; 2], [w, h]: [f64; 2]) -> fj::Sweep {
+        fj::Sketch::from_points(vec![
+            [x, y],
+            [x + w, y],
+            [x + w, y + h],
+            [x, y + h],
+        ])
+        .sweep(1.)
+    }
+
+    fn faces(shape: &fj::Shape3d) -> Faces {
+        shape.faces(TOLERANCE, &mut DebugInfo::new())
+    }
+
+    fn has_triangles(faces: &Faces) -> bool {
+        faces
+            .0
+            .iter()
+            .any(|face| matches!(face, Face::Triangles(_)))
+    }
+
+    fn assert_bounding_volume(
+        shape: &fj::Shape3d,
+        [mins, maxs]: [[f64; 3]; 2],
+    ) {
+        let aabb = shape.bounding_volume();
+        for (actual, expected) in [(aabb.mins, mins), (aabb.maxs, maxs)] {
+            let expected: Point<3> = expected.into();
+            assert!(
+                (actual - expected).magnitude() < 1e-9,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+}
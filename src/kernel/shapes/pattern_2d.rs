@@ -0,0 +1,209 @@
+use parry3d_f64::{bounding_volume::AABB, math::Isometry};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        topology::{
+            edges::Edges,
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+use super::pattern::{bounding_volume, circular, copies_overlap, linear};
+
+impl Shape for fj::CircularPattern2d {
+    fn bounding_volume(&self) -> AABB {
+        bounding_volume(
+            &self.shape,
+            &circular(Vector::z(), self.angle, self.count),
+        )
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let copies = circular(Vector::z(), self.angle, self.count);
+        faces(&self.shape, &copies, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let copies = circular(Vector::z(), self.angle, self.count);
+        edges(self, &self.shape, &copies)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+impl Shape for fj::LinearPattern2d {
+    fn bounding_volume(&self) -> AABB {
+        bounding_volume(&self.shape, &linear(offset(self), self.count))
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let copies = linear(offset(self), self.count);
+        faces(&self.shape, &copies, tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        let copies = linear(offset(self), self.count);
+        edges(self, &self.shape, &copies)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+fn offset(pattern: &fj::LinearPattern2d) -> Vector<3> {
+    let [x, y] = pattern.offset;
+    Vector::from([x, y, 0.])
+}
+
+/// Compute the faces of all copies of a shape
+///
+/// The faces of the shape are computed once, then transformed for each copy.
+fn faces(
+    shape: &impl Shape,
+    copies: &[Isometry<f64>],
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    let faces = shape.faces(tolerance, debug_info);
+    let overlap = copies_overlap(shape, copies);
+    let copies = copies.iter().map(|copy| transform(&faces, copy));
+
+    if !overlap {
+        return Faces(copies.flat_map(|copy| copy.0).collect());
+    }
+
+    // Copies that overlap need to be merged.
+    copies
+        .reduce(|a, b| csg_2d::union(a, b, tolerance))
+        .unwrap_or(Faces(Vec::new()))
+}
+
+/// Transform the edges of 2-dimensional faces
+///
+/// The surface is left as it is, as all 2-dimensional faces need to be in the
+/// same surface.
+fn transform(faces: &Faces, transform: &Isometry<f64>) -> Faces {
+    let faces = faces
+        .0
+        .iter()
+        .map(|face| match face {
+            Face::Face { edges, surface } => Face::Face {
+                edges: edges.clone().transform(transform),
+                surface: surface.clone(),
+            },
+            Face::Triangles(_) => {
+                // None of the 2D types use the triangles representation.
+                unreachable!()
+            }
+        })
+        .collect();
+
+    Faces(faces)
+}
+
+/// Compute the edges of all copies of a shape
+fn edges(
+    pattern: &impl Shape,
+    shape: &impl Shape,
+    copies: &[Isometry<f64>],
+) -> Edges {
+    if copies_overlap(shape, copies) {
+        return csg_2d::shape_edges(pattern);
+    }
+
+    let edges = shape.edges();
+    let cycles = copies
+        .iter()
+        .flat_map(|copy| edges.clone().transform(copy).cycles)
+        .collect();
+
+    Edges { cycles }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{area, assert_approx, cycles, TOLERANCE},
+            topology::faces::Faces,
+            Shape as _,
+        },
+    };
+
+    #[test]
+    fn linear_pattern_of_separate_copies() {
+        let shape: fj::Shape2d = rectangle([0., 0.], [1., 1.])
+            .linear_pattern([2., 0.], 3)
+            .into();
+
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 3);
+        assert_eq!(shape.edges().cycles.len(), 3);
+        assert_approx(area(&faces), 3., 1e-9);
+    }
+
+    #[test]
+    fn linear_pattern_of_overlapping_copies() {
+        let shape: fj::Shape2d = rectangle([0., 0.], [1., 1.])
+            .linear_pattern([0.5, 0.], 3)
+            .into();
+
+        // The copies are merged into a single face, with a single boundary.
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 1);
+        assert_eq!(cycles(&faces), 1);
+        assert_eq!(shape.edges().cycles.len(), 1);
+        assert_approx(area(&faces), 2., 1e-9);
+    }
+
+    #[test]
+    fn circular_pattern_of_separate_copies() {
+        let shape: fj::Shape2d = rectangle([2., -0.5], [1., 1.])
+            .circular_pattern(PI / 2., 4)
+            .into();
+
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 4);
+        assert_approx(area(&faces), 4., 1e-9);
+    }
+
+    #[test]
+    fn circular_pattern_of_overlapping_copies() {
+        // Four bars that meet at the center, each overlapping its neighbors in
+        // a 0.5x0.5 square.
+        let shape: fj::Shape2d = rectangle([0., -0.5], [2., 1.])
+            .circular_pattern(PI / 2., 4)
+            .into();
+
+        let faces = faces(&shape);
+        assert_eq!(faces.0.len(), 1);
+        assert_eq!(cycles(&faces), 1);
+        assert_approx(area(&faces), 4. * 2. - 4. * 0.25, 1e-9);
+    }
+
+    fn rectangle([x, y]: [f64; 2], [w, h]: [f64; 2]) -> fj::Sketch {
+        fj::Sketch::from_points(vec![
+            [x, y],
+            [x + w, y],
+            [x + w, y + h],
+            [x, y + h],
+        ])
+    }
+
+    fn faces(shape: &fj::Shape2d) -> Faces {
+        shape.faces(TOLERANCE, &mut DebugInfo::new())
+    }
+}
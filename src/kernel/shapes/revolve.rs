@@ -0,0 +1,277 @@
+use std::f64::consts::PI;
+
+use nalgebra::point;
+use parry3d_f64::{bounding_volume::AABB, math::Isometry};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        geometry::{surfaces::RevolvedCurve, Circle, Curve, Line, Surface},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Revolve {
+    fn bounding_volume(&self) -> AABB {
+        let (axis, _) = axis_and_angle(self);
+        let aabb = self.shape.bounding_volume();
+
+        // The revolved shape is contained within the convex hull of the circles
+        // that the corners of the original shape's bounding box trace during
+        // the revolution.
+        let corners = [
+            point![aabb.mins.x, aabb.mins.y, 0.],
+            point![aabb.maxs.x, aabb.mins.y, 0.],
+            point![aabb.maxs.x, aabb.maxs.y, 0.],
+            point![aabb.mins.x, aabb.maxs.y, 0.],
+        ];
+
+        let mut mins = Point::from([f64::MAX; 3]);
+        let mut maxs = Point::from([f64::MIN; 3]);
+        for corner in corners {
+            let center = Point::from(axis * axis.dot(&corner.coords));
+            let radius = (corner - center).magnitude();
+
+            // The extent of a circle along any coordinate axis depends on the
+            // angle between that coordinate axis and the circle's normal.
+            let extent = axis.map(|a| radius * (1. - a * a).max(0.).sqrt());
+
+            mins = mins.inf(&(center - extent));
+            maxs = maxs.sup(&(center + extent));
+        }
+
+        AABB { mins, maxs }
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let (axis, angle) = axis_and_angle(self);
+
+        // The orientation of the side faces is derived from the orientation
+        // of the original edges, so we need to make sure those are oriented
+        // consistently.
+        let original_faces =
+            csg_2d::orient(self.shape.faces(tolerance, debug_info), tolerance);
+
+        // The original shape lies on one side of the axis. Depending on which
+        // side that is, the revolution moves it in positive or negative z
+        // direction at the start of the revolution.
+        let center = self.shape.bounding_volume().center();
+        let radial = center.coords - axis * axis.dot(&center.coords);
+        let moves_up = axis.cross(&radial).z > 0.;
+
+        let mut faces = Vec::new();
+
+        for face in &original_faces.0 {
+            let edges = match face {
+                Face::Face { edges, .. } => edges,
+                Face::Triangles(_) => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
+            };
+
+            for edge in edges.cycles.iter().flat_map(|cycle| &cycle.edges) {
+                // Revolved surfaces can only revolve whole curves. Arcs are
+                // revolved as the lines that approximate them. Those lines are
+                // oriented like the edge already, so they don't need to be
+                // reversed.
+                let curves = if edge.is_arc() {
+                    edge.approx(tolerance)
+                        .segments
+                        .into_iter()
+                        .map(|segment| {
+                            let line = Line {
+                                a: segment.a,
+                                b: segment.b,
+                            };
+                            (Curve::Line(line), false)
+                        })
+                        .collect()
+                } else {
+                    vec![(edge.curve, edge.reverse)]
+                };
+
+                for (curve, reverse) in curves {
+                    let surface = RevolvedCurve {
+                        curve,
+                        origin: Point::origin(),
+                        axis,
+                        angle,
+                        // The surface normal of a revolved curve points
+                        // outward, if the revolution moves the curve in
+                        // negative z direction and the curve is oriented
+                        // counter-clockwise.
+                        reverse: reverse ^ moves_up,
+                    };
+
+                    faces.push(Face::Face {
+                        edges: Edges { cycles: Vec::new() },
+                        surface: Surface::Revolved(surface),
+                    });
+                }
+            }
+        }
+
+        if angle < 2. * PI {
+            // A partial revolution needs faces to close it off at the start and
+            // end of the revolution. The original faces point in positive z
+            // direction, so one of those needs to be reversed.
+            let start_faces = original_faces.clone();
+            let end_faces =
+                original_faces.transform(&Isometry::rotation(axis * angle));
+
+            let (start_faces, end_faces) = if moves_up {
+                (start_faces.reverse(), end_faces)
+            } else {
+                (start_faces, end_faces.reverse())
+            };
+
+            faces.extend(start_faces.0);
+            faces.extend(end_faces.0);
+        }
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        let (axis, angle) = axis_and_angle(self);
+        let rotation = Isometry::rotation(axis * angle);
+
+        let profile = self.shape.edges();
+        let mut cycles = Vec::new();
+
+        if angle < 2. * PI {
+            // A partial revolution is closed off by the original shape, at the
+            // start and end of the revolution.
+            cycles.extend(profile.cycles.clone());
+            cycles.extend(profile.clone().transform(&rotation).cycles);
+        }
+
+        // Each vertex of the original shape traces a seam between two side
+        // faces, unless it is on the axis.
+        for vertex in profile.vertices() {
+            let center = Point::from(axis * axis.dot(&vertex.coords));
+            let radius = vertex - center;
+            if radius.magnitude() < 1e-7 {
+                continue;
+            }
+
+            let curve = Curve::Circle(Circle {
+                center,
+                radius,
+                normal: axis,
+            });
+            let edge = if angle < 2. * PI {
+                let end = rotation.transform_point(&vertex);
+                Edge::with_vertices(curve, [vertex, end])
+            } else {
+                Edge::new(curve)
+            };
+
+            cycles.push(Cycle { edges: vec![edge] });
+        }
+
+        Edges { cycles }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Compute the normalized axis and angle of the revolution
+///
+/// The returned angle is always positive, and no larger than a full
+/// revolution. If the original angle was negative, the axis is flipped
+/// instead.
+fn axis_and_angle(revolve: &fj::Revolve) -> (Vector<3>, f64) {
+    let axis = Vector::from(revolve.axis).normalize();
+    let angle = revolve.angle.abs().min(2. * PI);
+
+    if revolve.angle < 0. {
+        (-axis, angle)
+    } else {
+        (axis, angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use nalgebra::point;
+
+    use crate::{kernel::Shape as _, math::Point};
+
+    fn revolve(angle: f64) -> fj::Revolve {
+        let rectangle = fj::Sketch::from_points(vec![
+            [1., 0.],
+            [2., 0.],
+            [2., 1.],
+            [1., 1.],
+        ]);
+
+        fj::Revolve {
+            shape: rectangle.into(),
+            axis: [0., 1., 0.],
+            angle,
+        }
+    }
+
+    fn assert_approx(a: Point<3>, b: Point<3>) {
+        assert!((a - b).magnitude() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn edges_of_partial_revolution() {
+        // A quarter revolution around the y-axis moves the rectangle from the
+        // positive x-axis to the negative z-axis.
+        let revolve = revolve(PI / 2.);
+
+        let edges = revolve.edges();
+        let aabb = edges.bounding_volume();
+
+        assert_approx(aabb.mins, point![0., 0., -2.]);
+        assert_approx(aabb.maxs, point![2., 1., 0.]);
+
+        // The rectangle at the start and end, and the arcs its corners trace.
+        assert_eq!(edges.cycles.len(), 2 + 4);
+        assert_eq!(revolve.vertices().len(), 2 * 4);
+    }
+
+    #[test]
+    fn edges_of_full_revolution() {
+        let revolve = revolve(2. * PI);
+
+        // Only the circles that the corners of the rectangle trace are left.
+        assert_eq!(revolve.edges().cycles.len(), 4);
+        assert!(revolve.vertices().is_empty());
+    }
+
+    #[test]
+    fn edges_of_transformed_and_patterned_revolution() {
+        let shape: fj::Shape3d = revolve(2. * PI).into();
+
+        let transform = fj::Transform::translation(shape.clone(), [0., 0., 5.]);
+        let aabb = transform.edges().bounding_volume();
+
+        assert_approx(aabb.mins, point![-2., 0., 3.]);
+        assert_approx(aabb.maxs, point![2., 1., 7.]);
+
+        let pattern = fj::LinearPattern {
+            shape,
+            offset: [5., 0., 0.],
+            count: 2,
+        };
+        let aabb = pattern.edges().bounding_volume();
+
+        assert_approx(aabb.mins, point![-2., 0., -2.]);
+        assert_approx(aabb.maxs, point![7., 1., 2.]);
+    }
+}
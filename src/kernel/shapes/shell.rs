@@ -0,0 +1,305 @@
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        geometry::Surface,
+        topology::{
+            edges::Edges,
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::Point,
+};
+
+use super::sweep::{
+    layers, layers_edges, layers_fit, straight_sweep, sweep_faces, Layer,
+};
+
+impl Shape for fj::Shell {
+    fn bounding_volume(&self) -> AABB {
+        self.shape.bounding_volume()
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let (sweep, outer, inner, open) = shell(self);
+
+        let outer = sweep_faces(&sweep.shape, &outer, tolerance, debug_info);
+        let inner = sweep_faces(&sweep.shape, &inner, tolerance, debug_info);
+
+        // The faces of the inner sweep bound the cavity, so they need to point
+        // into it.
+        let mut faces = outer.sides;
+        faces.extend(inner.sides.into_iter().map(Face::reverse));
+
+        let caps = outer.caps.into_iter().zip(inner.caps).zip(open);
+        for ((outer, inner), open) in caps {
+            if open {
+                // Where the shell is open, the outer and inner caps are in the
+                // same place. What remains is the area between their edges.
+                faces.extend(merge(outer, inner, tolerance));
+            } else {
+                faces.extend(outer.0);
+                faces.extend(inner.reverse().0);
+            }
+        }
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        let (sweep, outer, inner, _) = shell(self);
+
+        let mut edges = layers_edges(&sweep.shape, &outer);
+        edges
+            .cycles
+            .extend(layers_edges(&sweep.shape, &inner).cycles);
+
+        edges
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// Indicates whether the walls of a shell fit into the shape that is hollowed
+///
+/// Expects a shape that can be hollowed, which [`straight_sweep`] accepts.
+pub fn walls_fit(shell: &fj::Shell) -> bool {
+    let (sweep, outer, inner, _) = self::shell(shell);
+
+    // Walls at the closed ends of the shell move the inner layers towards each
+    // other. They must not meet or pass each other.
+    let direction = |layers: &[Layer]| layers[1].position - layers[0].position;
+    direction(&inner).dot(&direction(&outer)) > 0.
+        && layers_fit(&sweep.shape, &inner)
+}
+
+/// Compute the sweep and layers that define the shell
+///
+/// Returns the sweep, the layers of its outer and inner surfaces, and whether
+/// the shell is open at the first and last layer.
+fn shell(shell: &fj::Shell) -> (fj::Sweep, Vec<Layer>, Vec<Layer>, [bool; 2]) {
+    // Shells of other shapes are rejected when a model is loaded.
+    let sweep = straight_sweep(&shell.shape).expect(
+        "Only sweeps along the z-axis without draft, and cylinders, can be \
+        hollowed.",
+    );
+
+    let outer = layers(&sweep);
+    let [first, last] = [outer[0], outer[1]];
+
+    let [open_bottom, open_top] = match shell.open_faces {
+        fj::OpenFaces::None => [false, false],
+        fj::OpenFaces::Top => [false, true],
+        fj::OpenFaces::Bottom => [true, false],
+        fj::OpenFaces::TopAndBottom => [true, true],
+    };
+    let open = if last.position.z >= first.position.z {
+        [open_bottom, open_top]
+    } else {
+        [open_top, open_bottom]
+    };
+
+    let thickness = shell.thickness;
+    let length = (last.position - first.position).magnitude();
+
+    // The inner surface is moved inward by the thickness of the walls. At
+    // closed ends, it's also moved along the sweep.
+    let direction = (last.position - first.position) / length;
+    let inner = [(first, open[0], 1.), (last, open[1], -1.)]
+        .map(|(layer, open, sign)| {
+            let position = if open {
+                layer.position
+            } else {
+                layer.position + direction * thickness * sign
+            };
+
+            Layer {
+                position,
+                offset: thickness,
+            }
+        })
+        .to_vec();
+
+    (sweep, outer, inner, open)
+}
+
+/// Merge faces with the faces within them that cover the area to be removed
+///
+/// Moving the edges of a face inward doesn't necessarily result in one face
+/// per face, so each of the inner faces is merged into the outer face that
+/// contains it. Outer faces that don't contain an inner face remain as they
+/// are.
+fn merge(outer: Faces, inner: Faces, tolerance: f64) -> Vec<Face> {
+    let mut outer: Vec<_> = outer.0.into_iter().map(edges_of_cap).collect();
+
+    for face in inner.0 {
+        let (inner, _) = edges_of_cap(face);
+
+        // The faces of a shell are separated by its walls, so any point on the
+        // edges of an inner face is well within the outer face that contains
+        // it.
+        let point = inner.cycles.iter().find_map(|cycle| {
+            cycle.approx(tolerance).vertices.first().copied()
+        });
+        let point = match point {
+            Some(point) => point,
+            None => continue,
+        };
+
+        // Shells with walls that don't fit are rejected when a model is
+        // loaded, so this can't fail in a valid model.
+        let (edges, _) = outer
+            .iter_mut()
+            .find(|(edges, _)| contains(edges, point, tolerance))
+            .expect("Inner face of shell is not within its outer faces.");
+
+        edges.cycles.extend(inner.cycles);
+    }
+
+    outer
+        .into_iter()
+        .map(|(edges, surface)| Face::Face { edges, surface })
+        .collect()
+}
+
+fn edges_of_cap(face: Face) -> (Edges, Surface) {
+    match face {
+        Face::Face { edges, surface } => (edges, surface),
+        Face::Triangles(_) => {
+            // The caps of sweeps never use the triangles representation.
+            unreachable!()
+        }
+    }
+}
+
+/// Determine whether a point is within the area bounded by the edges of a cap
+///
+/// The caps of the sweeps that shells are made of are parallel to the x-y
+/// plane.
+fn contains(edges: &Edges, point: Point<3>, tolerance: f64) -> bool {
+    let segments = edges.approx(tolerance).segments;
+    csg_2d::contains_points(
+        segments
+            .iter()
+            .map(|segment| [segment.a.xy(), segment.b.xy()]),
+        point.xy(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::prelude::*;
+    use parry3d_f64::bounding_volume::BoundingVolume as _;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{assert_approx, volume, TOLERANCE},
+            topology::{edges::Edges, faces::Face},
+            Shape as _,
+        },
+    };
+
+    use super::merge;
+
+    #[test]
+    fn closed_shell() {
+        let shape: fj::Shape3d = square([0., 0.])
+            .sweep(2.)
+            .shell(0.5, fj::OpenFaces::None)
+            .into();
+
+        // The outer and inner surfaces each have six faces.
+        assert_eq!(faces(&shape), 6 + 6);
+        assert_approx(volume(&shape), 8. - 1., 1e-9);
+    }
+
+    #[test]
+    fn shell_open_at_top() {
+        let shape: fj::Shape3d = square([0., 0.])
+            .sweep(2.)
+            .shell(0.5, fj::OpenFaces::Top)
+            .into();
+
+        // The top face of the outer and inner surfaces are merged into one.
+        assert_eq!(faces(&shape), 4 + 4 + 2 + 1);
+        assert_approx(volume(&shape), 8. - 1.5, 1e-9);
+
+        let aabb = shape.bounding_volume();
+        assert_eq!(aabb.mins, [0., 0., 0.].into());
+        assert_eq!(aabb.maxs, [2., 2., 2.].into());
+    }
+
+    #[test]
+    fn shell_of_separate_faces_open_at_top_and_bottom() {
+        let shape: fj::Shape3d = fj::Union2d {
+            a: square([0., 0.]).into(),
+            b: square([3., 0.]).into(),
+        }
+        .sweep(1.)
+        .shell(0.5, fj::OpenFaces::TopAndBottom)
+        .into();
+
+        // Each inner face must be merged with the outer face that surrounds
+        // it, resulting in one face per square at each end.
+        assert_eq!(faces(&shape), 8 + 8 + 2 + 2);
+        assert_approx(volume(&shape), 2. * (4. - 1.), 1e-9);
+    }
+
+    #[test]
+    fn merge_matches_faces_by_containment() {
+        let outer = fj::Union2d {
+            a: square([0., 0.]).into(),
+            b: square([3., 0.]).into(),
+        };
+        let inner = fj::Union2d {
+            a: fj::Sketch::from_points(vec![
+                [1.5, 0.5],
+                [1.5, 1.5],
+                [0.5, 1.5],
+            ])
+            .into(),
+            b: fj::Sketch::from_points(vec![[3.5, 0.5], [4.5, 0.5], [4., 1.5]])
+                .into(),
+        };
+
+        let outer = outer.faces(TOLERANCE, &mut DebugInfo::new());
+        let mut inner = inner.faces(TOLERANCE, &mut DebugInfo::new());
+        inner.0.reverse();
+
+        for face in merge(outer, inner, TOLERANCE) {
+            let edges = match face {
+                Face::Face { edges, .. } => edges,
+                Face::Triangles(_) => unreachable!(),
+            };
+
+            // Each face keeps its own hole.
+            assert_eq!(edges.cycles.len(), 2);
+            let [a, b] = [&edges.cycles[0], &edges.cycles[1]].map(|cycle| {
+                Edges {
+                    cycles: vec![cycle.clone()],
+                }
+                .bounding_volume()
+            });
+            assert!(a.contains(&b) || b.contains(&a));
+        }
+    }
+
+    fn square([x, y]: [f64; 2]) -> fj::Sketch {
+        fj::Sketch::from_points(vec![
+            [x, y],
+            [x + 2., y],
+            [x + 2., y + 2.],
+            [x, y + 2.],
+        ])
+    }
+
+    fn faces(shape: &fj::Shape3d) -> usize {
+        shape.faces(TOLERANCE, &mut DebugInfo::new()).0.len()
+    }
+}
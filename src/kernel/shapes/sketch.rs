@@ -1,66 +1,147 @@
+use nalgebra::vector;
 use parry3d_f64::bounding_volume::AABB;
 
 use crate::{
     debug::DebugInfo,
     kernel::{
-        geometry::{Curve, Line, Surface},
+        algorithms::csg_2d,
+        geometry::{Bezier, Circle, Curve, Line, Surface},
         topology::{
-            edges::{Edge, Edges},
+            edges::{Cycle, Edge, Edges},
             faces::{Face, Faces},
         },
+        util::default_tolerance,
         Shape,
     },
-    math::Point,
+    math::{Point, Vector},
 };
 
 impl Shape for fj::Sketch {
     fn bounding_volume(&self) -> AABB {
-        AABB::from_points(&self.vertices())
+        if self.vertices().is_empty() {
+            return AABB::new(Point::origin(), Point::origin());
+        }
+
+        self.edges().bounding_volume()
     }
 
     fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
-        let edges = self.edges();
+        // Sketches are validated when the model is loaded, so there's no need
+        // to check the arrangement of their cycles here.
         let face = Face::Face {
-            edges,
+            edges: self.edges(),
             surface: Surface::x_y_plane(),
         };
         Faces(vec![face])
     }
 
     fn edges(&self) -> Edges {
-        let v = match self.vertices() {
-            vertices if vertices.is_empty() => vertices,
-            mut vertices => {
-                // Add the first vertex at the end again, to close the loop.
-                //
-                // This can't panic. This `match` expression makes sure that
-                // there are vertices.
-                vertices.push(vertices[0]);
-                vertices
-            }
-        };
+        let mut cycles: Vec<_> = self
+            .to_cycles()
+            .iter()
+            .filter(|cycle| !cycle.to_segments().is_empty())
+            .map(cycle)
+            .collect();
 
-        let mut edges = Vec::new();
-        for window in v.windows(2) {
-            // Can't panic, we passed `2` to `windows`.
-            //
-            // Can be cleaned up, once `array_windows` is stable.
-            let a = window[0];
-            let b = window[1];
+        // The orientation of the outer boundary is left as it is, but holes
+        // need to have the opposite orientation, for the interior of the face
+        // to be on the same side of all cycles.
+        if cycles.len() > 1 {
+            let tolerance = default_tolerance(
+                &Edges {
+                    cycles: cycles.clone(),
+                }
+                .bounding_volume(),
+            );
 
-            let line = Curve::Line(Line { a, b });
-            let edge = Edge::new(line);
-
-            edges.push(edge);
+            let outer_is_ccw = signed_area(&cycles[0], tolerance) > 0.;
+            for hole in &mut cycles[1..] {
+                if (signed_area(hole, tolerance) > 0.) == outer_is_ccw {
+                    hole.reverse();
+                }
+            }
         }
 
-        Edges::single_cycle(edges)
+        Edges { cycles }
     }
 
     fn vertices(&self) -> Vec<Point<3>> {
-        self.to_points()
-            .into_iter()
-            .map(|[x, y]| Point::from([x, y, 0.]))
+        self.to_cycles()
+            .iter()
+            .flat_map(|cycle| cycle.to_points())
+            .map(point)
             .collect()
     }
 }
+
+/// Create the cycle of edges for a cycle of sketch segments
+fn cycle(cycle: &fj::SketchCycle) -> Cycle {
+    let segments = cycle.to_segments();
+
+    let mut edges = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        // The last segment ends where the first one starts, closing the loop.
+        let end = segments[(i + 1) % segments.len()].start;
+
+        edges.push(edge(segment, end));
+    }
+
+    Cycle { edges }
+}
+
+/// Compute the signed area of a cycle in the x-y plane
+fn signed_area(cycle: &Cycle, tolerance: f64) -> f64 {
+    csg_2d::signed_area(
+        cycle
+            .approx(tolerance)
+            .segments
+            .into_iter()
+            .map(|segment| [segment.a.xy(), segment.b.xy()]),
+    )
+}
+
+/// Create the edge for a sketch segment that ends at `end`
+fn edge(segment: &fj::SketchSegment, end: [f64; 2]) -> Edge {
+    let [a, b] = [point(segment.start), point(end)];
+
+    match segment.kind {
+        fj::SegmentKind::Line | fj::SegmentKind::Bulge(0.) => {
+            return Edge::new(Curve::Line(Line { a, b }));
+        }
+        fj::SegmentKind::Bezier { control } => {
+            return Edge::new(Curve::Bezier(Bezier {
+                points: [a, point(control[0]), point(control[1]), b],
+            }));
+        }
+        fj::SegmentKind::Arc { .. }
+        | fj::SegmentKind::ArcWithRadius { .. }
+        | fj::SegmentKind::Bulge(_) => {}
+    }
+
+    // Panicking is not great, but as long as we don't have a real error
+    // handling mechanism, it will do.
+    let (center, counter_clockwise) = segment
+        .arc_center(end)
+        .expect("Arc can't be constructed from its parameters.");
+    let center = point(center);
+
+    let circle = Circle {
+        center,
+        radius: a - center,
+        normal: Vector::z(),
+    };
+
+    // Circles are always counter-clockwise. A clockwise arc is the reversed
+    // counter-clockwise arc between the same points.
+    if counter_clockwise {
+        Edge::with_vertices(Curve::Circle(circle), [a, b])
+    } else {
+        let mut edge = Edge::with_vertices(Curve::Circle(circle), [b, a]);
+        edge.reverse();
+        edge
+    }
+}
+
+fn point([x, y]: [f64; 2]) -> Point<3> {
+    Point::from(vector![x, y, 0.])
+}
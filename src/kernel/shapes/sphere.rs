@@ -0,0 +1,50 @@
+use nalgebra::point;
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{surfaces::Sphere, Surface},
+        topology::{
+            edges::Edges,
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::Point,
+};
+
+impl Shape for fj::Sphere {
+    fn bounding_volume(&self) -> AABB {
+        AABB {
+            mins: point![-self.radius, -self.radius, -self.radius],
+            maxs: point![self.radius, self.radius, self.radius],
+        }
+    }
+
+    fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(self.radius > 0., "A sphere must have a positive radius.");
+
+        let surface = Sphere {
+            center: Point::origin(),
+            radius: self.radius,
+            reverse: false,
+        };
+
+        Faces(vec![Face::Face {
+            edges: Edges { cycles: Vec::new() },
+            surface: Surface::Sphere(surface),
+        }])
+    }
+
+    fn edges(&self) -> Edges {
+        // Spheres consist of a single face without any edges.
+        Edges { cycles: Vec::new() }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        Vec::new()
+    }
+}
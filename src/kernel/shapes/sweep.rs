@@ -1,82 +1,1061 @@
 use std::f64::consts::PI;
 
-use nalgebra::vector;
-use parry3d_f64::{bounding_volume::AABB, math::Isometry};
+use parry3d_f64::{
+    bounding_volume::{BoundingVolume as _, AABB},
+    math::Isometry,
+};
 
 use crate::{
     debug::DebugInfo,
     kernel::{
+        algorithms::csg_2d,
+        geometry::{
+            surfaces::{Plane, RevolvedCurve},
+            Circle, Curve, Line, Surface,
+        },
+        shapes::on_plane,
         topology::{
-            edges::Edges,
+            edges::{Cycle, Edge, Edges},
             faces::{Face, Faces},
         },
+        util::default_tolerance,
         Shape,
     },
-    math::Point,
+    math::{Point, Vector},
 };
 
 impl Shape for fj::Sweep {
     fn bounding_volume(&self) -> AABB {
-        let mut aabb = self.shape.bounding_volume();
-        aabb.maxs.z = self.length;
-        aabb
+        layers_bounding_volume(&self.shape, &layers(self))
     }
 
     fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
-        let original_faces = self.shape.faces(tolerance, debug_info);
-
-        let bottom_faces = original_faces
-            .clone()
-            .transform(&Isometry::rotation(vector![PI, 0., 0.]));
-
-        let top_faces = original_faces.transform(&Isometry::translation(
-            0.0,
-            0.0,
-            self.length,
-        ));
-
-        // This will only work correctly, if the original shape consists of one
-        // edge. If there are more, this will create some kind of weird face
-        // chimera, a single face to represent all the side faces.
-        //
-        // It'll be even worse, if the original shape consists of multiple
-        // faces.
-        let approx = self.shape.edges().approx(tolerance);
-
-        let mut quads = Vec::new();
-        for segment in approx.segments {
-            let [v0, v1] = [segment.a, segment.b];
-            let [v3, v2] = {
-                let segment = segment.transformed(&Isometry::translation(
-                    0.0,
-                    0.0,
-                    self.length,
-                ));
-                [segment.a, segment.b]
+        layers_faces(&self.shape, &layers(self), tolerance, debug_info)
+    }
+
+    fn edges(&self) -> Edges {
+        layers_edges(&self.shape, &layers(self))
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// A cross-section of a sweep
+///
+/// Sweeps are defined by a sequence of layers. The swept shape is placed at
+/// each layer, with its edges moved inward by the layer's offset. Neighboring
+/// layers are connected by side faces.
+#[derive(Clone, Copy)]
+pub struct Layer {
+    /// The position of the layer
+    pub position: Vector<3>,
+
+    /// How far the edges are moved inward at this layer
+    pub offset: f64,
+}
+
+/// Compute the layers of a sweep
+pub fn layers(sweep: &fj::Sweep) -> Vec<Layer> {
+    let [start, end] = path(sweep);
+    let [offset_start, offset_end] = offsets(sweep);
+
+    vec![
+        Layer {
+            position: start,
+            offset: offset_start,
+        },
+        Layer {
+            position: end,
+            offset: offset_end,
+        },
+    ]
+}
+
+/// Compute the bounding volume of a sweep through the given layers
+pub fn layers_bounding_volume(shape: &fj::Shape2d, layers: &[Layer]) -> AABB {
+    let aabbs: Vec<_> = if layers.iter().all(|layer| layer.offset == 0.) {
+        let aabb = shape.bounding_volume();
+        layers.iter().map(|_| aabb).collect()
+    } else {
+        // With edges that are moved inward, the bounding volume depends on
+        // how the corners of the shape move. The only way to find out is to
+        // compute the moved edges.
+        let faces = original_faces(shape, layers);
+        layers
+            .iter()
+            .map(|layer| bounding_volume(&offset(faces.clone(), layer.offset)))
+            .collect()
+    };
+
+    aabbs
+        .into_iter()
+        .zip(layers)
+        .map(|(aabb, layer)| aabb.transform_by(&translation(layer.position)))
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+}
+
+/// Compute the faces of a sweep through the given layers
+///
+/// There must be at least two layers.
+pub fn layers_faces(
+    shape: &fj::Shape2d,
+    layers: &[Layer],
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> Faces {
+    sweep_faces(shape, layers, tolerance, debug_info).into_faces()
+}
+
+/// The faces of a sweep, sorted by where they are
+pub struct SweepFaces {
+    /// The faces at the first and last layer, pointing outward
+    pub caps: [Faces; 2],
+
+    /// The faces that connect the layers
+    pub sides: Vec<Face>,
+}
+
+impl SweepFaces {
+    /// Merge all faces into a single collection
+    pub fn into_faces(self) -> Faces {
+        let [first, last] = self.caps;
+
+        let mut faces = Vec::new();
+        faces.extend(first.0);
+        faces.extend(last.0);
+        faces.extend(self.sides);
+
+        Faces(faces)
+    }
+}
+
+/// Compute the faces of a sweep through the given layers, sorted by where they
+/// are
+///
+/// There must be at least two layers.
+pub fn sweep_faces(
+    shape: &fj::Shape2d,
+    layers: &[Layer],
+    tolerance: f64,
+    debug_info: &mut DebugInfo,
+) -> SweepFaces {
+    // The orientation of the side faces is derived from the orientation of the
+    // original edges, so we need to make sure those are oriented consistently.
+    let mut original_faces =
+        csg_2d::orient(shape.faces(tolerance, debug_info), tolerance);
+
+    // Circles that are swept along the z-axis result in cylinders or cones,
+    // which can be represented exactly. If the sweep goes in any other
+    // direction, the circles are approximated by lines, resulting in a number
+    // of planar side faces instead. The same goes for ellipses, regardless of
+    // the direction.
+    //
+    // Moving arcs inward, together with the lines they connect to, is not
+    // supported. If any of the layers has an offset, they need to be
+    // approximated too.
+    let along_z = layers.iter().all(|layer| {
+        (layer.position - layers[0].position).xy().magnitude() == 0.
+    });
+    let offset_arcs = layers.iter().any(|layer| layer.offset != 0.)
+        && has_arcs(&original_faces);
+    if !along_z || offset_arcs || has_free_form_curves(&original_faces) {
+        original_faces = original_faces.to_lines(tolerance);
+    }
+
+    let layer_faces: Vec<_> = layers
+        .iter()
+        .map(|layer| {
+            offset(original_faces.clone(), layer.offset)
+                .transform(&translation(layer.position))
+        })
+        .collect();
+
+    // The original faces point in positive z direction. Whichever of the
+    // bottom and top faces is at the lower end of the sweep needs to point the
+    // other way.
+    let upwards = layers[layers.len() - 1].position.z >= layers[0].position.z;
+
+    let mut side_faces = Vec::new();
+    for pair in layer_faces.windows(2) {
+        for (bottom, top) in pair[0].0.iter().zip(&pair[1].0) {
+            let (bottom, top) = match (bottom, top) {
+                (
+                    Face::Face { edges: bottom, .. },
+                    Face::Face { edges: top, .. },
+                ) => (bottom, top),
+                _ => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
             };
 
-            quads.push([v0, v1, v2, v3]);
+            // Moving the edges doesn't change the structure of the faces, so
+            // the edges of the bottom and top faces correspond to each other.
+            let edges = bottom
+                .cycles
+                .iter()
+                .flat_map(|cycle| &cycle.edges)
+                .zip(top.cycles.iter().flat_map(|cycle| &cycle.edges));
+
+            for (bottom, top) in edges {
+                side_faces.push(side_face(bottom, top, upwards));
+            }
         }
+    }
 
-        let mut side_face = Vec::new();
-        for [v0, v1, v2, v3] in quads {
-            side_face.push([v0, v1, v2].into());
-            side_face.push([v0, v2, v3].into());
+    let mut layer_faces = layer_faces.into_iter();
+    let first_faces = layer_faces.next().expect("Expected first layer");
+    let last_faces = layer_faces.last().expect("Expected last layer");
+
+    let caps = if upwards {
+        [first_faces.reverse(), last_faces]
+    } else {
+        [first_faces, last_faces.reverse()]
+    };
+
+    SweepFaces {
+        caps,
+        sides: side_faces,
+    }
+}
+
+/// Compute the edges of a sweep through the given layers
+///
+/// Those are the edges of the faces at the first and last layer.
+pub fn layers_edges(shape: &fj::Shape2d, layers: &[Layer]) -> Edges {
+    let faces = original_faces(shape, layers);
+
+    let mut cycles = Vec::new();
+    for layer in [layers.first(), layers.last()].into_iter().flatten() {
+        let faces = offset(faces.clone(), layer.offset)
+            .transform(&translation(layer.position));
+
+        for face in faces.0 {
+            if let Face::Face { edges, .. } = face {
+                cycles.extend(edges.cycles);
+            }
         }
+    }
 
-        let mut faces = Vec::new();
-        faces.extend(bottom_faces.0);
-        faces.extend(top_faces.0);
-        faces.push(Face::Triangles(side_face));
+    Edges { cycles }
+}
 
-        Faces(faces)
+/// Represent a shape as a straight sweep along the z-axis, if possible
+///
+/// Returns `None`, if the shape is neither such a sweep without a draft angle,
+/// nor a shape that is equivalent to one.
+pub fn straight_sweep(shape: &fj::Shape3d) -> Option<fj::Sweep> {
+    match shape {
+        fj::Shape3d::Cylinder(cylinder) => Some(fj::Sweep::from_length(
+            fj::Circle {
+                radius: cylinder.radius,
+            },
+            cylinder.height,
+        )),
+        fj::Shape3d::Sweep(sweep) => {
+            let along_z = Vector::from(sweep.direction).xy().magnitude() == 0.;
+
+            (along_z && sweep.draft == 0.).then(|| sweep.clone())
+        }
+        _ => None,
     }
+}
 
-    fn edges(&self) -> Edges {
-        todo!()
+/// Indicates whether the draft angle of a sweep is small enough for its length
+pub fn draft_fits(sweep: &fj::Sweep) -> bool {
+    sweep.draft == 0. || layers_fit(&sweep.shape, &layers(sweep))
+}
+
+/// Indicates whether the edges of a swept shape can be moved inward as far as
+/// the layers require
+///
+/// Moving the edges inward must neither make circles vanish, nor turn straight
+/// edges around. The latter happens, once the shape's opposite edges have
+/// crossed each other.
+pub fn layers_fit(shape: &fj::Shape2d, layers: &[Layer]) -> bool {
+    let (shape, _) = on_plane::unplace(shape);
+    let faces = original_faces(shape, layers);
+
+    layers.iter().all(|layer| {
+        faces.0.iter().all(|face| match face {
+            Face::Face { edges, .. } => edges
+                .cycles
+                .iter()
+                .all(|cycle| offset_fits(cycle, layer.offset)),
+            Face::Triangles(_) => true,
+        })
+    })
+}
+
+/// A modification of the edges of a sweep, like rounding or beveling them
+pub struct EdgeModification {
+    /// The edges that are modified
+    pub edges: fj::EdgeSelection,
+
+    /// How the edges of the top or bottom face are modified
+    ///
+    /// Consists of pairs of distance from the face and offset, starting at the
+    /// side of the sweep and ending at the face.
+    pub profile: Vec<[f64; 2]>,
+
+    /// Modifies the corners of a sketch, for edges parallel to the z-axis
+    pub modify_corners: Box<dyn Fn(&fj::Sketch) -> fj::Sketch>,
+}
+
+/// Compute the shape and layers of a sweep with modified edges
+///
+/// `shape` is the shape whose edges are modified by `modification`. If that is
+/// itself a shape with modified edges, its modifications are combined with
+/// `modification`. Modifications of edges parallel to the z-axis are applied
+/// to the swept shape first, regardless of their order.
+pub fn modify_edges(
+    shape: &fj::Shape3d,
+    modification: EdgeModification,
+    tolerance: f64,
+) -> (fj::Shape2d, Vec<Layer>) {
+    let mut shape = shape;
+    let mut modifications = vec![modification];
+    while let Some((inner, modification)) = edge_modification(shape, tolerance)
+    {
+        shape = inner;
+        modifications.push(modification);
     }
 
-    fn vertices(&self) -> Vec<Point<3>> {
-        todo!()
+    // Models with edge modifications that can't be applied are rejected when
+    // they're loaded, so none of the following can fail in a valid model.
+    let sweep = straight_sweep(shape).expect(
+        "Only edges of sweeps along the z-axis without draft can be modified.",
+    );
+
+    let mut swept = sweep.shape.clone();
+    let mut profiles = [None, None];
+
+    for modification in modifications.into_iter().rev() {
+        let i = match modification.edges {
+            fj::EdgeSelection::ParallelToZ => {
+                swept = modify_corners(&swept, &modification.modify_corners);
+                continue;
+            }
+            fj::EdgeSelection::BottomFace => 0,
+            fj::EdgeSelection::TopFace => 1,
+        };
+
+        assert!(
+            profiles[i].is_none(),
+            "The edges of a face can only be modified once."
+        );
+        profiles[i] = Some(modification.profile);
+    }
+
+    let [start, end] = [layers(&sweep)[0], layers(&sweep)[1]];
+    let upwards = end.position.z >= start.position.z;
+
+    // The profiles are placed at either end of the sweep, whichever is the
+    // face they belong to.
+    let [bottom, top] = profiles;
+    let [start_profile, end_profile] = if upwards {
+        [bottom, top]
+    } else {
+        [top, bottom]
+    };
+    let [start_profile, end_profile] =
+        [start_profile, end_profile].map(Option::unwrap_or_default);
+
+    let length = (end.position - start.position).magnitude();
+    let extent = |profile: &[[f64; 2]]| {
+        profile
+            .iter()
+            .map(|[distance, _]| *distance)
+            .fold(0., f64::max)
+    };
+    assert!(
+        extent(&start_profile) + extent(&end_profile) < length,
+        "Sweep is too short for the modified edges."
+    );
+
+    let direction = (end.position - start.position) / length;
+    let layer = |face: Layer,
+                 direction: Vector<3>,
+                 [distance, offset]: [f64; 2]| Layer {
+        position: face.position + direction * distance,
+        offset,
+    };
+
+    let mut layers = Vec::new();
+    if start_profile.is_empty() {
+        layers.push(start);
+    }
+    layers.extend(
+        start_profile
+            .into_iter()
+            .rev()
+            .map(|step| layer(start, direction, step)),
+    );
+    layers.extend(end_profile.iter().map(|&step| layer(end, -direction, step)));
+    if end_profile.is_empty() {
+        layers.push(end);
+    }
+
+    (swept, layers)
+}
+
+/// Separate a shape with modified edges into the original shape and the
+/// modification
+fn edge_modification(
+    shape: &fj::Shape3d,
+    tolerance: f64,
+) -> Option<(&fj::Shape3d, EdgeModification)> {
+    match shape {
+        fj::Shape3d::Chamfer(chamfer) => {
+            Some((&chamfer.shape, super::chamfer::modification(chamfer)))
+        }
+        fj::Shape3d::Fillet(fillet) => Some((
+            &fillet.shape,
+            super::fillet::modification(fillet, tolerance),
+        )),
+        _ => None,
+    }
+}
+
+/// Modify the corners of a 2-dimensional shape
+///
+/// Shapes that aren't sketches are converted to sketches first, one per face.
+/// Ellipses and Bézier curves are approximated with lines in the process.
+fn modify_corners(
+    shape: &fj::Shape2d,
+    modify: &dyn Fn(&fj::Sketch) -> fj::Sketch,
+) -> fj::Shape2d {
+    if let fj::Shape2d::Sketch(sketch) = shape {
+        return modify(sketch).into();
+    }
+
+    let tolerance = default_tolerance(&shape.bounding_volume());
+    let faces = csg_2d::orient(
+        shape.faces(tolerance, &mut DebugInfo::new()),
+        tolerance,
+    );
+
+    let faces = if has_free_form_curves(&faces) {
+        faces.to_lines(tolerance)
+    } else {
+        faces
+    };
+
+    faces
+        .0
+        .iter()
+        .map(|face| match face {
+            Face::Face { edges, .. } => modify(&sketch(edges, tolerance)),
+            Face::Triangles(_) => {
+                // None of the 2D types use the triangles representation.
+                unreachable!()
+            }
+        })
+        .map(fj::Shape2d::from)
+        .reduce(|a, b| fj::Union2d { a, b }.into())
+        .unwrap_or_else(|| shape.clone())
+}
+
+/// Create a sketch from the edges of an oriented face
+///
+/// All edges must be lines or circles.
+fn sketch(edges: &Edges, tolerance: f64) -> fj::Sketch {
+    let mut cycles: Vec<_> = edges
+        .cycles
+        .iter()
+        .map(|cycle| {
+            let area = csg_2d::signed_area(
+                cycle
+                    .approx(tolerance)
+                    .segments
+                    .into_iter()
+                    .map(|segment| [segment.a.xy(), segment.b.xy()]),
+            );
+            (cycle, area)
+        })
+        .collect();
+
+    // The outer boundary of an oriented face is its only counter-clockwise
+    // cycle. Sketches expect it to come first.
+    cycles.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    let cycles = cycles
+        .into_iter()
+        .map(|(cycle, _)| {
+            let segments = cycle.edges.iter().map(|edge| {
+                let xy = |point: Point<3>| [point.x, point.y];
+
+                match edge.curve {
+                    Curve::Line(_) => {
+                        fj::SketchSegment::line(xy(line_points(edge)[0]))
+                    }
+                    Curve::Circle(circle) => {
+                        let start = match edge.vertices {
+                            Some([a, b]) => {
+                                if edge.reverse {
+                                    b
+                                } else {
+                                    a
+                                }
+                            }
+                            None => circle.point_curve_to_model(0.),
+                        };
+                        let counter_clockwise =
+                            (circle.normal.z > 0.) != edge.reverse;
+
+                        fj::SketchSegment::arc(
+                            xy(start),
+                            xy(circle.center),
+                            counter_clockwise,
+                        )
+                    }
+                    Curve::Bezier(_) | Curve::Ellipse(_) => {
+                        // Those have been approximated by lines.
+                        unreachable!()
+                    }
+                }
+            });
+
+            fj::SketchCycle::from_segments(segments.collect())
+        })
+        .collect();
+
+    fj::Sketch::from_cycles(cycles)
+}
+
+/// Compute the oriented faces of the swept shape, ready to be moved inward
+fn original_faces(shape: &fj::Shape2d, layers: &[Layer]) -> Faces {
+    let tolerance = default_tolerance(&shape.bounding_volume());
+    let faces = csg_2d::orient(
+        shape.faces(tolerance, &mut DebugInfo::new()),
+        tolerance,
+    );
+
+    if layers.iter().any(|layer| layer.offset != 0.)
+        && (has_arcs(&faces) || has_free_form_curves(&faces))
+    {
+        faces.to_lines(tolerance)
+    } else {
+        faces
+    }
+}
+
+/// Compute the start and end offset of the sweep
+fn path(sweep: &fj::Sweep) -> [Vector<3>; 2] {
+    let path = Vector::from(sweep.direction).normalize() * sweep.length;
+
+    if sweep.symmetric {
+        [-path / 2., path / 2.]
+    } else {
+        [Vector::zeros(), path]
+    }
+}
+
+/// Compute how far the edges are moved inward at the start and end of the sweep
+fn offsets(sweep: &fj::Sweep) -> [f64; 2] {
+    let length = sweep.length.abs();
+    let distances = if sweep.symmetric {
+        [-length / 2., length / 2.]
+    } else {
+        [0., length]
+    };
+
+    distances.map(|distance| distance * sweep.draft.tan())
+}
+
+/// Create the side face that connects an edge of the bottom and top faces
+fn side_face(bottom: &Edge, top: &Edge, upwards: bool) -> Face {
+    let face = match (bottom.curve, top.curve) {
+        (Curve::Line(_), Curve::Line(_)) => {
+            let [a, b] = line_points(bottom);
+            let [c, d] = line_points(top);
+
+            let u = (b - a).normalize();
+            let v = c - a;
+            let v = (v - u * u.dot(&v)).normalize();
+
+            let edges = Edges::single_cycle(
+                [[a, b], [b, d], [d, c], [c, a]]
+                    .map(|[a, b]| Edge::new(Curve::Line(Line { a, b }))),
+            );
+
+            Face::Face {
+                edges,
+                surface: Surface::Plane(Plane { origin: a, u, v }),
+            }
+        }
+        (Curve::Circle(bottom_circle), Curve::Circle(top_circle)) => {
+            // Arcs are revolved from their first vertex, full circles from the
+            // origin of their curve coordinates.
+            let (a, b, angle) = match (bottom.vertices, top.vertices) {
+                (Some([a, b]), Some([c, _])) => {
+                    (a, c, bottom_circle.arc_angle([a, b]))
+                }
+                _ => (
+                    bottom_circle.point_curve_to_model(0.),
+                    top_circle.point_curve_to_model(0.),
+                    2. * PI,
+                ),
+            };
+
+            let surface = RevolvedCurve {
+                curve: Curve::Line(Line { a, b }),
+                origin: bottom_circle.center,
+                axis: bottom_circle.normal,
+                angle,
+                reverse: bottom.reverse,
+            };
+
+            // Faces on revolved surfaces always cover the whole surface, so
+            // they don't need any edges.
+            Face::Face {
+                edges: Edges { cycles: Vec::new() },
+                surface: Surface::Revolved(surface),
+            }
+        }
+        _ => {
+            // The edges of the top face were created by moving the edges of
+            // the bottom face, so their curves are always of the same kind.
+            unreachable!()
+        }
+    };
+
+    // Side faces point outward, if the sweep goes upwards. Otherwise, they
+    // need to be reversed.
+    if upwards {
+        face
+    } else {
+        face.reverse()
+    }
+}
+
+/// Return the start and end point of an edge that is a line
+fn line_points(edge: &Edge) -> [Point<3>; 2] {
+    match edge.curve {
+        Curve::Line(Line { a, b }) => {
+            if edge.reverse {
+                [b, a]
+            } else {
+                [a, b]
+            }
+        }
+        Curve::Bezier(_) | Curve::Circle(_) | Curve::Ellipse(_) => {
+            unreachable!()
+        }
+    }
+}
+
+/// Move the boundaries of the faces inward by `distance`
+///
+/// Expects the faces to be oriented, like the result of [`csg_2d::orient`]. A
+/// negative distance moves the boundaries outward.
+///
+/// Straight edges are moved parallel to themselves, with their ends extended or
+/// trimmed to meet again. Circles change their radius.
+fn offset(faces: Faces, distance: f64) -> Faces {
+    if distance == 0. {
+        return faces;
+    }
+
+    let faces = faces
+        .0
+        .into_iter()
+        .map(|face| match face {
+            Face::Face { edges, surface } => {
+                let cycles = edges
+                    .cycles
+                    .into_iter()
+                    .map(|cycle| offset_cycle(cycle, distance))
+                    .collect();
+
+                Face::Face {
+                    edges: Edges { cycles },
+                    surface,
+                }
+            }
+            Face::Triangles(_) => {
+                // None of the 2D types use the triangles representation.
+                unreachable!()
+            }
+        })
+        .collect();
+
+    Faces(faces)
+}
+
+fn offset_cycle(cycle: Cycle, distance: f64) -> Cycle {
+    let lines: Option<Vec<[Point<3>; 2]>> = cycle
+        .edges
+        .iter()
+        .map(|edge| match edge.curve {
+            Curve::Line(_) => Some(line_points(edge)),
+            Curve::Bezier(_) | Curve::Circle(_) | Curve::Ellipse(_) => None,
+        })
+        .collect();
+
+    let lines = match lines {
+        Some(lines) => lines,
+        None => {
+            // Only full circles are supported so far, which means a cycle that
+            // contains a circle consists of only that circle.
+            let edges = cycle
+                .edges
+                .into_iter()
+                .map(|mut edge| {
+                    if let Curve::Circle(circle) = &mut edge.curve {
+                        *circle =
+                            offset_circle(*circle, distance, edge.reverse);
+                    }
+                    edge
+                })
+                .collect();
+
+            return Cycle { edges };
+        }
+    };
+
+    // The interior of an oriented face is always to the left of its edges.
+    let normals: Vec<_> = lines
+        .iter()
+        .map(|[a, b]| Vector::<3>::z().cross(&(b - a).normalize()))
+        .collect();
+
+    let n = lines.len();
+    let points: Vec<_> = (0..n)
+        .map(|i| {
+            let [previous, next] = [normals[(i + n - 1) % n], normals[i]];
+            lines[i][0]
+                + (previous + next) * (distance / (1. + previous.dot(&next)))
+        })
+        .collect();
+
+    let edges = (0..n)
+        .map(|i| {
+            Edge::new(Curve::Line(Line {
+                a: points[i],
+                b: points[(i + 1) % n],
+            }))
+        })
+        .collect();
+
+    Cycle { edges }
+}
+
+/// Indicates whether a cycle can be moved inward by `distance`
+///
+/// See [`layers_fit`].
+fn offset_fits(cycle: &Cycle, distance: f64) -> bool {
+    let is_line = |edge: &Edge| matches!(edge.curve, Curve::Line(_));
+    if !cycle.edges.iter().all(is_line) {
+        return cycle.edges.iter().all(|edge| match &edge.curve {
+            Curve::Circle(circle) => {
+                offset_radius(circle, distance, edge.reverse) > 0.
+            }
+            _ => true,
+        });
+    }
+
+    let moved = offset_cycle(cycle.clone(), distance);
+    cycle.edges.iter().zip(&moved.edges).all(|(edge, moved)| {
+        let [a, b] = line_points(edge);
+        let [c, d] = line_points(moved);
+        (d - c).dot(&(b - a)) > 0.
+    })
+}
+
+fn offset_circle(circle: Circle, distance: f64, reverse: bool) -> Circle {
+    let radius = circle.radius.magnitude();
+    let new_radius = offset_radius(&circle, distance, reverse);
+
+    // Sweeps with a draft angle that is too large are rejected when a model
+    // is loaded, so this can't happen in a valid model.
+    assert!(
+        new_radius > 0.,
+        "Draft angle is too large for the length of the sweep."
+    );
+
+    Circle {
+        radius: circle.radius * (new_radius / radius),
+        ..circle
+    }
+}
+
+/// Compute the radius of a circle, after moving it inward by `distance`
+fn offset_radius(circle: &Circle, distance: f64, reverse: bool) -> f64 {
+    let radius = circle.radius.magnitude();
+
+    // Circles are oriented counter-clockwise, which means their interior is to
+    // the left. Unless they're reversed.
+    if reverse {
+        radius + distance
+    } else {
+        radius - distance
+    }
+}
+
+/// Compute the bounding volume of the edges of the faces
+fn bounding_volume(faces: &Faces) -> AABB {
+    faces
+        .0
+        .iter()
+        .filter_map(|face| match face {
+            Face::Face { edges, .. } => Some(edges.bounding_volume()),
+            Face::Triangles(_) => None,
+        })
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+}
+
+/// Indicates whether any of the faces' edges is an arc
+fn has_arcs(faces: &Faces) -> bool {
+    faces.0.iter().any(|face| match face {
+        Face::Face { edges, .. } => edges
+            .cycles
+            .iter()
+            .flat_map(|cycle| &cycle.edges)
+            .any(Edge::is_arc),
+        Face::Triangles(_) => false,
+    })
+}
+
+/// Indicates whether any of the faces' edges is on an ellipse or Bézier curve
+fn has_free_form_curves(faces: &Faces) -> bool {
+    faces.0.iter().any(|face| match face {
+        Face::Face { edges, .. } => edges
+            .cycles
+            .iter()
+            .flat_map(|cycle| &cycle.edges)
+            .any(|edge| {
+                matches!(edge.curve, Curve::Bezier(_) | Curve::Ellipse(_))
+            }),
+        Face::Triangles(_) => false,
+    })
+}
+
+fn translation(offset: Vector<3>) -> Isometry<f64> {
+    Isometry::translation(offset.x, offset.y, offset.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+    use nalgebra::{point, vector};
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            geometry::{Circle, Curve, Line},
+            test_util::{assert_approx, volume, TOLERANCE},
+            topology::edges::{Cycle, Edge},
+            Shape as _,
+        },
+        math::Point,
+    };
+
+    use super::{draft_fits, line_points, offset_circle, offset_cycle};
+
+    #[test]
+    fn drafted_sweep_of_square() {
+        // With a draft angle whose tangent is 0.5, each side of the square
+        // moves inward by 0.5 over the length of the sweep.
+        let sweep = square([0., 0.], 2.).sweep_drafted(1., 0.5f64.atan());
+        let shape = fj::Shape3d::from(sweep);
+
+        let aabb = shape.bounding_volume();
+        assert_points(&[aabb.mins, aabb.maxs], &[[0., 0., 0.], [2., 2., 1.]]);
+        assert_approx(volume(&shape), (4. + 1. + 2.) / 3., 1e-9);
+    }
+
+    #[test]
+    fn drafted_sweep_of_square_with_hole() {
+        // The hole grows, while the outer boundary shrinks.
+        let sketch = square([-3., -3.], 6.).with_hole(hole([-1., -1.], 2.));
+        let shape = fj::Shape3d::from(sketch.sweep_drafted(1., 0.5f64.atan()));
+
+        let outer = (36. + 25. + 30.) / 3.;
+        let inner = (4. + 9. + 6.) / 3.;
+        assert_approx(volume(&shape), outer - inner, 1e-9);
+    }
+
+    #[test]
+    fn drafted_symmetric_sweep() {
+        let sweep = fj::Sweep {
+            symmetric: true,
+            ..square([0., 0.], 2.).sweep_drafted(2., 0.5f64.atan())
+        };
+        let shape = fj::Shape3d::from(sweep);
+
+        // The square grows towards the start of the sweep, and shrinks towards
+        // its end.
+        let aabb = shape.bounding_volume();
+        assert_points(
+            &[aabb.mins, aabb.maxs],
+            &[[-0.5, -0.5, -1.], [2.5, 2.5, 1.]],
+        );
+        assert_approx(volume(&shape), 2. * (9. + 1. + 3.) / 3., 1e-9);
+    }
+
+    #[test]
+    fn drafted_sweep_of_circle() {
+        let sweep = fj::Circle { radius: 1. }.sweep_drafted(1., 0.5f64.atan());
+        let shape = fj::Shape3d::from(sweep);
+
+        // A truncated cone, with a top radius of 0.5
+        assert_approx(volume(&shape), PI * (1. + 0.25 + 0.5) / 3., 0.01);
+    }
+
+    #[test]
+    fn draft_must_fit_length_of_sweep() {
+        let draft = 0.5f64.atan();
+
+        // The sides of the square meet in its center at a length of 2.
+        assert!(draft_fits(&square([0., 0.], 2.).sweep_drafted(1.9, draft)));
+        assert!(!draft_fits(&square([0., 0.], 2.).sweep_drafted(2.1, draft)));
+
+        // With a negative draft angle, holes shrink instead.
+        let sketch = square([-3., -3.], 6.).with_hole(hole([-1., -1.], 2.));
+        assert!(draft_fits(&sketch.clone().sweep_drafted(1.9, -draft)));
+        assert!(!draft_fits(&sketch.sweep_drafted(2.1, -draft)));
+
+        let circle = fj::Circle { radius: 1. };
+        assert!(draft_fits(&circle.sweep_drafted(1.9, draft)));
+        assert!(!draft_fits(&circle.sweep_drafted(2.1, draft)));
+    }
+
+    #[test]
+    fn edges_of_sweep() {
+        let shape = fj::Shape3d::from(square([0., 0.], 2.).sweep(1.));
+
+        // One cycle for the bottom face, one for the top face.
+        let edges = shape.edges();
+        assert_eq!(edges.cycles.len(), 2);
+        for (cycle, z) in edges.cycles.iter().zip([0., 1.]) {
+            assert_eq!(cycle.edges.len(), 4);
+            for point in start_points(cycle) {
+                assert_eq!(point.z, z);
+            }
+        }
+
+        assert_eq!(shape.vertices().len(), 8);
+    }
+
+    #[test]
+    fn faces_of_sweep() {
+        let shape = fj::Shape3d::from(square([0., 0.], 2.).sweep(1.));
+
+        let faces = shape.faces(TOLERANCE, &mut DebugInfo::new());
+        assert_eq!(faces.0.len(), 2 + 4);
+        assert_approx(volume(&shape), 4., 1e-9);
+    }
+
+    #[test]
+    fn offset_cycle_moves_lines_inward() {
+        let square = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]];
+
+        let cycle = offset_cycle(cycle(square), 0.5);
+        assert_points(
+            &start_points(&cycle),
+            &[
+                [0.5, 0.5, 0.],
+                [1.5, 0.5, 0.],
+                [1.5, 1.5, 0.],
+                [0.5, 1.5, 0.],
+            ],
+        );
+
+        // Clockwise cycles are the boundaries of holes. Moving their lines
+        // inward grows the holes.
+        let mut hole = square;
+        hole.reverse();
+
+        let cycle = offset_cycle(self::cycle(hole), 0.5);
+        assert_points(
+            &start_points(&cycle),
+            &[
+                [-0.5, 2.5, 0.],
+                [2.5, 2.5, 0.],
+                [2.5, -0.5, 0.],
+                [-0.5, -0.5, 0.],
+            ],
+        );
+    }
+
+    #[test]
+    fn offset_circle_changes_radius() {
+        let circle = Circle {
+            center: point![1., 1., 0.],
+            radius: vector![2., 0., 0.],
+            normal: vector![0., 0., 1.],
+        };
+
+        let shrunk = offset_circle(circle, 0.5, false);
+        assert_eq!(shrunk.center, circle.center);
+        assert_eq!(shrunk.radius, vector![1.5, 0., 0.]);
+
+        // Reversed circles are the boundaries of holes, which grow instead.
+        let grown = offset_circle(circle, 0.5, true);
+        assert_eq!(grown.radius, vector![2.5, 0., 0.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Draft angle is too large")]
+    fn offset_circle_panics_if_circle_vanishes() {
+        let circle = Circle {
+            center: point![0., 0., 0.],
+            radius: vector![1., 0., 0.],
+            normal: vector![0., 0., 1.],
+        };
+
+        offset_circle(circle, 1., false);
+    }
+
+    fn square([x, y]: [f64; 2], size: f64) -> fj::Sketch {
+        fj::Sketch::from_points(vec![
+            [x, y],
+            [x + size, y],
+            [x + size, y + size],
+            [x, y + size],
+        ])
+    }
+
+    fn hole([x, y]: [f64; 2], size: f64) -> fj::SketchCycle {
+        fj::SketchCycle::from_points(vec![
+            [x, y],
+            [x, y + size],
+            [x + size, y + size],
+            [x + size, y],
+        ])
+    }
+
+    fn cycle(points: [[f64; 2]; 4]) -> Cycle {
+        let points = points.map(|[x, y]| point![x, y, 0.]);
+        let edges = (0..4)
+            .map(|i| {
+                let [a, b] = [points[i], points[(i + 1) % 4]];
+                Edge::new(Curve::Line(Line { a, b }))
+            })
+            .collect();
+
+        Cycle { edges }
+    }
+
+    fn start_points(cycle: &Cycle) -> Vec<Point<3>> {
+        cycle
+            .edges
+            .iter()
+            .map(|edge| line_points(edge)[0])
+            .collect()
+    }
+
+    fn assert_points(points: &[Point<3>], expected: &[[f64; 3]]) {
+        assert_eq!(points.len(), expected.len());
+        for (a, b) in points.iter().zip(expected) {
+            assert!((a - Point::from(*b)).magnitude() < 1e-9, "{a} != {b:?}");
+        }
     }
 }
@@ -0,0 +1,301 @@
+use nalgebra::{point, vector};
+use parry2d_f64::bounding_volume::{BoundingVolume as _, AABB as AABB2};
+use parry3d_f64::bounding_volume::AABB;
+use wgpu_glyph::ab_glyph::{self, Font as _, FontRef, GlyphId, OutlineCurve};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        geometry::{Curve, Line, Surface},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Text {
+    fn bounding_volume(&self) -> AABB {
+        let aabbs: Vec<_> = glyphs(self)
+            .into_iter()
+            .filter_map(|glyph| glyph.aabb())
+            .collect();
+
+        match aabbs.split_first() {
+            Some((first, rest)) => {
+                let aabb =
+                    rest.iter().fold(*first, |aabb, other| aabb.merged(other));
+
+                AABB::new(
+                    point![aabb.mins.x, aabb.mins.y, 0.],
+                    point![aabb.maxs.x, aabb.maxs.y, 0.],
+                )
+            }
+            None => AABB::new(Point::origin(), Point::origin()),
+        }
+    }
+
+    fn faces(&self, tolerance: f64, _: &mut DebugInfo) -> Faces {
+        let mut faces = Faces(Vec::new());
+        let mut aabbs: Vec<AABB2> = Vec::new();
+
+        for glyph in glyphs(self) {
+            let aabb = match glyph.aabb() {
+                Some(aabb) => aabb,
+                None => continue,
+            };
+
+            // The outer boundaries and holes of a glyph aren't distinguished
+            // by the font in a way we can rely on. Orienting the cycles sorts
+            // that out, based on how they are nested.
+            let face = Face::Face {
+                edges: Edges {
+                    cycles: glyph.cycles(tolerance),
+                },
+                surface: Surface::x_y_plane(),
+            };
+            let glyph_faces = csg_2d::orient(Faces(vec![face]), tolerance);
+
+            // Glyphs usually don't touch each other, but some, like those of
+            // italic fonts, might. Those need to be merged.
+            if aabbs.iter().any(|other| other.intersects(&aabb)) {
+                faces = csg_2d::union(faces, glyph_faces, tolerance);
+            } else {
+                faces.0.extend(glyph_faces.0);
+            }
+
+            aabbs.push(aabb);
+        }
+
+        faces
+    }
+
+    fn edges(&self) -> Edges {
+        csg_2d::shape_edges(self)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
+
+/// A glyph, positioned within the text
+struct Glyph {
+    outline: Option<ab_glyph::Outline>,
+
+    /// The position of the glyph's origin
+    position: Vector<2>,
+
+    /// The factor that converts font units into model units
+    scale: f64,
+}
+
+impl Glyph {
+    fn aabb(&self) -> Option<AABB2> {
+        // The bounds use a y-down convention, as is common for text. The
+        // outline itself doesn't.
+        let bounds = self.outline.as_ref()?.bounds;
+
+        Some(AABB2::new(
+            self.point(bounds.min.x, bounds.max.y),
+            self.point(bounds.max.x, bounds.min.y),
+        ))
+    }
+
+    /// Approximate the glyph's outline with cycles of lines
+    fn cycles(&self, tolerance: f64) -> Vec<Cycle> {
+        let curves = match &self.outline {
+            Some(outline) => &outline.curves,
+            None => return Vec::new(),
+        };
+
+        // The outline is a flat list of curves. Each contour of the glyph ends
+        // where it started.
+        let tolerance = tolerance / self.scale;
+        let mut contours = Vec::new();
+        let mut contour = Vec::new();
+
+        for curve in curves {
+            let control: Vec<_> = match curve {
+                OutlineCurve::Line(a, b) => vec![a, b],
+                OutlineCurve::Quad(a, b, c) => vec![a, b, c],
+                OutlineCurve::Cubic(a, b, c, d) => vec![a, b, c, d],
+            }
+            .into_iter()
+            .map(|p| point![f64::from(p.x), f64::from(p.y)])
+            .collect();
+
+            if contour.is_empty() {
+                contour.push(control[0]);
+            }
+            flatten(&control, tolerance, &mut contour);
+
+            if contour.last() == contour.first() {
+                contours.push(contour);
+                contour = Vec::new();
+            }
+        }
+
+        contours
+            .into_iter()
+            .filter_map(|contour| self.cycle(contour))
+            .collect()
+    }
+
+    /// Create a cycle from the points of a closed contour
+    fn cycle(&self, contour: Vec<Point<2>>) -> Option<Cycle> {
+        let mut points: Vec<_> = contour
+            .into_iter()
+            .map(|point| point * self.scale + self.position)
+            .collect();
+
+        // The contour ends at its start point, and some of its curves might be
+        // degenerate. Neither leads to useful edges.
+        points.dedup();
+        if points.first() == points.last() {
+            points.pop();
+        }
+
+        if points.len() < 3 {
+            return None;
+        }
+
+        let edges = (0..points.len())
+            .map(|i| {
+                let a = points[i];
+                let b = points[(i + 1) % points.len()];
+
+                Edge::new(Curve::Line(Line {
+                    a: point![a.x, a.y, 0.],
+                    b: point![b.x, b.y, 0.],
+                }))
+            })
+            .collect();
+
+        Some(Cycle { edges })
+    }
+
+    /// Convert a point in font units into model units
+    fn point(&self, x: f32, y: f32) -> Point<2> {
+        Point::from(vector![f64::from(x), f64::from(y)] * self.scale)
+            + self.position
+    }
+}
+
+/// Lay out the glyphs of the text
+///
+/// Lines are laid out from left to right, starting at the origin. Each line is
+/// below the previous one.
+fn glyphs(text: &fj::Text) -> Vec<Glyph> {
+    let font = font(text.font);
+
+    // Panicking is not great, but as long as we don't have a real error
+    // handling mechanism, it will do.
+    let units_per_em = font
+        .units_per_em()
+        .expect("Font doesn't define its units per em");
+    let scale = text.size / f64::from(units_per_em);
+
+    let line_height = f64::from(
+        font.ascent_unscaled() - font.descent_unscaled()
+            + font.line_gap_unscaled(),
+    ) * scale;
+
+    let mut glyphs = Vec::new();
+
+    for (i, line) in text.content().lines().enumerate() {
+        let y = -line_height * i as f64;
+        let mut x = 0.;
+        let mut previous: Option<GlyphId> = None;
+
+        for c in line.chars() {
+            let id = font.glyph_id(c);
+
+            if let Some(previous) = previous {
+                x += f64::from(font.kern_unscaled(previous, id)) * scale;
+            }
+
+            glyphs.push(Glyph {
+                outline: font.outline(id),
+                position: vector![x, y],
+                scale,
+            });
+
+            x += f64::from(font.h_advance_unscaled(id)) * scale;
+            previous = Some(id);
+        }
+    }
+
+    glyphs
+}
+
+/// Approximate a Bézier curve with lines
+///
+/// Adds the points of the approximation to `points`, except the start point of
+/// the curve, which is expected to already be there.
+fn flatten(control: &[Point<2>], tolerance: f64, points: &mut Vec<Point<2>>) {
+    let degree = control.len() - 1;
+
+    // If a curve is split into `n` segments of equal parameter length, the
+    // distance between the curve and the segments is bounded by its maximum
+    // second derivative, divided by `8 * n^2`.
+    let max_second_derivative = control
+        .windows(3)
+        .map(|p| (p[0].coords - p[1].coords * 2. + p[2].coords).magnitude())
+        .fold(0., f64::max)
+        * (degree * degree.saturating_sub(1)) as f64;
+
+    let n = (max_second_derivative / (8. * tolerance))
+        .sqrt()
+        .ceil()
+        .max(1.);
+    let n = n as usize;
+
+    for i in 1..=n {
+        let t = i as f64 / n as f64;
+
+        // De Casteljau's algorithm
+        let mut p = control.to_vec();
+        while p.len() > 1 {
+            p = p.windows(2).map(|p| p[0] + (p[1] - p[0]) * t).collect();
+        }
+
+        points.push(p[0]);
+    }
+}
+
+fn font(font: fj::Font) -> FontRef<'static> {
+    let data: &'static [u8] = match font {
+        fj::Font::B612Regular => {
+            include_bytes!("../../graphics/fonts/B612-Regular.ttf")
+        }
+        fj::Font::B612Bold => {
+            include_bytes!("../../graphics/fonts/B612-Bold.ttf")
+        }
+        fj::Font::B612Italic => {
+            include_bytes!("../../graphics/fonts/B612-Italic.ttf")
+        }
+        fj::Font::B612BoldItalic => {
+            include_bytes!("../../graphics/fonts/B612-BoldItalic.ttf")
+        }
+        fj::Font::B612MonoRegular => {
+            include_bytes!("../../graphics/fonts/B612Mono-Regular.ttf")
+        }
+        fj::Font::B612MonoBold => {
+            include_bytes!("../../graphics/fonts/B612Mono-Bold.ttf")
+        }
+        fj::Font::B612MonoItalic => {
+            include_bytes!("../../graphics/fonts/B612Mono-Italic.ttf")
+        }
+        fj::Font::B612MonoBoldItalic => {
+            include_bytes!("../../graphics/fonts/B612Mono-BoldItalic.ttf")
+        }
+    };
+
+    // The fonts are bundled with the application, so they are known to be
+    // valid.
+    FontRef::try_from_slice(data).expect("Bundled font is invalid")
+}
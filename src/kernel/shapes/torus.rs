@@ -0,0 +1,59 @@
+use nalgebra::point;
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{surfaces::Torus, Surface},
+        topology::{
+            edges::Edges,
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Torus {
+    fn bounding_volume(&self) -> AABB {
+        let outer = self.major_radius + self.minor_radius;
+        let minor = self.minor_radius;
+
+        AABB {
+            mins: point![-outer, -outer, -minor],
+            maxs: point![outer, outer, minor],
+        }
+    }
+
+    fn faces(&self, _: f64, _: &mut DebugInfo) -> Faces {
+        // Panicking is not great, but as long as we don't have a real error
+        // handling mechanism, it will do.
+        assert!(
+            self.minor_radius > 0. && self.major_radius > self.minor_radius,
+            "A torus must have a positive minor radius that is smaller than \
+            its major radius."
+        );
+
+        let surface = Torus {
+            center: Point::origin(),
+            axis: Vector::z(),
+            major_radius: self.major_radius,
+            minor_radius: self.minor_radius,
+            reverse: false,
+        };
+
+        Faces(vec![Face::Face {
+            edges: Edges { cycles: Vec::new() },
+            surface: Surface::Torus(surface),
+        }])
+    }
+
+    fn edges(&self) -> Edges {
+        // A torus consists of a single face without any edges.
+        Edges { cycles: Vec::new() }
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        Vec::new()
+    }
+}
@@ -1,35 +1,272 @@
-use parry3d_f64::{bounding_volume::AABB, math::Isometry};
+use nalgebra::{
+    point, Matrix3, Matrix4, Rotation3, Translation3, UnitQuaternion,
+};
+use parry3d_f64::{bounding_volume::AABB, math::Isometry, shape::Triangle};
 
 use crate::{
     debug::DebugInfo,
     kernel::{
-        topology::{edges::Edges, faces::Faces},
+        geometry::{Curve, Line},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        util::default_tolerance,
         Shape,
     },
-    math::{Point, Vector},
+    math::Point,
 };
 
 impl Shape for fj::Transform {
     fn bounding_volume(&self) -> AABB {
-        self.shape.bounding_volume().transform_by(&isometry(self))
+        let aabb = self.shape.bounding_volume();
+        let (mins, maxs) = (aabb.mins, aabb.maxs);
+
+        // An affine transformation maps the bounding box of the original shape
+        // to a parallelepiped. Its corners bound the transformed shape.
+        let corners: Vec<_> = (0..8)
+            .map(|i| {
+                let x = if i & 1 == 0 { mins.x } else { maxs.x };
+                let y = if i & 2 == 0 { mins.y } else { maxs.y };
+                let z = if i & 4 == 0 { mins.z } else { maxs.z };
+
+                matrix(self).transform_point(&point![x, y, z])
+            })
+            .collect();
+
+        AABB::from_points(&corners)
     }
 
     fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
-        self.shape
-            .faces(tolerance, debug_info)
-            .transform(&isometry(self))
+        if let Some((factor, isometry)) = similarity(self) {
+            // The faces are scaled after they have been approximated, so the
+            // approximation needs to be more precise.
+            return self
+                .shape
+                .faces(tolerance / factor.abs(), debug_info)
+                .scale(factor)
+                .transform(&isometry);
+        }
+
+        // The geometry that faces are made of, like circles or spheres, can't
+        // represent the result of transformations that change its shape, like
+        // non-uniform scaling or shearing. The faces are approximated with
+        // triangles instead, which are then transformed.
+        //
+        // The transformation stretches the approximation, so it needs to be
+        // more precise, to stay within the tolerance.
+        let matrix = matrix(self);
+        let linear = linear(self);
+        let tolerance = tolerance / linear.singular_values().max();
+
+        let mut triangles = Vec::new();
+        self.shape.faces(tolerance, debug_info).triangles(
+            tolerance,
+            &mut triangles,
+            debug_info,
+        );
+
+        // Mirroring inverts the orientation of the triangles. They need to be
+        // flipped, to keep pointing outward.
+        let mirrors = linear.determinant() < 0.;
+
+        let triangles = triangles
+            .into_iter()
+            .map(|Triangle { a, b, c }| {
+                let [a, b, c] =
+                    [a, b, c].map(|point| matrix.transform_point(&point));
+
+                if mirrors {
+                    Triangle::new(a, c, b)
+                } else {
+                    Triangle::new(a, b, c)
+                }
+            })
+            .collect();
+
+        Faces(vec![Face::Triangles(triangles)])
     }
 
     fn edges(&self) -> Edges {
-        todo!()
+        let edges = self.shape.edges();
+
+        if let Some((factor, isometry)) = similarity(self) {
+            return edges.scale(factor).transform(&isometry);
+        }
+
+        // Same as with the faces, the curves that edges are made of can't
+        // represent transformations that change their shape. They are
+        // approximated with lines, which are then transformed.
+        let matrix = matrix(self);
+        let tolerance = default_tolerance(&self.shape.bounding_volume());
+
+        let cycles = edges
+            .to_lines(tolerance)
+            .cycles
+            .into_iter()
+            .map(|cycle| Cycle {
+                edges: cycle
+                    .edges
+                    .into_iter()
+                    .map(|edge| match edge.curve {
+                        Curve::Line(Line { a, b }) => {
+                            Edge::new(Curve::Line(Line {
+                                a: matrix.transform_point(&a),
+                                b: matrix.transform_point(&b),
+                            }))
+                        }
+                        Curve::Bezier(_)
+                        | Curve::Circle(_)
+                        | Curve::Ellipse(_) => {
+                            // All edges have been converted to lines.
+                            unreachable!()
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Edges { cycles }
     }
 
     fn vertices(&self) -> Vec<Point<3>> {
-        todo!()
+        if similarity(self).is_some() {
+            return self.edges().vertices();
+        }
+
+        // The edges are approximated, so their vertices include points that
+        // aren't vertices of the original shape.
+        let matrix = matrix(self);
+        self.shape
+            .vertices()
+            .into_iter()
+            .map(|vertex| matrix.transform_point(&vertex))
+            .collect()
+    }
+}
+
+fn matrix(transform: &fj::Transform) -> Matrix4<f64> {
+    Matrix4::from_column_slice(&transform.matrix)
+}
+
+fn linear(transform: &fj::Transform) -> Matrix3<f64> {
+    matrix(transform).fixed_slice::<3, 3>(0, 0).into_owned()
+}
+
+/// Split the transformation into a uniform scaling and an isometry, if it is
+/// a similarity
+///
+/// The scaling is relative to the origin, and is applied first. Its factor is
+/// negative, if the transformation mirrors the shape. Mirroring through the
+/// origin and a rotation add up to any other kind of mirroring.
+///
+/// Returns `None`, if the transformation scales the shape non-uniformly, or
+/// shears it.
+fn similarity(transform: &fj::Transform) -> Option<(f64, Isometry<f64>)> {
+    let linear = linear(transform);
+
+    let determinant = linear.determinant();
+    let scale = determinant.abs().cbrt();
+    if scale == 0. {
+        return None;
+    }
+
+    let normalized = linear / scale;
+    let gram = normalized.transpose() * normalized;
+    if (gram - Matrix3::identity()).abs().max() > 1e-12 {
+        return None;
     }
+
+    let factor = scale.copysign(determinant);
+    let rotation = UnitQuaternion::from_rotation_matrix(
+        &Rotation3::from_matrix_unchecked(linear / factor),
+    );
+    let translation = Translation3::new(
+        transform.matrix[12],
+        transform.matrix[13],
+        transform.matrix[14],
+    );
+
+    Some((factor, Isometry::from_parts(translation, rotation)))
 }
 
-fn isometry(transform: &fj::Transform) -> Isometry<f64> {
-    let axis = Vector::from(transform.axis).normalize();
-    Isometry::new(Vector::from(transform.offset), axis * transform.angle)
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj::prelude::*;
+    use nalgebra::point;
+
+    use crate::{
+        debug::DebugInfo,
+        kernel::{
+            test_util::{assert_approx, triangles, volume, TOLERANCE},
+            topology::faces::Face,
+            Shape as _,
+        },
+    };
+
+    #[test]
+    fn mirrored_shape_points_outward() {
+        let shape: fj::Shape3d = cube().mirror([1., 0., 0.]).into();
+
+        let center = point![-1., 1., 1.];
+        for triangle in triangles(&shape) {
+            let normal = triangle.normal().unwrap();
+            let centroid = triangle.center();
+
+            assert!(normal.dot(&(centroid - center)) > 0.);
+        }
+
+        assert_approx(volume(&shape), 8., 1e-9);
+    }
+
+    #[test]
+    fn similarities_keep_exact_geometry() {
+        let cylinder = fj::Cylinder {
+            radius: 1.,
+            height: 1.,
+        };
+        let sphere = fj::Sphere { radius: 1. };
+
+        let shapes: [(fj::Shape3d, f64); 4] = [
+            (cylinder.scale([2., 2., 2.]).into(), 8. * PI),
+            (cylinder.mirror([1., 1., 0.]).into(), PI),
+            (sphere.scale([-2., -2., -2.]).into(), 32. / 3. * PI),
+            (cube().rotate([0., 0., 1.], 1.).scale([0.5; 3]).into(), 1.),
+        ];
+        for (shape, expected) in shapes {
+            let faces = shape.faces(TOLERANCE, &mut DebugInfo::new());
+            for face in faces.0 {
+                assert!(matches!(face, Face::Face { .. }));
+            }
+
+            // A negative volume would mean the faces point inward.
+            assert_approx(volume(&shape), expected, expected * 0.01);
+        }
+    }
+
+    #[test]
+    fn bounding_volume_of_non_uniformly_scaled_shape() {
+        let cylinder = fj::Cylinder {
+            radius: 1.,
+            height: 1.,
+        };
+        let shape: fj::Shape3d = cylinder.scale([2., 0.5, 3.]).into();
+
+        let aabb = shape.bounding_volume();
+        assert_eq!(aabb.mins, point![-2., -0.5, 0.]);
+        assert_eq!(aabb.maxs, point![2., 0.5, 3.]);
+
+        for triangle in triangles(&shape) {
+            for vertex in triangle.vertices() {
+                assert!(aabb.contains_local_point(vertex));
+            }
+        }
+    }
+
+    fn cube() -> fj::Sweep {
+        fj::Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .sweep(2.)
+    }
 }
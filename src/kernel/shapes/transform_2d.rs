@@ -0,0 +1,207 @@
+use nalgebra::{point, vector, Matrix2, Matrix3};
+use parry3d_f64::bounding_volume::AABB;
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        geometry::{Bezier, Circle, Curve, Ellipse, Line},
+        topology::{
+            edges::{Cycle, Edge, Edges},
+            faces::{Face, Faces},
+        },
+        Shape,
+    },
+    math::{Point, Vector},
+};
+
+impl Shape for fj::Transform2d {
+    fn bounding_volume(&self) -> AABB {
+        let aabb = self.shape.bounding_volume();
+        let (mins, maxs) = (aabb.mins, aabb.maxs);
+
+        // An affine transformation maps the bounding box of the original shape
+        // to a parallelogram. Its corners bound the transformed shape.
+        let corners = [
+            point![mins.x, mins.y, 0.],
+            point![maxs.x, mins.y, 0.],
+            point![mins.x, maxs.y, 0.],
+            point![maxs.x, maxs.y, 0.],
+        ]
+        .map(|corner| transform_point(self, corner));
+
+        AABB::from_points(&corners)
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        // The transformation stretches any approximations that happen while
+        // computing the faces, so they need to be more precise, to stay within
+        // the tolerance.
+        let tolerance = tolerance / linear(self).singular_values().max();
+
+        let faces = self
+            .shape
+            .faces(tolerance, debug_info)
+            .0
+            .into_iter()
+            .map(|face| match face {
+                Face::Face { edges, surface } => Face::Face {
+                    edges: transform_edges(self, edges),
+                    surface,
+                },
+                Face::Triangles(_) => {
+                    // None of the 2D types use the triangles representation.
+                    unreachable!()
+                }
+            })
+            .collect();
+
+        Faces(faces)
+    }
+
+    fn edges(&self) -> Edges {
+        transform_edges(self, self.shape.edges())
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.shape
+            .vertices()
+            .into_iter()
+            .map(|vertex| transform_point(self, vertex))
+            .collect()
+    }
+}
+
+/// Transform edges in the x-y plane
+///
+/// Circles are only kept, if the transformation preserves their shape.
+/// Otherwise, they become ellipses.
+fn transform_edges(transform: &fj::Transform2d, edges: Edges) -> Edges {
+    let linear = linear(transform);
+    let similarity = is_similarity(&linear);
+
+    // Circles always go counter-clockwise. If the transformation mirrors them,
+    // their edges need to be reversed.
+    let mirrors = linear.determinant() < 0.;
+
+    let cycles = edges
+        .cycles
+        .into_iter()
+        .map(|cycle| Cycle {
+            edges: cycle
+                .edges
+                .into_iter()
+                .map(|edge| {
+                    let vertices = edge.vertices.map(|vertices| {
+                        vertices
+                            .map(|vertex| transform_point(transform, vertex))
+                    });
+
+                    let curve = match edge.curve {
+                        Curve::Bezier(Bezier { points }) => {
+                            Curve::Bezier(Bezier {
+                                points: points.map(|point| {
+                                    transform_point(transform, point)
+                                }),
+                            })
+                        }
+                        Curve::Line(Line { a, b }) => Curve::Line(Line {
+                            a: transform_point(transform, a),
+                            b: transform_point(transform, b),
+                        }),
+                        Curve::Circle(circle) if similarity => {
+                            let curve = Curve::Circle(Circle {
+                                center: transform_point(
+                                    transform,
+                                    circle.center,
+                                ),
+                                radius: transform_vector(
+                                    &linear,
+                                    circle.radius,
+                                ),
+                                normal: Vector::z(),
+                            });
+
+                            if mirrors {
+                                return Edge {
+                                    curve,
+                                    vertices: vertices.map(|[a, b]| [b, a]),
+                                    reverse: !edge.reverse,
+                                };
+                            }
+
+                            curve
+                        }
+                        Curve::Circle(circle) => {
+                            Curve::Ellipse(transform_ellipse(
+                                transform,
+                                &linear,
+                                Ellipse::from_circle(circle),
+                            ))
+                        }
+                        Curve::Ellipse(ellipse) => Curve::Ellipse(
+                            transform_ellipse(transform, &linear, ellipse),
+                        ),
+                    };
+
+                    // Lines, Bézier curves and ellipses are transformed as a
+                    // whole, including their direction. Their edges stay as
+                    // they are.
+                    Edge {
+                        curve,
+                        vertices,
+                        reverse: edge.reverse,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    Edges { cycles }
+}
+
+/// Transform an ellipse
+///
+/// Any affine transformation of an ellipse is still an ellipse, so this is
+/// always exact.
+fn transform_ellipse(
+    transform: &fj::Transform2d,
+    linear: &Matrix2<f64>,
+    ellipse: Ellipse,
+) -> Ellipse {
+    Ellipse {
+        center: transform_point(transform, ellipse.center),
+        a: transform_vector(linear, ellipse.a),
+        b: transform_vector(linear, ellipse.b),
+    }
+}
+
+fn matrix(transform: &fj::Transform2d) -> Matrix3<f64> {
+    Matrix3::from_column_slice(&transform.matrix)
+}
+
+fn linear(transform: &fj::Transform2d) -> Matrix2<f64> {
+    matrix(transform).fixed_slice::<2, 2>(0, 0).into_owned()
+}
+
+/// Indicates whether the transformation preserves shapes
+///
+/// This is the case, if it consists of only rotation, translation, uniform
+/// scaling, and mirroring.
+fn is_similarity(linear: &Matrix2<f64>) -> bool {
+    let squared = linear.transpose() * linear;
+    let epsilon = 1e-12 * squared.trace();
+
+    squared[(0, 1)].abs() < epsilon
+        && (squared[(0, 0)] - squared[(1, 1)]).abs() < epsilon
+}
+
+/// Transform a point, leaving its z coordinate as it is
+fn transform_point(transform: &fj::Transform2d, point: Point<3>) -> Point<3> {
+    let transformed = matrix(transform).transform_point(&point.xy());
+    point![transformed.x, transformed.y, point.z]
+}
+
+fn transform_vector(linear: &Matrix2<f64>, vector: Vector<3>) -> Vector<3> {
+    let transformed = linear * vector.xy();
+    vector![transformed.x, transformed.y, vector.z]
+}
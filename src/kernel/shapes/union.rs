@@ -3,6 +3,7 @@ use parry3d_f64::bounding_volume::{BoundingVolume as _, AABB};
 use crate::{
     debug::DebugInfo,
     kernel::{
+        algorithms::{csg, feature_edges},
         topology::{edges::Edges, faces::Faces},
         Shape,
     },
@@ -21,23 +22,14 @@ impl Shape for fj::Union {
         let a = self.a.faces(tolerance, debug_info);
         let b = self.b.faces(tolerance, debug_info);
 
-        // This doesn't create a true union, as it doesn't eliminate, merge, or
-        // split faces.
-        //
-        // See issue:
-        // https://github.com/hannobraun/Fornjot/issues/42
-        let mut faces = Vec::new();
-        faces.extend(a.0);
-        faces.extend(b.0);
-
-        Faces(faces)
+        csg::union(a, b, tolerance, debug_info)
     }
 
     fn edges(&self) -> Edges {
-        todo!()
+        feature_edges::of_shape(self)
     }
 
     fn vertices(&self) -> Vec<Point<3>> {
-        todo!()
+        self.edges().vertices()
     }
 }
@@ -0,0 +1,35 @@
+use parry3d_f64::bounding_volume::{BoundingVolume as _, AABB};
+
+use crate::{
+    debug::DebugInfo,
+    kernel::{
+        algorithms::csg_2d,
+        topology::{edges::Edges, faces::Faces},
+        Shape,
+    },
+    math::Point,
+};
+
+impl Shape for fj::Union2d {
+    fn bounding_volume(&self) -> AABB {
+        let a = self.a.bounding_volume();
+        let b = self.b.bounding_volume();
+
+        a.merged(&b)
+    }
+
+    fn faces(&self, tolerance: f64, debug_info: &mut DebugInfo) -> Faces {
+        let a = self.a.faces(tolerance, debug_info);
+        let b = self.b.faces(tolerance, debug_info);
+
+        csg_2d::union(a, b, tolerance)
+    }
+
+    fn edges(&self) -> Edges {
+        csg_2d::shape_edges(self)
+    }
+
+    fn vertices(&self) -> Vec<Point<3>> {
+        self.edges().vertices()
+    }
+}
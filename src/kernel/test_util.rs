@@ -0,0 +1,122 @@
+//! Helpers for the tests of the kernel
+
+use std::collections::HashMap;
+
+use parry3d_f64::shape::Triangle;
+
+use crate::{debug::DebugInfo, math::Point};
+
+use super::{
+    topology::faces::{Face, Faces},
+    Shape,
+};
+
+/// The tolerance that shapes are approximated with in tests
+pub const TOLERANCE: f64 = 0.001;
+
+/// Approximate the faces of a shape with triangles
+pub fn triangles(shape: &impl Shape) -> Vec<Triangle> {
+    let mut triangles = Vec::new();
+    shape.faces(TOLERANCE, &mut DebugInfo::new()).triangles(
+        TOLERANCE,
+        &mut triangles,
+        &mut DebugInfo::new(),
+    );
+
+    triangles
+}
+
+/// Compute the volume that is enclosed by the faces of a shape
+///
+/// Only correct for shapes whose faces are closed, and point outward.
+pub fn volume(shape: &impl Shape) -> f64 {
+    triangles(shape)
+        .iter()
+        .map(|Triangle { a, b, c }| a.coords.dot(&b.coords.cross(&c.coords)))
+        .sum::<f64>()
+        / 6.
+}
+
+/// Compute the area of 2-dimensional faces
+pub fn area(faces: &Faces) -> f64 {
+    let mut triangles = Vec::new();
+    faces.triangles(TOLERANCE, &mut triangles, &mut DebugInfo::new());
+
+    triangles.iter().map(|triangle| triangle.area()).sum()
+}
+
+/// Count the edges of faces that aren't shared with another triangle
+///
+/// The faces are approximated with triangles first. A closed shape has no such
+/// edges, and neither gaps nor T-junctions between its triangles.
+pub fn open_edges(faces: &Faces) -> usize {
+    let mut triangles = Vec::new();
+    faces.triangles(TOLERANCE, &mut triangles, &mut DebugInfo::new());
+
+    let key = |point: Point<3>| point.coords.map(f64::to_bits);
+
+    let mut edges = HashMap::new();
+    for Triangle { a, b, c } in triangles {
+        for [p, q] in [[a, b], [b, c], [c, a]] {
+            *edges.entry([key(p), key(q)]).or_insert(0) += 1;
+            *edges.entry([key(q), key(p)]).or_insert(0) -= 1;
+        }
+    }
+
+    edges.values().filter(|&&count| count > 0).count()
+}
+
+/// Count the cycles of the edges of faces
+pub fn cycles(faces: &Faces) -> usize {
+    faces
+        .0
+        .iter()
+        .map(|face| match face {
+            Face::Face { edges, .. } => edges.cycles.len(),
+            Face::Triangles(_) => 0,
+        })
+        .sum()
+}
+
+/// Count the arcs among the edges of faces
+pub fn arcs(faces: &Faces) -> usize {
+    faces
+        .0
+        .iter()
+        .map(|face| match face {
+            Face::Face { edges, .. } => edges
+                .cycles
+                .iter()
+                .flat_map(|cycle| &cycle.edges)
+                .filter(|edge| edge.is_arc())
+                .count(),
+            Face::Triangles(_) => 0,
+        })
+        .sum()
+}
+
+/// Create the face of a square in the x-y plane
+pub fn square(min: [f64; 2], size: f64) -> Faces {
+    rectangle(min, [size, size])
+}
+
+/// Create the face of a rectangle in the x-y plane
+pub fn rectangle([x, y]: [f64; 2], [w, h]: [f64; 2]) -> Faces {
+    // Clockwise, to make sure the algorithms don't depend on orientation.
+    fj::Sketch::from_points(vec![
+        [x, y],
+        [x, y + h],
+        [x + w, y + h],
+        [x + w, y],
+    ])
+    .faces(TOLERANCE, &mut DebugInfo::new())
+}
+
+/// Create the face of a circle in the x-y plane
+pub fn circle(radius: f64) -> Faces {
+    fj::Circle { radius }.faces(TOLERANCE, &mut DebugInfo::new())
+}
+
+pub fn assert_approx(a: f64, b: f64, epsilon: f64) {
+    assert!((a - b).abs() < epsilon, "{} != {}", a, b);
+}
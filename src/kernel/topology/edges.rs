@@ -1,9 +1,11 @@
+use std::f64::consts::PI;
+
 use nalgebra::vector;
-use parry3d_f64::{math::Isometry, shape::Segment};
+use parry3d_f64::{bounding_volume::AABB, math::Isometry, shape::Segment};
 
 use crate::{
-    kernel::geometry::{Circle, Curve},
-    math::Point,
+    kernel::geometry::{Bezier, Circle, Curve, Ellipse, Line},
+    math::{Point, Vector},
 };
 
 /// The edges of a shape
@@ -35,6 +37,26 @@ impl Edges {
         for cycle in &mut self.cycles {
             for edge in &mut cycle.edges {
                 edge.curve = edge.curve.transform(transform);
+                edge.vertices = edge.vertices.map(|vertices| {
+                    vertices.map(|vertex| transform.transform_point(&vertex))
+                });
+            }
+        }
+
+        self
+    }
+
+    /// Scale the edges uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the edges through the origin.
+    #[must_use]
+    pub fn scale(mut self, factor: f64) -> Self {
+        for cycle in &mut self.cycles {
+            for edge in &mut cycle.edges {
+                edge.curve = edge.curve.scale(factor);
+                edge.vertices = edge
+                    .vertices
+                    .map(|vertices| vertices.map(|vertex| vertex * factor));
             }
         }
 
@@ -75,6 +97,93 @@ impl Edges {
 
         Approx { vertices, segments }
     }
+
+    /// Replace all edges with lines that approximate them
+    ///
+    /// `tolerance` defines how far the lines are allowed to deviate from the
+    /// actual edges.
+    pub fn to_lines(&self, tolerance: f64) -> Self {
+        let cycles = self
+            .cycles
+            .iter()
+            .map(|cycle| Cycle {
+                edges: cycle
+                    .approx(tolerance)
+                    .segments
+                    .into_iter()
+                    .map(|segment| {
+                        Edge::new(Curve::Line(Line {
+                            a: segment.a,
+                            b: segment.b,
+                        }))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { cycles }
+    }
+
+    /// Compute the bounding volume of the edges
+    pub fn bounding_volume(&self) -> AABB {
+        let mut points = Vec::new();
+
+        for edge in self.cycles.iter().flat_map(|cycle| &cycle.edges) {
+            match (edge.curve, edge.vertices) {
+                (Curve::Bezier(bezier), vertices) => {
+                    // The curve is within the convex hull of its control
+                    // points.
+                    let section = vertices.map_or(bezier, |vertices| {
+                        bezier.section_between(vertices)
+                    });
+                    points.extend(section.points);
+                }
+                (Curve::Line(Line { a, b }), _) => points.extend([a, b]),
+                (Curve::Circle(circle), vertices) => {
+                    // A circle is an ellipse with equal semi-axes. The points
+                    // that bound it depend on the orientation of its plane.
+                    points.extend(ellipse_extremes(
+                        &Ellipse::from_circle(circle),
+                        vertices,
+                    ));
+                }
+                (Curve::Ellipse(ellipse), vertices) => {
+                    points.extend(ellipse_extremes(&ellipse, vertices));
+                }
+            }
+        }
+
+        AABB::from_points(&points)
+    }
+
+    /// Compute the vertices that bound the edges
+    ///
+    /// Each vertex is only returned once, even if it bounds multiple edges.
+    /// Edges that are connected to themselves, like full circles, don't have
+    /// any vertices.
+    pub fn vertices(&self) -> Vec<Point<3>> {
+        let mut vertices: Vec<Point<3>> = Vec::new();
+
+        for cycle in &self.cycles {
+            for edge in &cycle.edges {
+                // Both vertices of each edge are taken into account, as the end
+                // of an open cycle doesn't connect to another edge.
+                for vertex in edge.vertices.into_iter().flatten() {
+                    // Vertices that are shared between edges might have been
+                    // computed separately for each of them, so we can't expect
+                    // them to be exactly identical.
+                    let is_duplicate = vertices
+                        .iter()
+                        .any(|other| (other - vertex).magnitude() < 1e-7);
+                    if !is_duplicate {
+                        vertices.push(vertex);
+                    }
+                }
+            }
+        }
+
+        vertices
+    }
 }
 
 /// A cycle of connected edges
@@ -82,6 +191,13 @@ impl Edges {
 /// The end of each edge in the cycle must connect to the beginning of the next
 /// edge. The end of the last edge must connect to the beginning of the first
 /// one.
+///
+/// The only exception are the seams of a shape, where two of its side faces
+/// meet, like the lines that connect the corners of a loft's profiles. They
+/// don't bound a face, and are returned from [`Shape::edges`] as open cycles,
+/// one per seam.
+///
+/// [`Shape::edges`]: crate::kernel::Shape::edges
 #[derive(Clone)]
 pub struct Cycle {
     pub edges: Vec<Edge>,
@@ -109,6 +225,14 @@ impl Cycle {
 
         Approx { vertices, segments }
     }
+
+    /// Reverse the direction of the cycle
+    pub fn reverse(&mut self) {
+        self.edges.reverse();
+        for edge in &mut self.edges {
+            edge.reverse();
+        }
+    }
 }
 
 /// An edge of a shape
@@ -119,16 +243,15 @@ pub struct Edge {
     /// The edge is a segment of the curve that is bounded by two vertices.
     pub curve: Curve,
 
-    /// The vertices that bound this edge on the curve, in curve coordinates
+    /// The vertices that bound this edge on the curve
     ///
     /// If there are no such vertices, that means the edge is connected to
     /// itself (like a full circle, for example).
     ///
-    /// This field is a placeholder. Eventually, there will be actual vertices
-    /// here. For now, this field just tracks whether there are such bounding
-    /// vertices or not. If there are, they are implicitly assumed to be the
-    /// points with the curve coordinates `0` and `1`.
-    pub vertices: Option<[(); 2]>,
+    /// Otherwise, the edge goes from the first vertex to the second one, in
+    /// the direction of the curve. Both vertices must be on the curve. For
+    /// lines, they must be identical to the points that define the line.
+    pub vertices: Option<[Point<3>; 2]>,
 
     /// Indicates whether the curve's direction is reversed
     ///
@@ -139,24 +262,42 @@ pub struct Edge {
 }
 
 impl Edge {
-    /// Construct an edge
+    /// Construct an edge that covers a whole curve
+    ///
+    /// Lines and Bézier curves are bounded by the points that define them.
+    /// Circles and ellipses are not bounded, and connect to themselves.
     pub fn new(curve: Curve) -> Self {
+        let vertices = match curve {
+            Curve::Bezier(Bezier { points }) => Some([points[0], points[3]]),
+            Curve::Line(Line { a, b }) => Some([a, b]),
+            Curve::Circle(_) | Curve::Ellipse(_) => None,
+        };
+
         Self {
             curve,
-            vertices: Some([(), ()]),
+            vertices,
             reverse: false,
         }
     }
 
-    /// Create an arc
+    /// Construct an edge that is bounded by two vertices on the curve
     ///
-    /// So far, the name of this method is a bit ambitious, as only full circles
-    /// are supported.
+    /// The edge goes from `a` to `b`, in the direction of the curve.
+    pub fn with_vertices(curve: Curve, [a, b]: [Point<3>; 2]) -> Self {
+        Self {
+            curve,
+            vertices: Some([a, b]),
+            reverse: false,
+        }
+    }
+
+    /// Create a full circle around the origin
     pub fn arc(radius: f64) -> Self {
         Self {
             curve: Curve::Circle(Circle {
                 center: Point::origin(),
                 radius: vector![radius, 0., 0.],
+                normal: Vector::z(),
             }),
             vertices: None,
             reverse: false,
@@ -168,13 +309,58 @@ impl Edge {
         self.reverse = !self.reverse;
     }
 
+    /// Move one of the edge's vertices to exactly the provided point
+    ///
+    /// `i` is the index of the vertex, in the direction of the curve. The point
+    /// must be on the curve (or close enough to it, that the difference
+    /// doesn't matter). Does nothing, if the edge has no vertices.
+    pub fn snap_vertex(&mut self, i: usize, point: Point<3>) {
+        let mut previous = None;
+        if let Some(vertices) = &mut self.vertices {
+            previous = Some(vertices[i]);
+            vertices[i] = point;
+        }
+
+        match &mut self.curve {
+            Curve::Line(line) => {
+                if i == 0 {
+                    line.a = point;
+                } else {
+                    line.b = point;
+                }
+            }
+            Curve::Bezier(bezier) => {
+                // Only move the end of the curve, if the vertex was there.
+                // Otherwise, the shape of the rest of the curve would change.
+                let end = if i == 0 { 0 } else { 3 };
+                if previous == Some(bezier.points[end]) {
+                    bezier.points[end] = point;
+                }
+            }
+            Curve::Circle(_) | Curve::Ellipse(_) => {}
+        }
+    }
+
+    /// Indicates whether the edge is a section of a circle or an ellipse
+    ///
+    /// Full circles and ellipses don't count.
+    pub fn is_arc(&self) -> bool {
+        matches!(self.curve, Curve::Circle(_) | Curve::Ellipse(_))
+            && self.vertices.is_some()
+    }
+
     /// Compute an approximation of the edge
     ///
     /// `tolerance` defines how far the approximation is allowed to deviate from
     /// the actual edge.
     pub fn approx(&self, tolerance: f64) -> Approx {
         let mut vertices = Vec::new();
-        self.curve.approx(tolerance, &mut vertices);
+        match self.vertices {
+            Some(bounds) => {
+                self.curve.approx_between(tolerance, bounds, &mut vertices)
+            }
+            None => self.curve.approx(tolerance, &mut vertices),
+        }
 
         if self.reverse {
             vertices.reverse()
@@ -207,3 +393,34 @@ pub struct Approx {
     pub vertices: Vec<Point<3>>,
     pub segments: Vec<Segment>,
 }
+
+/// Compute the points that bound an ellipse, or an arc of it
+///
+/// This works for circles too, via [`Ellipse::from_circle`]. Arcs are bounded
+/// by their vertices, and by the points of the ellipse that are furthest along
+/// the coordinate axes, if the arc includes them.
+fn ellipse_extremes(
+    ellipse: &Ellipse,
+    vertices: Option<[Point<3>; 2]>,
+) -> Vec<Point<3>> {
+    // Along each axis, the ellipse is furthest from its center where the
+    // derivative of that coordinate is zero.
+    let extremes = (0..3).flat_map(|i| {
+        let t = ellipse.b[i].atan2(ellipse.a[i]);
+        [t, t + PI].map(|t| ellipse.point_curve_to_model(t))
+    });
+
+    match vertices {
+        Some([a, b]) => {
+            let angle = ellipse.arc_angle([a, b]);
+
+            let mut points = vec![a, b];
+            points
+                .extend(extremes.filter(|&extreme| {
+                    ellipse.arc_angle([a, extreme]) < angle
+                }));
+            points
+        }
+        None => extremes.collect(),
+    }
+}
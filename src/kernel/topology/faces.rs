@@ -1,11 +1,5 @@
-use std::collections::BTreeSet;
-
-use decorum::R64;
-use parry2d_f64::{
-    bounding_volume::AABB,
-    query::{Ray as Ray2, RayCast as _},
-    shape::Segment as Segment2,
-};
+use nalgebra::vector;
+use parry2d_f64::query::Ray as Ray2;
 use parry3d_f64::{
     math::Isometry,
     query::Ray as Ray3,
@@ -36,6 +30,42 @@ impl Faces {
         Self(faces)
     }
 
+    /// Scale all the faces uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the faces through the origin. They keep
+    /// pointing away from the same side.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        Self(self.0.into_iter().map(|face| face.scale(factor)).collect())
+    }
+
+    /// Reverse the orientation of all the faces
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self(self.0.into_iter().map(Face::reverse).collect())
+    }
+
+    /// Replace all edges of the faces with lines that approximate them
+    ///
+    /// `tolerance` defines how far the lines are allowed to deviate from the
+    /// actual edges. Faces that are represented as triangles are not changed.
+    #[must_use]
+    pub fn to_lines(&self, tolerance: f64) -> Self {
+        let faces = self
+            .0
+            .iter()
+            .map(|face| match face {
+                Face::Face { edges, surface } => Face::Face {
+                    edges: edges.to_lines(tolerance),
+                    surface: surface.clone(),
+                },
+                face => face.clone(),
+            })
+            .collect();
+
+        Self(faces)
+    }
+
     pub fn triangles(
         &self,
         tolerance: f64,
@@ -100,6 +130,54 @@ impl Face {
         }
     }
 
+    /// Scale the face uniformly, relative to the origin
+    ///
+    /// A negative factor also mirrors the face through the origin. It keeps
+    /// pointing away from the same side.
+    #[must_use]
+    pub fn scale(self, factor: f64) -> Self {
+        match self {
+            Self::Face { edges, surface } => Self::Face {
+                edges: edges.scale(factor),
+                surface: surface.scale(factor),
+            },
+            Self::Triangles(triangles) => {
+                // Mirroring through the origin inverts the orientation of the
+                // triangles, so they need to be flipped.
+                let triangles = triangles.into_iter().map(|triangle| {
+                    let [a, b, c] = triangle.vertices().map(|v| v * factor);
+                    if factor < 0. {
+                        Triangle::new(a, c, b)
+                    } else {
+                        Triangle::new(a, b, c)
+                    }
+                });
+
+                Self::Triangles(triangles.collect())
+            }
+        }
+    }
+
+    /// Reverse the orientation of the face
+    ///
+    /// The reversed face covers the same area, but points in the opposite
+    /// direction.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Face { edges, surface } => Self::Face {
+                edges,
+                surface: surface.reverse(),
+            },
+            Self::Triangles(triangles) => Self::Triangles(
+                triangles
+                    .into_iter()
+                    .map(|Triangle { a, b, c }| Triangle { a, b: c, c: b })
+                    .collect(),
+            ),
+        }
+    }
+
     pub fn triangles(
         &self,
         tolerance: f64,
@@ -107,7 +185,32 @@ impl Face {
         debug_info: &mut DebugInfo,
     ) {
         match self {
-            Self::Face { edges, surface } => {
+            Self::Face {
+                surface: Surface::Revolved(surface),
+                ..
+            } => {
+                // Faces on curved surfaces always cover the whole surface, so
+                // the edges don't need to be taken into account.
+                surface.approx(tolerance, out);
+            }
+            Self::Face {
+                surface: Surface::Sphere(sphere),
+                ..
+            } => {
+                // Same as above.
+                sphere.approx(tolerance, out);
+            }
+            Self::Face {
+                surface: Surface::Torus(torus),
+                ..
+            } => {
+                // Same as above.
+                torus.approx(tolerance, out);
+            }
+            Self::Face {
+                edges,
+                surface: surface @ Surface::Plane(plane),
+            } => {
                 let approx = edges.approx(tolerance);
 
                 let vertices: Vec<_> = approx
@@ -133,13 +236,6 @@ impl Face {
                     })
                     .collect();
 
-                // We're also going to need a point outside of the polygon, for
-                // the point-in-polygon tests.
-                let aabb = AABB::from_points(
-                    vertices.iter().map(|vertex| &vertex.value),
-                );
-                let outside = aabb.maxs * 2.;
-
                 let mut triangles = triangulate(vertices);
                 let face_as_polygon = segments;
 
@@ -167,19 +263,13 @@ impl Face {
 
                         let ray = Ray2 {
                             origin: center,
-                            dir: outside - center,
+                            dir: vector![1., 0.],
                         };
                         let mut check = TriangleEdgeCheck::new(Ray3 {
                             origin: surface.point_surface_to_model(ray.origin),
-                            dir: surface.vector_surface_to_model(ray.dir),
+                            dir: plane.vector_surface_to_model(ray.dir),
                         });
 
-                        // We need to keep track of where our ray hits the
-                        // edges. Otherwise, if the ray hits a vertex, we might
-                        // count that hit twice, as every vertex is attached to
-                        // two edges.
-                        let mut hits = BTreeSet::new();
-
                         // Use ray-casting to determine if `center` is within
                         // the face-polygon.
                         for edge in &face_as_polygon {
@@ -188,29 +278,28 @@ impl Face {
                             // check above. We don't need to handle any edge
                             // cases that would arise from that case.
 
-                            let edge =
-                                Segment2::from(edge.map(|point| point.value));
-
-                            let intersection =
-                                edge.cast_local_ray(&ray, f64::INFINITY, true);
+                            let [a, b] = edge.map(|point| point.value);
 
-                            if let Some(t) = intersection {
-                                // Due to slight inaccuracies, we might get
-                                // different values for the same intersections.
-                                // Let's round `t` before using it.
-                                let eps = 1_000_000.0;
-                                let t = (t * eps).round() / eps;
+                            // Every edge includes the vertex at only one of its
+                            // ends, from the perspective of the ray. That way,
+                            // if the ray hits a vertex, it is counted once if
+                            // the polygon boundary crosses the ray there, and
+                            // not at all (or twice) if it only touches it.
+                            if (a.y > center.y) == (b.y > center.y) {
+                                continue;
+                            }
 
-                                let t_r64: R64 = t.into();
-                                if hits.insert(t_r64) {
-                                    check.hits.push(t);
-                                }
+                            let x = a.x
+                                + (center.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                            if x > center.x {
+                                check.hits.push(x - center.x);
                             }
                         }
 
+                        let is_inside = check.hits.len() % 2 == 1;
                         debug_info.triangle_edge_checks.push(check);
 
-                        if hits.len() % 2 == 0 {
+                        if !is_inside {
                             // The segment is outside of the face. This means we
                             // can throw away the whole triangle.
                             return false;
@@ -231,3 +320,43 @@ impl Face {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{debug::DebugInfo, kernel::Shape as _};
+
+    const TOLERANCE: f64 = 0.001;
+
+    #[test]
+    fn triangles_of_polygon_with_vertices_on_ray() {
+        // A crown with notches. The rays cast from the inside of the polygon
+        // pass through the vertices at the bottom of the notches, where the
+        // boundary touches the ray without crossing it.
+        let crown = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [4., 0.],
+            [4., 2.],
+            [3., 1.],
+            [2., 2.],
+            [1., 1.],
+            [0., 2.],
+        ]);
+
+        assert_approx(area(crown), 6.);
+    }
+
+    fn area(sketch: fj::Sketch) -> f64 {
+        let mut triangles = Vec::new();
+        sketch.faces(TOLERANCE, &mut DebugInfo::new()).triangles(
+            TOLERANCE,
+            &mut triangles,
+            &mut DebugInfo::new(),
+        );
+
+        triangles.iter().map(|triangle| triangle.area()).sum()
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+}
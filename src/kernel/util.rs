@@ -1,4 +1,5 @@
 use parry2d_f64::utils::point_in_triangle::{corner_direction, Orientation};
+use parry3d_f64::bounding_volume::AABB;
 use spade::HasPosition;
 
 use super::geometry::points::SurfacePoint;
@@ -31,6 +32,39 @@ pub fn triangulate(points: Vec<SurfacePoint>) -> Vec<[SurfacePoint; 3]> {
     triangles
 }
 
+/// Compute a reasonable default tolerance for a shape
+///
+/// To do this, we just look at the smallest non-zero extent of the shape's
+/// bounding box and divide that by some value.
+pub fn default_tolerance(aabb: &AABB) -> f64 {
+    let mut min_extent = f64::MAX;
+    for &extent in aabb.extents().iter() {
+        if extent > 0. && extent < min_extent {
+            min_extent = extent;
+        }
+    }
+
+    // `tolerance` must not be zero, or we'll run into trouble.
+    let tolerance = min_extent / 1000.;
+    assert!(tolerance > 0.);
+
+    tolerance
+}
+
+/// Compute the intersection of two axis-aligned bounding boxes
+///
+/// If the bounding boxes don't overlap, an empty bounding box is returned.
+pub fn aabb_intersection(a: &AABB, b: &AABB) -> AABB {
+    let mins = a.mins.sup(&b.mins);
+    let maxs = a.maxs.inf(&b.maxs);
+
+    if mins.iter().zip(maxs.iter()).any(|(min, max)| min > max) {
+        return AABB::new(mins, mins);
+    }
+
+    AABB::new(mins, maxs)
+}
+
 // Enables the use of `SurfacePoint` in the triangulation.
 impl HasPosition for SurfacePoint {
     type Scalar = f64;
@@ -26,7 +26,7 @@ use crate::{
     camera::Camera,
     debug::DebugInfo,
     graphics::{DrawConfig, Renderer},
-    kernel::Shape as _,
+    kernel::{util::default_tolerance, Shape as _},
     mesh::{HashVector, MeshMaker},
     model::Model,
     window::Window,
@@ -65,23 +65,7 @@ fn main() -> anyhow::Result<()> {
 
     let mut aabb = shape.bounding_volume();
 
-    // Compute a reasonable default for the tolerance value. To do this, we just
-    // look at the smallest non-zero extent of the bounding box and divide that
-    // by some value.
-    let tolerance = {
-        let mut min_extent = f64::MAX;
-        for &extent in aabb.extents().iter() {
-            if extent > 0. && extent < min_extent {
-                min_extent = extent;
-            }
-        }
-
-        // `tolerance` must not be zero, or we'll run into trouble.
-        let tolerance = min_extent / 1000.;
-        assert!(tolerance > 0.);
-
-        tolerance
-    };
+    let tolerance = default_tolerance(&aabb);
 
     let mut debug_info = DebugInfo::new();
     let faces = shape.faces(tolerance, &mut debug_info);
@@ -168,6 +152,11 @@ fn main() -> anyhow::Result<()> {
                         println!("Error compiling model");
                         return;
                     }
+                    Err(err @ model::Error::Sketch(_)) => {
+                        // Same as above. This should be displayed in the UI.
+                        println!("{}", err);
+                        return;
+                    }
                     Err(err) => {
                         panic!("Error reloading model: {:?}", err);
                     }
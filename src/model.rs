@@ -2,6 +2,8 @@ use std::{collections::HashMap, io, path::PathBuf, process::Command};
 
 use thiserror::Error;
 
+use crate::kernel::shapes::{on_plane, shell, sweep};
+
 pub struct Model {
     name: String,
 }
@@ -67,16 +69,157 @@ impl Model {
         // I don't know of a way to fix this. We should take this as motivation
         // to switch to a better technique:
         // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
+        let mut shape = unsafe {
             let lib = libloading::Library::new(self.lib_path())?;
             let model: libloading::Symbol<ModelFn> = lib.get(b"model")?;
             model(&arguments)
         };
 
+        // The kernel assumes that sketches and the shapes made from them are
+        // valid, so we need to make sure that they are, before anyone tries to
+        // evaluate the shape.
+        validate(&shape)?;
+
+        // Placing shapes on the faces of other shapes requires evaluating
+        // those. That is done once here, instead of whenever the shape is
+        // evaluated.
+        on_plane::place(&mut shape);
+
         Ok(shape)
     }
 }
 
+/// Validate a shape, and all sketches that are part of it
+fn validate(shape: &fj::Shape) -> Result<(), Error> {
+    match shape {
+        fj::Shape::Shape2d(shape) => validate_2d(shape),
+        fj::Shape::Shape3d(shape) => validate_3d(shape),
+    }
+}
+
+fn validate_2d(shape: &fj::Shape2d) -> Result<(), Error> {
+    match shape {
+        fj::Shape2d::Circle(_) => Ok(()),
+        fj::Shape2d::CircularPattern(shape) => validate_2d(&shape.shape),
+        fj::Shape2d::Difference(shape) => {
+            validate_2d(&shape.a)?;
+            validate_2d(&shape.b)
+        }
+        fj::Shape2d::Ellipse(_) => Ok(()),
+        fj::Shape2d::Intersection(shape) => {
+            validate_2d(&shape.a)?;
+            validate_2d(&shape.b)
+        }
+        fj::Shape2d::LinearPattern(shape) => validate_2d(&shape.shape),
+        fj::Shape2d::Offset(shape) => validate_2d(&shape.shape),
+        fj::Shape2d::OnPlane(shape) => {
+            if let fj::Plane::Face(face) = &shape.plane {
+                validate_3d(&face.shape)?;
+            }
+            validate_2d(&shape.shape)
+        }
+        fj::Shape2d::Sketch(shape) => Ok(shape.validate()?),
+        fj::Shape2d::Text(_) => Ok(()),
+        fj::Shape2d::Transform(shape) => validate_2d(&shape.shape),
+        fj::Shape2d::Union(shape) => {
+            validate_2d(&shape.a)?;
+            validate_2d(&shape.b)
+        }
+    }
+}
+
+fn validate_3d(shape: &fj::Shape3d) -> Result<(), Error> {
+    match shape {
+        fj::Shape3d::Chamfer(_) | fj::Shape3d::Fillet(_) => {
+            validate_edge_modifications(shape)
+        }
+        fj::Shape3d::CircularPattern(shape) => validate_3d(&shape.shape),
+        fj::Shape3d::Cone(_)
+        | fj::Shape3d::Cylinder(_)
+        | fj::Shape3d::Sphere(_)
+        | fj::Shape3d::Torus(_) => Ok(()),
+        fj::Shape3d::Difference(shape) => {
+            validate_3d(&shape.a)?;
+            validate_3d(&shape.b)
+        }
+        fj::Shape3d::Intersection(shape) => {
+            validate_3d(&shape.a)?;
+            validate_3d(&shape.b)
+        }
+        fj::Shape3d::LinearPattern(shape) => validate_3d(&shape.shape),
+        fj::Shape3d::Loft(shape) => shape
+            .to_profiles()
+            .iter()
+            .try_for_each(|profile| validate_2d(&profile.shape)),
+        fj::Shape3d::PathSweep(shape) => validate_2d(&shape.shape),
+        fj::Shape3d::Revolve(shape) => validate_2d(&shape.shape),
+        fj::Shape3d::Shell(shape) => {
+            validate_3d(&shape.shape)?;
+            if sweep::straight_sweep(&shape.shape).is_none() {
+                return Err(Error::UnsupportedShell);
+            }
+            if !shell::walls_fit(shape) {
+                return Err(Error::WallsTooThick);
+            }
+            Ok(())
+        }
+        fj::Shape3d::Sweep(shape) => {
+            validate_2d(&shape.shape)?;
+            if !sweep::draft_fits(shape) {
+                return Err(Error::DraftTooLarge);
+            }
+            Ok(())
+        }
+        fj::Shape3d::Transform(shape) => validate_3d(&shape.shape),
+        fj::Shape3d::Union(shape) => {
+            validate_3d(&shape.a)?;
+            validate_3d(&shape.b)
+        }
+    }
+}
+
+/// Validate a shape with modified edges
+///
+/// Only the edges of sweeps along the z-axis without draft, and of cylinders,
+/// can be modified. Fillets and chamfers can be nested, but the edges of each
+/// face can only be modified once, and the modifications of both faces must
+/// fit into the length of the sweep.
+fn validate_edge_modifications(shape: &fj::Shape3d) -> Result<(), Error> {
+    let mut shape = shape;
+    let mut extents = [None, None];
+    loop {
+        let (edges, extent, inner) = match shape {
+            fj::Shape3d::Chamfer(chamfer) => {
+                (chamfer.edges, chamfer.distance, &chamfer.shape)
+            }
+            fj::Shape3d::Fillet(fillet) => {
+                (fillet.edges, fillet.radius, &fillet.shape)
+            }
+            _ => break,
+        };
+        shape = inner;
+
+        let face = match edges {
+            fj::EdgeSelection::BottomFace => 0,
+            fj::EdgeSelection::TopFace => 1,
+            fj::EdgeSelection::ParallelToZ => continue,
+        };
+        if extents[face].replace(extent).is_some() {
+            return Err(Error::EdgesModifiedTwice);
+        }
+    }
+
+    validate_3d(shape)?;
+
+    let sweep = sweep::straight_sweep(shape)
+        .ok_or(Error::UnsupportedEdgeModification)?;
+    if extents.iter().flatten().sum::<f64>() >= sweep.length.abs() {
+        return Err(Error::SweepTooShort);
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Error compiling model")]
@@ -87,7 +230,91 @@ pub enum Error {
 
     #[error("Error loading model from dynamic library")]
     LibLoading(#[from] libloading::Error),
+
+    #[error("Invalid sketch in model: {0}")]
+    Sketch(#[from] fj::SketchError),
+
+    #[error("Draft angle of sweep is too large for its length")]
+    DraftTooLarge,
+
+    #[error(
+        "Only edges of sweeps along the z-axis without draft, and of \
+        cylinders, can be modified"
+    )]
+    UnsupportedEdgeModification,
+
+    #[error("The edges of a face can only be modified once")]
+    EdgesModifiedTwice,
+
+    #[error("Sweep is too short for its modified edges")]
+    SweepTooShort,
+
+    #[error(
+        "Only sweeps along the z-axis without draft, and cylinders, can be \
+        hollowed"
+    )]
+    UnsupportedShell,
+
+    #[error("Walls of shell are too thick for the shape that is hollowed")]
+    WallsTooThick,
 }
 
 type ModelFn =
     unsafe extern "C" fn(args: &HashMap<String, String>) -> fj::Shape;
+
+#[cfg(test)]
+mod tests {
+    use fj::prelude::*;
+
+    use super::{validate, Error};
+
+    #[test]
+    fn validate_shell() {
+        let open = fj::OpenFaces::Top;
+
+        assert!(validate(&cube().shell(0.25, open).into()).is_ok());
+
+        let shape = fj::Sphere { radius: 1. }.shell(0.25, open);
+        assert!(matches!(
+            validate(&shape.into()),
+            Err(Error::UnsupportedShell)
+        ));
+
+        let shape = cube().shell(0.5, fj::OpenFaces::None);
+        assert!(matches!(validate(&shape.into()), Err(Error::WallsTooThick)));
+
+        // The walls on the sides meet, even though the shell is open.
+        let shape = cube().shell(0.6, fj::OpenFaces::TopAndBottom);
+        assert!(matches!(validate(&shape.into()), Err(Error::WallsTooThick)));
+    }
+
+    #[test]
+    fn validate_edge_modifications() {
+        let top = fj::EdgeSelection::TopFace;
+        let bottom = fj::EdgeSelection::BottomFace;
+        let z = fj::EdgeSelection::ParallelToZ;
+
+        let shape = cube().chamfer(0.25, top).fillet(0.25, z);
+        assert!(validate(&shape.into()).is_ok());
+
+        let shape = fj::Sphere { radius: 1. }.fillet(0.25, top);
+        assert!(matches!(
+            validate(&shape.into()),
+            Err(Error::UnsupportedEdgeModification)
+        ));
+
+        let shape = cube().chamfer(0.25, top).fillet(0.25, top);
+        assert!(matches!(
+            validate(&shape.into()),
+            Err(Error::EdgesModifiedTwice)
+        ));
+
+        let shape = cube().chamfer(0.5, top).fillet(0.5, bottom);
+        assert!(matches!(validate(&shape.into()), Err(Error::SweepTooShort)));
+    }
+
+    fn cube() -> fj::Sweep {
+        fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+            .sweep(1.)
+    }
+}